@@ -0,0 +1,58 @@
+/*
+ *  The MIT License (MIT)
+ *
+ *  Copyright (c) 2024-2025, Peter Vrba
+ *
+ *  Permission is hereby granted, free of charge, to any person obtaining a copy
+ *  of this software and associated documentation files (the "Software"), to deal
+ *  in the Software without restriction, including without limitation the rights
+ *  to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ *  copies of the Software, and to permit persons to whom the Software is
+ *  furnished to do so, subject to the following conditions:
+ *
+ *  The above copyright notice and this permission notice shall be included in
+ *  all copies or substantial portions of the Software.
+ *
+ *  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ *  IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ *  FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ *  AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ *  LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ *  OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+ *  THE SOFTWARE.
+ *
+ */
+
+//! quickapi-derive contains derive macros for quickapi (macro_rules macros live in quickapi-macro).
+
+mod serializer;
+
+use proc_macro::TokenStream;
+use syn::{DeriveInput, parse_macro_input};
+
+/// QuickSerializer derives serializers for sea-orm Model from the annotated (output) struct.
+///
+/// ```ignore
+/// #[derive(Clone, Debug, serde::Serialize, QuickSerializer)]
+/// #[quickapi(model = entity::UserModel, fields(id, username), read_only(id))]
+/// pub struct SimpleUser {
+///     pub id: i32,
+///     pub username: String,
+/// }
+/// ```
+///
+/// generates:
+/// - `From<Model>` for the struct (fields not listed in `fields` are set to default)
+/// - `CreateSimpleUser` input struct (fields except read only) with `From<CreateSimpleUser> for Model`,
+///   read only fields are set to default
+/// - `UpdateSimpleUser` input struct (optional fields except read only) implementing `UpdateModelTrait`,
+///   only fields present in request body are changed
+///
+/// Names of input structs can be changed with `create = Name` and `update = Name`.
+#[proc_macro_derive(QuickSerializer, attributes(quickapi))]
+pub fn derive_quick_serializer(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    serializer::expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
@@ -0,0 +1,297 @@
+/*
+ *  The MIT License (MIT)
+ *
+ *  Copyright (c) 2024-2025, Peter Vrba
+ *
+ *  Permission is hereby granted, free of charge, to any person obtaining a copy
+ *  of this software and associated documentation files (the "Software"), to deal
+ *  in the Software without restriction, including without limitation the rights
+ *  to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ *  copies of the Software, and to permit persons to whom the Software is
+ *  furnished to do so, subject to the following conditions:
+ *
+ *  The above copyright notice and this permission notice shall be included in
+ *  all copies or substantial portions of the Software.
+ *
+ *  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ *  IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ *  FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ *  AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ *  LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ *  OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+ *  THE SOFTWARE.
+ *
+ */
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{Data, DeriveInput, Fields, Ident, Path, Type};
+
+/// Config is parsed from `#[quickapi(...)]` attributes.
+#[derive(Default)]
+struct Config {
+    model: Option<Path>,
+    fields: Option<Vec<Ident>>,
+    read_only: Vec<Ident>,
+    create: Option<Ident>,
+    update: Option<Ident>,
+}
+
+impl Config {
+    /// parse parses all `#[quickapi(...)]` attributes of the input.
+    fn parse(input: &DeriveInput) -> syn::Result<Self> {
+        let mut config = Config::default();
+
+        for attr in input.attrs.iter().filter(|a| a.path().is_ident("quickapi")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("model") {
+                    config.model = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("fields") {
+                    config.fields = Some(parse_idents(&meta)?);
+                } else if meta.path.is_ident("read_only") {
+                    config.read_only.extend(parse_idents(&meta)?);
+                } else if meta.path.is_ident("create") {
+                    config.create = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("update") {
+                    config.update = Some(meta.value()?.parse()?);
+                } else {
+                    return Err(meta.error("unsupported quickapi attribute"));
+                }
+                Ok(())
+            })?;
+        }
+
+        Ok(config)
+    }
+}
+
+/// parse_idents parses list of identifiers, e.g. `fields(id, username)`.
+fn parse_idents(meta: &syn::meta::ParseNestedMeta) -> syn::Result<Vec<Ident>> {
+    let mut idents = Vec::new();
+    meta.parse_nested_meta(|nested| {
+        idents.push(nested.path.require_ident()?.clone());
+        Ok(())
+    })?;
+    Ok(idents)
+}
+
+/// expand generates From<Model> for the struct, create and update input structs.
+pub(crate) fn expand(input: DeriveInput) -> syn::Result<TokenStream> {
+    let config = Config::parse(&input)?;
+    let name = &input.ident;
+    let vis = &input.vis;
+
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.generics,
+            "QuickSerializer does not support generics",
+        ));
+    }
+
+    let Some(model) = config.model else {
+        return Err(syn::Error::new_spanned(
+            name,
+            "missing #[quickapi(model = ...)] attribute",
+        ));
+    };
+
+    let struct_fields: Vec<(Ident, Type)> = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(named) => named
+                .named
+                .iter()
+                .map(|f| (f.ident.clone().expect("named field"), f.ty.clone()))
+                .collect(),
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    name,
+                    "QuickSerializer supports only structs with named fields",
+                ));
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                name,
+                "QuickSerializer supports only structs",
+            ));
+        }
+    };
+
+    // fields default to all struct fields
+    let fields = config.fields.unwrap_or_else(|| {
+        struct_fields
+            .iter()
+            .map(|(ident, _)| ident.clone())
+            .collect()
+    });
+
+    for ident in fields.iter() {
+        if !struct_fields.iter().any(|(f, _)| f == ident) {
+            return Err(syn::Error::new_spanned(
+                ident,
+                format!("field `{}` is not declared in `{}`", ident, name),
+            ));
+        }
+    }
+    for ident in config.read_only.iter() {
+        if !fields.contains(ident) {
+            return Err(syn::Error::new_spanned(
+                ident,
+                format!("read only field `{}` is not listed in fields", ident),
+            ));
+        }
+    }
+
+    // output: fields copied from model, other struct fields are default
+    let output = fields.iter();
+    let output_default = struct_fields
+        .iter()
+        .map(|(ident, _)| ident)
+        .filter(|ident| !fields.contains(ident));
+
+    // input: fields except read only, model columns missing in create input are default (Model: Default)
+    let writable: Vec<&(Ident, Type)> = struct_fields
+        .iter()
+        .filter(|(ident, _)| fields.contains(ident) && !config.read_only.contains(ident))
+        .collect();
+    let writable_idents: Vec<&Ident> = writable.iter().map(|(ident, _)| ident).collect();
+    let writable_types: Vec<&Type> = writable.iter().map(|(_, ty)| ty).collect();
+
+    let create = config
+        .create
+        .unwrap_or_else(|| format_ident!("Create{}", name));
+    let update = config
+        .update
+        .unwrap_or_else(|| format_ident!("Update{}", name));
+    let create_doc = format!("{} is create input for `{}`.", create, name);
+    let update_doc = format!(
+        "{} is partial update input for `{}`, only present fields are changed.",
+        update, name
+    );
+
+    Ok(quote! {
+        impl ::core::convert::From<#model> for #name {
+            fn from(model: #model) -> Self {
+                Self {
+                    #(#output: model.#output,)*
+                    #(#output_default: ::core::default::Default::default(),)*
+                }
+            }
+        }
+
+        #[doc = #create_doc]
+        #[derive(Clone, Debug, ::serde::Serialize, ::serde::Deserialize)]
+        #vis struct #create {
+            #(pub #writable_idents: #writable_types,)*
+        }
+
+        impl ::core::convert::From<#create> for #model {
+            #[allow(clippy::needless_update)]
+            fn from(input: #create) -> Self {
+                Self {
+                    #(#writable_idents: input.#writable_idents,)*
+                    ..::core::default::Default::default()
+                }
+            }
+        }
+
+        #[doc = #update_doc]
+        #[derive(Clone, Debug, Default, ::serde::Serialize, ::serde::Deserialize)]
+        #vis struct #update {
+            #(
+                #[serde(default, skip_serializing_if = "Option::is_none")]
+                pub #writable_idents: ::core::option::Option<#writable_types>,
+            )*
+        }
+
+        impl ::quickapi::prelude::UpdateModelTrait<<#model as ::sea_orm::ModelTrait>::Entity> for #update {
+            #[allow(unused_mut)]
+            fn update(&self, value: #model) -> ::core::result::Result<#model, ::quickapi::ModelError> {
+                let mut value = value;
+                #(
+                    if let ::core::option::Option::Some(v) = &self.#writable_idents {
+                        value.#writable_idents = ::core::clone::Clone::clone(v);
+                    }
+                )*
+                ::core::result::Result::Ok(value)
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expand_str(input: TokenStream) -> syn::Result<String> {
+        expand(syn::parse2(input).unwrap()).map(|tokens| tokens.to_string())
+    }
+
+    #[test]
+    fn test_expand() {
+        let expanded = expand_str(quote! {
+            #[quickapi(model = user::Model, fields(id, username), read_only(id))]
+            pub struct SimpleUser {
+                pub id: i32,
+                pub username: String,
+                pub orders: i64,
+            }
+        })
+        .unwrap();
+
+        assert!(
+            expanded.contains("impl :: core :: convert :: From < user :: Model > for SimpleUser")
+        );
+        assert!(expanded.contains("orders : :: core :: default :: Default :: default ()"));
+        assert!(expanded.contains("pub struct CreateSimpleUser { pub username : String , }"));
+        assert!(expanded.contains(
+            "Self { username : input . username , .. :: core :: default :: Default :: default () }"
+        ));
+        assert!(expanded.contains("pub struct UpdateSimpleUser"));
+        assert!(expanded.contains("pub username : :: core :: option :: Option < String >"));
+        assert!(!expanded.contains("pub id : :: core :: option :: Option"));
+    }
+
+    #[test]
+    fn test_expand_names() {
+        let expanded = expand_str(quote! {
+            #[quickapi(model = Model, create = NewUser, update = PatchUser)]
+            struct User {
+                id: i32,
+            }
+        })
+        .unwrap();
+
+        assert!(expanded.contains("struct NewUser"));
+        assert!(expanded.contains("struct PatchUser"));
+        assert!(expanded.contains("pub id : :: core :: option :: Option < i32 >"));
+    }
+
+    #[test]
+    fn test_expand_errors() {
+        let err = |input: TokenStream| expand_str(input).unwrap_err().to_string();
+
+        assert_eq!(
+            err(quote! { struct User { id: i32 } }),
+            "missing #[quickapi(model = ...)] attribute"
+        );
+        assert_eq!(
+            err(quote! { #[quickapi(model = Model, fields(email))] struct User { id: i32 } }),
+            "field `email` is not declared in `User`"
+        );
+        assert_eq!(
+            err(
+                quote! { #[quickapi(model = Model, fields(id), read_only(name))] struct User { id: i32, name: String } }
+            ),
+            "read only field `name` is not listed in fields"
+        );
+        assert_eq!(
+            err(quote! { #[quickapi(model = Model, unknown)] struct User { id: i32 } }),
+            "unsupported quickapi attribute"
+        );
+        assert_eq!(
+            err(quote! { #[quickapi(model = Model)] struct User(i32); }),
+            "QuickSerializer supports only structs with named fields"
+        );
+    }
+}
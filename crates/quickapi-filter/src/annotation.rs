@@ -0,0 +1,286 @@
+/*
+ *  The MIT License (MIT)
+ *
+ *  Copyright (c) 2024-2025, Peter Vrba
+ *
+ *  Permission is hereby granted, free of charge, to any person obtaining a copy
+ *  of this software and associated documentation files (the "Software"), to deal
+ *  in the Software without restriction, including without limitation the rights
+ *  to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ *  copies of the Software, and to permit persons to whom the Software is
+ *  furnished to do so, subject to the following conditions:
+ *
+ *  The above copyright notice and this permission notice shall be included in
+ *  all copies or substantial portions of the Software.
+ *
+ *  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ *  IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ *  FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ *  AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ *  LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ *  OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+ *  THE SOFTWARE.
+ *
+ */
+
+use axum::http::request::Parts;
+use sea_orm::sea_query::{SelectStatement, SimpleExpr, SubQueryStatement};
+use sea_orm::{
+    ConnectionTrait, DbErr, EntityTrait, FromQueryResult, QueryResult, QuerySelect, QueryTrait,
+    Select, TryGetable,
+};
+use std::sync::Arc;
+
+/// Getter reads annotation value of given name from the row.
+type Getter = Arc<dyn Fn(&QueryResult, &str) -> Result<serde_json::Value, DbErr> + Send + Sync>;
+
+/// Annotation is named SQL expression (or subquery) added to SELECT of the view (column_as).
+/// Its value is returned alongside the model and filters can use it by name (see Annotations::expr).
+#[derive(Clone)]
+pub struct Annotation {
+    name: String,
+    expr: SimpleExpr,
+    getter: Getter,
+}
+
+impl Annotation {
+    /// new creates annotation of type T (e.g. i64, String, Option<String>) from expression.
+    pub fn new<T>(name: impl Into<String>, expr: impl Into<SimpleExpr>) -> Self
+    where
+        T: TryGetable + serde::Serialize + 'static,
+    {
+        Self {
+            name: name.into(),
+            expr: expr.into(),
+            getter: Arc::new(|row: &QueryResult, name: &str| {
+                let value: T = row.try_get("", name)?;
+                serde_json::to_value(value).map_err(|e| DbErr::Type(e.to_string()))
+            }),
+        }
+    }
+
+    /// subquery creates annotation of type T from subquery (e.g. count of related rows).
+    pub fn subquery<T>(name: impl Into<String>, query: SelectStatement) -> Self
+    where
+        T: TryGetable + serde::Serialize + 'static,
+    {
+        Self::new::<T>(
+            name,
+            SimpleExpr::SubQuery(None, Box::new(SubQueryStatement::SelectStatement(query))),
+        )
+    }
+
+    /// name returns name of the annotation.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// expr returns expression of the annotation.
+    pub fn expr(&self) -> &SimpleExpr {
+        &self.expr
+    }
+}
+
+impl std::fmt::Debug for Annotation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Annotation")
+            .field("name", &self.name)
+            .field("expr", &self.expr)
+            .finish()
+    }
+}
+
+/// Annotations are annotations of the view. View inserts them into request extensions
+/// before filters run, so filters can use annotations by name.
+#[derive(Clone, Debug, Default)]
+pub struct Annotations(Vec<Annotation>);
+
+impl Annotations {
+    /// new creates empty annotations.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// push adds annotation (annotation with the same name is replaced).
+    pub fn push(&mut self, annotation: Annotation) {
+        self.0.retain(|a| a.name != annotation.name);
+        self.0.push(annotation);
+    }
+
+    /// is_empty returns true when there are no annotations.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// get returns annotation by name.
+    pub fn get(&self, name: &str) -> Option<&Annotation> {
+        self.0.iter().find(|a| a.name == name)
+    }
+
+    /// expr returns expression of annotation by name from request extensions.
+    /// Filters use it to filter or order by annotation, e.g. `query.filter(expr.gt(5))`.
+    pub fn expr(parts: &Parts, name: &str) -> Option<SimpleExpr> {
+        parts
+            .extensions
+            .get::<Annotations>()
+            .and_then(|annotations| annotations.get(name))
+            .map(|annotation| annotation.expr.clone())
+    }
+
+    /// select adds annotations to the select query.
+    pub fn select<E>(&self, query: Select<E>) -> Select<E>
+    where
+        E: EntityTrait,
+    {
+        self.0.iter().fold(query, |query, annotation| {
+            query.column_as(annotation.expr.clone(), annotation.name.as_str())
+        })
+    }
+
+    /// values reads annotation values from the row.
+    pub fn values(
+        &self,
+        row: &QueryResult,
+    ) -> Result<serde_json::Map<String, serde_json::Value>, DbErr> {
+        self.0
+            .iter()
+            .map(|a| Ok((a.name.clone(), (a.getter)(row, &a.name)?)))
+            .collect()
+    }
+
+    /// all runs the query with annotations, returns models with annotation values.
+    pub async fn all<E, C>(
+        &self,
+        conn: &C,
+        query: Select<E>,
+    ) -> Result<Vec<(E::Model, serde_json::Map<String, serde_json::Value>)>, DbErr>
+    where
+        E: EntityTrait,
+        C: ConnectionTrait,
+    {
+        let statement = self.select(query).build(conn.get_database_backend());
+        conn.query_all(statement)
+            .await?
+            .iter()
+            .map(|row| Ok((E::Model::from_query_result(row, "")?, self.values(row)?)))
+            .collect()
+    }
+
+    /// one runs the query with annotations, returns first model with annotation values.
+    pub async fn one<E, C>(
+        &self,
+        conn: &C,
+        query: Select<E>,
+    ) -> Result<Option<(E::Model, serde_json::Map<String, serde_json::Value>)>, DbErr>
+    where
+        E: EntityTrait,
+        C: ConnectionTrait,
+    {
+        let statement = self
+            .select(query)
+            .limit(1)
+            .build(conn.get_database_backend());
+        match conn.query_one(statement).await? {
+            Some(row) => Ok(Some((
+                E::Model::from_query_result(&row, "")?,
+                self.values(&row)?,
+            ))),
+            None => Ok(None),
+        }
+    }
+
+    /// merge adds annotation values to serialized object (other values are left untouched).
+    pub fn merge(
+        object: &mut serde_json::Value,
+        values: serde_json::Map<String, serde_json::Value>,
+    ) {
+        if let serde_json::Value::Object(object) = object {
+            object.extend(values);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sea_orm::entity::prelude::*;
+    use sea_orm::sea_query::{Alias, Expr, Func, Query};
+    use sea_orm::{DbBackend, QueryTrait};
+
+    #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+    #[sea_orm(table_name = "user")]
+    pub struct Model {
+        #[sea_orm(primary_key)]
+        pub id: i32,
+        pub username: String,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+
+    fn annotations() -> Annotations {
+        let mut annotations = Annotations::new();
+        annotations.push(Annotation::new::<String>(
+            "upper_name",
+            Func::upper(Expr::col(Column::Username)),
+        ));
+        annotations.push(Annotation::subquery::<i64>(
+            "order_count",
+            Query::select()
+                .expr(Expr::col(Alias::new("id")).count())
+                .from(Alias::new("order"))
+                .and_where(
+                    Expr::col((Alias::new("order"), Alias::new("user_id")))
+                        .equals((Entity, Column::Id)),
+                )
+                .to_owned(),
+        ));
+        annotations
+    }
+
+    #[test]
+    fn test_select() {
+        assert_eq!(
+            annotations()
+                .select(Entity::find())
+                .build(DbBackend::Postgres)
+                .to_string(),
+            r#"SELECT "user"."id", "user"."username", UPPER("username") AS "upper_name", (SELECT COUNT("id") FROM "order" WHERE "order"."user_id" = "user"."id") AS "order_count" FROM "user""#
+        );
+    }
+
+    #[test]
+    fn test_expr() {
+        let (mut parts, _) = axum::http::Request::builder()
+            .body(())
+            .unwrap()
+            .into_parts();
+        assert!(Annotations::expr(&parts, "upper_name").is_none());
+
+        parts.extensions.insert(annotations());
+        assert!(Annotations::expr(&parts, "upper_name").is_some());
+        assert!(Annotations::expr(&parts, "unknown").is_none());
+    }
+
+    #[test]
+    fn test_push_replaces() {
+        let mut annotations = annotations();
+        annotations.push(Annotation::new::<i32>("upper_name", Expr::val(1)));
+        assert!(matches!(
+            annotations.get("upper_name").unwrap().expr(),
+            SimpleExpr::Value(_)
+        ));
+        assert_eq!(annotations.0.len(), 2);
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut object = serde_json::json!({"id": 1});
+        let mut values = serde_json::Map::new();
+        values.insert("order_count".to_owned(), 5.into());
+        Annotations::merge(&mut object, values);
+        assert_eq!(object, serde_json::json!({"id": 1, "order_count": 5}));
+    }
+}
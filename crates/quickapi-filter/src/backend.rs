@@ -0,0 +1,107 @@
+/*
+ *  The MIT License (MIT)
+ *
+ *  Copyright (c) 2024-2025, Peter Vrba
+ *
+ *  Permission is hereby granted, free of charge, to any person obtaining a copy
+ *  of this software and associated documentation files (the "Software"), to deal
+ *  in the Software without restriction, including without limitation the rights
+ *  to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ *  copies of the Software, and to permit persons to whom the Software is
+ *  furnished to do so, subject to the following conditions:
+ *
+ *  The above copyright notice and this permission notice shall be included in
+ *  all copies or substantial portions of the Software.
+ *
+ *  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ *  IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ *  FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ *  AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ *  LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ *  OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+ *  THE SOFTWARE.
+ *
+ */
+
+use sea_orm::DbBackend;
+use sea_orm::sea_query::extension::postgres::PgExpr;
+use sea_orm::sea_query::{Expr, Func, IntoColumnRef, LikeExpr, SimpleExpr};
+
+/// DEFAULT_BACKEND is database backend enabled by features (postgres, mysql, sqlite in this order),
+/// filters generate sql for it unless other backend is set.
+#[cfg(any(feature = "postgres", not(any(feature = "mysql", feature = "sqlite"))))]
+pub const DEFAULT_BACKEND: DbBackend = DbBackend::Postgres;
+
+/// DEFAULT_BACKEND is database backend enabled by features (postgres, mysql, sqlite in this order),
+/// filters generate sql for it unless other backend is set.
+#[cfg(all(feature = "mysql", not(feature = "postgres")))]
+pub const DEFAULT_BACKEND: DbBackend = DbBackend::MySql;
+
+/// DEFAULT_BACKEND is database backend enabled by features (postgres, mysql, sqlite in this order),
+/// filters generate sql for it unless other backend is set.
+#[cfg(all(feature = "sqlite", not(any(feature = "postgres", feature = "mysql"))))]
+pub const DEFAULT_BACKEND: DbBackend = DbBackend::Sqlite;
+
+/// icontains returns case insensitive "contains" condition for column, `ILIKE` on postgres
+/// and `LOWER(column) LIKE` on mysql and sqlite. Wildcards in value are escaped.
+pub fn icontains(backend: DbBackend, column: impl IntoColumnRef, value: &str) -> SimpleExpr {
+    icontains_expr(backend, Expr::col(column).into(), value)
+}
+
+/// icontains_expr returns case insensitive "contains" condition for expression (e.g. annotation).
+pub fn icontains_expr(backend: DbBackend, expr: SimpleExpr, value: &str) -> SimpleExpr {
+    let pattern = format!("%{}%", escape_like(value));
+    match backend {
+        DbBackend::Postgres => Expr::expr(expr).ilike(LikeExpr::new(pattern).escape('\\')),
+        DbBackend::MySql | DbBackend::Sqlite => Expr::expr(Func::lower(expr))
+            .like(LikeExpr::new(pattern.to_lowercase()).escape('\\')),
+    }
+}
+
+/// escape_like escapes LIKE wildcards (and escape character) in value.
+fn escape_like(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, '%' | '_' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sea_orm::sea_query::{Alias, Query};
+
+    fn sql(backend: DbBackend, value: &str) -> String {
+        let query = Query::select()
+            .column(Alias::new("id"))
+            .from(Alias::new("user"))
+            .and_where(icontains(backend, Alias::new("username"), value))
+            .to_owned();
+        backend.build(&query).to_string()
+    }
+
+    #[test]
+    fn test_icontains() {
+        assert_eq!(
+            sql(DbBackend::Postgres, "Joe"),
+            r#"SELECT "id" FROM "user" WHERE "username" ILIKE ('%Joe%' ESCAPE E'\\')"#
+        );
+        assert_eq!(
+            sql(DbBackend::Sqlite, "Joe"),
+            r#"SELECT "id" FROM "user" WHERE LOWER("username") LIKE '%joe%' ESCAPE '\'"#
+        );
+        assert_eq!(
+            sql(DbBackend::MySql, "Joe"),
+            r#"SELECT `id` FROM `user` WHERE LOWER(`username`) LIKE '%joe%' ESCAPE '\\'"#
+        );
+    }
+
+    #[test]
+    fn test_escape_like() {
+        assert_eq!(escape_like(r"50%_a\b"), r"50\%\_a\\b");
+    }
+}
@@ -22,5 +22,7 @@
  * THE SOFTWARE.
  */
 
+pub mod ordering;
 pub mod paginator;
+pub mod search;
 
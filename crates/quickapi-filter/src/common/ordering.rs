@@ -0,0 +1,188 @@
+/*
+ *  The MIT License (MIT)
+ *
+ *  Copyright (c) 2024-2025, Peter Vrba
+ *
+ *  Permission is hereby granted, free of charge, to any person obtaining a copy
+ *  of this software and associated documentation files (the "Software"), to deal
+ *  in the Software without restriction, including without limitation the rights
+ *  to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ *  copies of the Software, and to permit persons to whom the Software is
+ *  furnished to do so, subject to the following conditions:
+ *
+ *  The above copyright notice and this permission notice shall be included in
+ *  all copies or substantial portions of the Software.
+ *
+ *  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ *  IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ *  FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ *  AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ *  LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ *  OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+ *  THE SOFTWARE.
+ *
+ */
+
+use crate::annotation::Annotations;
+use crate::select::SelectFilter;
+use axum::http::request::Parts;
+use sea_orm::sea_query::Order;
+use sea_orm::{EntityTrait, IdenStatic, QueryOrder};
+
+/// DEFAULT_PARAM is the default query parameter of Ordering filter.
+pub const DEFAULT_PARAM: &str = "ordering";
+
+/// Ordering is a filter that orders rows by query parameter (e.g. `?ordering=-order_count,username`),
+/// `-` prefix means descending order. Allowed are given columns (by column name) and annotations
+/// of the view (with_annotations), unknown fields are ignored.
+#[derive(Clone, Debug)]
+pub struct Ordering<E, S>
+where
+    E: EntityTrait,
+    S: Clone + Send + Sync + 'static,
+{
+    param: String,
+    columns: Vec<E::Column>,
+    annotations: Vec<String>,
+    _phantom: std::marker::PhantomData<(E, S)>,
+}
+
+impl<E, S> Ordering<E, S>
+where
+    E: EntityTrait,
+    S: Clone + Send + Sync + 'static,
+{
+    /// new creates Ordering filter for given columns.
+    pub fn new(columns: impl IntoIterator<Item = E::Column>) -> Self {
+        Self {
+            param: DEFAULT_PARAM.to_owned(),
+            columns: columns.into_iter().collect(),
+            annotations: Vec::new(),
+            _phantom: std::marker::PhantomData,
+        }
+    }
+
+    /// with_param sets the query parameter name.
+    pub fn with_param(mut self, param: impl Into<String>) -> Self {
+        self.param = param.into();
+        self
+    }
+
+    /// with_annotations allows ordering by annotations of the view (by name).
+    pub fn with_annotations<I, T>(mut self, names: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        self.annotations.extend(names.into_iter().map(Into::into));
+        self
+    }
+}
+
+#[async_trait::async_trait]
+impl<E, S> SelectFilter<E, S, ()> for Ordering<E, S>
+where
+    E: EntityTrait,
+    S: Clone + Send + Sync + 'static,
+{
+    async fn filter_select(
+        &self,
+        parts: &mut Parts,
+        _state: &S,
+        query: sea_orm::Select<E>,
+    ) -> Result<sea_orm::Select<E>, crate::Error> {
+        let value = url::form_urlencoded::parse(parts.uri.query().unwrap_or_default().as_bytes())
+            .find(|(key, _)| key == &self.param)
+            .map(|(_, value)| value.into_owned())
+            .unwrap_or_default();
+
+        let mut query = query;
+        for field in value.split(',').map(str::trim).filter(|f| !f.is_empty()) {
+            let (name, order) = match field.strip_prefix('-') {
+                Some(name) => (name, Order::Desc),
+                None => (field, Order::Asc),
+            };
+
+            if let Some(column) = self.columns.iter().find(|c| c.as_str() == name) {
+                query = query.order_by(*column, order);
+            } else if let Some(expr) = self
+                .annotations
+                .iter()
+                .find(|a| *a == name)
+                .and_then(|a| Annotations::expr(parts, a))
+            {
+                query = query.order_by(expr, order);
+            }
+        }
+
+        Ok(query)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::annotation::Annotation;
+    use sea_orm::entity::prelude::*;
+    use sea_orm::sea_query::{Expr, Func};
+    use sea_orm::{DbBackend, QueryTrait};
+
+    #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+    #[sea_orm(table_name = "user")]
+    pub struct Model {
+        #[sea_orm(primary_key)]
+        pub id: i32,
+        pub username: String,
+        pub email: String,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+
+    async fn filter(ordering: &Ordering<Entity, ()>, uri: &str) -> String {
+        let (mut parts, _) = axum::http::Request::builder()
+            .uri(uri)
+            .body(())
+            .unwrap()
+            .into_parts();
+        let mut annotations = Annotations::new();
+        annotations.push(Annotation::new::<String>(
+            "upper_name",
+            Func::upper(Expr::col(Column::Username)),
+        ));
+        parts.extensions.insert(annotations);
+
+        ordering
+            .filter_select(&mut parts, &(), Entity::find())
+            .await
+            .unwrap()
+            .build(DbBackend::Postgres)
+            .to_string()
+    }
+
+    #[tokio::test]
+    async fn test_ordering() {
+        let ordering = Ordering::<Entity, ()>::new([Column::Id, Column::Username]);
+        assert!(
+            filter(&ordering, "/user?ordering=-username,%20id")
+                .await
+                .ends_with(r#"ORDER BY "user"."username" DESC, "user"."id" ASC"#)
+        );
+
+        // not allowed fields are ignored
+        assert!(
+            !filter(&ordering, "/user?ordering=email,upper_name")
+                .await
+                .contains("ORDER BY")
+        );
+
+        let ordering = ordering.with_param("o").with_annotations(["upper_name"]);
+        assert!(
+            filter(&ordering, "/user?o=-upper_name,id")
+                .await
+                .ends_with(r#"ORDER BY UPPER("username") DESC, "user"."id" ASC"#)
+        );
+    }
+}
@@ -59,6 +59,13 @@ impl From<usize> for Limit {
     }
 }
 
+/// Convert Limit to usize
+impl From<Limit> for usize {
+    fn from(value: Limit) -> Self {
+        value.0
+    }
+}
+
 /// Convert from numeric types to Limit
 macro_rules! impl_from_limit {
     ($($t:ty),+) => {
@@ -29,5 +29,5 @@ mod params;
 
 pub use limit::{DEFAULT_LIMIT, Limit};
 pub use page::{DEFAULT_PAGE, Page};
-pub use paginator::Paginator;
+pub use paginator::{Pagination, Paginator};
 pub use params::Params;
@@ -42,6 +42,13 @@ impl From<usize> for Page {
     }
 }
 
+/// Convert Page to usize
+impl From<Page> for usize {
+    fn from(value: Page) -> Self {
+        value.0
+    }
+}
+
 // implement parse from string
 impl FromStr for Page {
     type Err = crate::error::Error;
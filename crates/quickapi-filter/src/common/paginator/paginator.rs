@@ -28,6 +28,7 @@ use crate::common::paginator::params;
 use crate::select::SelectFilter;
 use async_trait::async_trait;
 use axum::http::request::Parts;
+use sea_orm::QuerySelect;
 
 #[derive(Clone, Debug, Default)]
 pub struct Paginator<E, S>
@@ -105,15 +106,79 @@ where
     ) -> Result<sea_orm::Select<E>, crate::Error> {
         let query_str = _parts.uri.query().unwrap_or_default();
 
-        let (_page, _limit) = self.parse_query(query_str)?;
-        // let offset = (page.0 - 1) * limit.0;
-        // TODO: add to parts information for response pagination
-        // Ok(query.limit(limit.0).offset(offset).into())
+        let (page, limit) = self.parse_query(query_str)?;
+        let pagination = Pagination {
+            page: page.into(),
+            limit: limit.into(),
+            params: self.params.clone(),
+        };
+
+        // add information for response pagination (links, meta)
+        let query = query
+            .limit(pagination.limit as u64)
+            .offset(pagination.offset() as u64);
+        _parts.extensions.insert(pagination);
 
         Ok(query)
     }
 }
 
+/// Pagination is the current page of paginated query, Paginator inserts it into request extensions
+/// so views can add pagination links and meta to response.
+#[derive(Clone, Debug)]
+pub struct Pagination {
+    pub page: usize,
+    pub limit: usize,
+    pub params: params::Params,
+}
+
+impl Pagination {
+    /// offset returns number of rows skipped before current page.
+    pub fn offset(&self) -> usize {
+        (self.page - 1) * self.limit
+    }
+
+    /// link returns given uri with page and limit parameters of given page (other parameters are kept).
+    pub fn link(&self, uri: &axum::http::Uri, page: usize) -> String {
+        let mut serializer = url::form_urlencoded::Serializer::new(String::new());
+        for (key, value) in url::form_urlencoded::parse(uri.query().unwrap_or_default().as_bytes()) {
+            if key != self.params.page && key != self.params.limit {
+                serializer.append_pair(&key, &value);
+            }
+        }
+        serializer.append_pair(&self.params.page, &page.to_string());
+        serializer.append_pair(&self.params.limit, &self.limit.to_string());
+
+        format!("{}?{}", uri.path(), serializer.finish())
+    }
+
+    /// links returns self, first, prev and next links, next is present when page is full (count of objects on page).
+    pub fn links(
+        &self,
+        uri: &axum::http::Uri,
+        count: usize,
+    ) -> serde_json::Map<String, serde_json::Value> {
+        let mut links = serde_json::Map::new();
+        links.insert("self".into(), self.link(uri, self.page).into());
+        links.insert("first".into(), self.link(uri, 1).into());
+        if self.page > 1 {
+            links.insert("prev".into(), self.link(uri, self.page - 1).into());
+        }
+        if count >= self.limit {
+            links.insert("next".into(), self.link(uri, self.page + 1).into());
+        }
+        links
+    }
+
+    /// meta returns page and limit.
+    pub fn meta(&self) -> serde_json::Map<String, serde_json::Value> {
+        serde_json::Map::from_iter([
+            ("page".to_owned(), self.page.into()),
+            ("limit".to_owned(), self.limit.into()),
+        ])
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -171,4 +236,33 @@ mod tests {
         assert_eq!(limit, 20.into());
         assert_eq!(page, 1.into());
     }
+
+    #[tokio::test]
+    async fn test_paginator_filter() {
+        use sea_orm::{DbBackend, QueryTrait};
+        let paginator = Paginator::<Entity, ()>::default()
+            .with_default_limit(20)
+            .with_limit_constraint(LimitConstraint::Any);
+        let (mut parts, _) = axum::http::Request::builder()
+            .uri("/user?search=joe&page=2&limit=5")
+            .body(())
+            .unwrap()
+            .into_parts();
+
+        let sql = paginator
+            .filter_select(&mut parts, &(), Entity::find())
+            .await
+            .unwrap()
+            .build(DbBackend::Postgres)
+            .to_string();
+        assert!(sql.ends_with("LIMIT 5 OFFSET 5"));
+
+        let pagination = parts.extensions.get::<Pagination>().unwrap();
+        let links = pagination.links(&parts.uri, 5);
+        assert_eq!(links["self"], "/user?search=joe&page=2&limit=5");
+        assert_eq!(links["first"], "/user?search=joe&page=1&limit=5");
+        assert_eq!(links["prev"], "/user?search=joe&page=1&limit=5");
+        assert_eq!(links["next"], "/user?search=joe&page=3&limit=5");
+        assert!(!pagination.links(&parts.uri, 4).contains_key("next"));
+    }
 }
@@ -0,0 +1,240 @@
+/*
+ *  The MIT License (MIT)
+ *
+ *  Copyright (c) 2024-2025, Peter Vrba
+ *
+ *  Permission is hereby granted, free of charge, to any person obtaining a copy
+ *  of this software and associated documentation files (the "Software"), to deal
+ *  in the Software without restriction, including without limitation the rights
+ *  to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ *  copies of the Software, and to permit persons to whom the Software is
+ *  furnished to do so, subject to the following conditions:
+ *
+ *  The above copyright notice and this permission notice shall be included in
+ *  all copies or substantial portions of the Software.
+ *
+ *  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ *  IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ *  FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ *  AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ *  LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ *  OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+ *  THE SOFTWARE.
+ *
+ */
+
+use crate::annotation::Annotations;
+use crate::backend::{DEFAULT_BACKEND, icontains, icontains_expr};
+use crate::select::SelectFilter;
+use axum::http::request::Parts;
+use sea_orm::sea_query::Condition;
+use sea_orm::{DbBackend, EntityTrait, QueryFilter};
+
+/// DEFAULT_PARAM is the default query parameter of Search filter.
+pub const DEFAULT_PARAM: &str = "search";
+
+/// Search is a filter that matches query parameter (e.g. `?search=joe`) case insensitively
+/// in any of the columns. Sql is generated for backend set by with_backend, or `DbBackend`
+/// request extension, or backend enabled by features (in this order).
+/// Annotations of the view can be searched too (with_annotations).
+#[derive(Clone, Debug)]
+pub struct Search<E, S>
+where
+    E: EntityTrait,
+    S: Clone + Send + Sync + 'static,
+{
+    param: String,
+    columns: Vec<E::Column>,
+    annotations: Vec<String>,
+    backend: Option<DbBackend>,
+    _phantom: std::marker::PhantomData<(E, S)>,
+}
+
+impl<E, S> Search<E, S>
+where
+    E: EntityTrait,
+    S: Clone + Send + Sync + 'static,
+{
+    /// new creates Search filter for given columns.
+    pub fn new(columns: impl IntoIterator<Item = E::Column>) -> Self {
+        Self {
+            param: DEFAULT_PARAM.to_owned(),
+            columns: columns.into_iter().collect(),
+            annotations: Vec::new(),
+            backend: None,
+            _phantom: std::marker::PhantomData,
+        }
+    }
+
+    /// with_param sets the query parameter name.
+    pub fn with_param(mut self, param: impl Into<String>) -> Self {
+        self.param = param.into();
+        self
+    }
+
+    /// with_annotations searches also in annotations of the view (by name).
+    pub fn with_annotations<I, T>(mut self, names: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        self.annotations.extend(names.into_iter().map(Into::into));
+        self
+    }
+
+    /// with_backend sets database backend sql is generated for (e.g. `db.get_database_backend()`).
+    pub fn with_backend(mut self, backend: DbBackend) -> Self {
+        self.backend = Some(backend);
+        self
+    }
+
+    /// backend returns database backend sql is generated for.
+    fn backend(&self, parts: &Parts) -> DbBackend {
+        self.backend
+            .or_else(|| parts.extensions.get::<DbBackend>().copied())
+            .unwrap_or(DEFAULT_BACKEND)
+    }
+}
+
+#[async_trait::async_trait]
+impl<E, S> SelectFilter<E, S, ()> for Search<E, S>
+where
+    E: EntityTrait,
+    S: Clone + Send + Sync + 'static,
+{
+    async fn filter_select(
+        &self,
+        parts: &mut Parts,
+        _state: &S,
+        query: sea_orm::Select<E>,
+    ) -> Result<sea_orm::Select<E>, crate::Error> {
+        let value = url::form_urlencoded::parse(parts.uri.query().unwrap_or_default().as_bytes())
+            .find(|(key, _)| key == &self.param)
+            .map(|(_, value)| value.trim().to_owned())
+            .unwrap_or_default();
+
+        if value.is_empty() || (self.columns.is_empty() && self.annotations.is_empty()) {
+            return Ok(query);
+        }
+
+        let backend = self.backend(parts);
+        let condition = self
+            .columns
+            .iter()
+            .fold(Condition::any(), |condition, column| {
+                condition.add(icontains(backend, (E::default(), *column), &value))
+            });
+        let condition = self
+            .annotations
+            .iter()
+            .filter_map(|name| Annotations::expr(parts, name))
+            .fold(condition, |condition, expr| {
+                condition.add(icontains_expr(backend, expr, &value))
+            });
+
+        Ok(query.filter(condition))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sea_orm::QueryTrait;
+    use sea_orm::entity::prelude::*;
+
+    #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+    #[sea_orm(table_name = "user")]
+    pub struct Model {
+        #[sea_orm(primary_key)]
+        pub id: i32,
+        pub username: String,
+        pub email: String,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+
+    async fn filter(search: &Search<Entity, ()>, uri: &str, backend: Option<DbBackend>) -> String {
+        use sea_orm::sea_query::{PostgresQueryBuilder, SqliteQueryBuilder};
+
+        let (mut parts, _) = axum::http::Request::builder()
+            .uri(uri)
+            .body(())
+            .unwrap()
+            .into_parts();
+        if let Some(backend) = backend {
+            parts.extensions.insert(backend);
+        }
+        let query = search
+            .filter_select(&mut parts, &(), Entity::find())
+            .await
+            .unwrap()
+            .into_query();
+        match search.backend(&parts) {
+            DbBackend::Sqlite => query.to_string(SqliteQueryBuilder),
+            _ => query.to_string(PostgresQueryBuilder),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_search() {
+        let search = Search::<Entity, ()>::new([Column::Username, Column::Email]);
+        assert_eq!(
+            filter(&search, "/user?search=Joe", None).await,
+            r#"SELECT "user"."id", "user"."username", "user"."email" FROM "user" WHERE ("user"."username" ILIKE ('%Joe%' ESCAPE E'\\')) OR ("user"."email" ILIKE ('%Joe%' ESCAPE E'\\'))"#
+        );
+
+        // empty search does not filter
+        assert!(
+            !filter(&search, "/user?search=%20", None)
+                .await
+                .contains("WHERE")
+        );
+
+        // backend from request extension
+        assert!(
+            filter(&search, "/user?search=Joe", Some(DbBackend::Sqlite))
+                .await
+                .contains(r#"LOWER("user"."username") LIKE '%joe%'"#)
+        );
+
+        let search = search.with_param("q").with_backend(DbBackend::Sqlite);
+        assert_eq!(
+            filter(&search, "/user?q=Joe", Some(DbBackend::Postgres)).await,
+            r#"SELECT "user"."id", "user"."username", "user"."email" FROM "user" WHERE LOWER("user"."username") LIKE '%joe%' ESCAPE '\' OR LOWER("user"."email") LIKE '%joe%' ESCAPE '\'"#
+        );
+    }
+
+    #[tokio::test]
+    async fn test_search_annotations() {
+        use crate::annotation::Annotation;
+        use sea_orm::sea_query::{Expr, Func, PostgresQueryBuilder};
+
+        let mut annotations = Annotations::new();
+        annotations.push(Annotation::new::<String>(
+            "upper_name",
+            Func::upper(Expr::col(Column::Username)),
+        ));
+
+        let (mut parts, _) = axum::http::Request::builder()
+            .uri("/user?search=Joe")
+            .body(())
+            .unwrap()
+            .into_parts();
+        parts.extensions.insert(annotations);
+
+        let query = Search::<Entity, ()>::new([])
+            .with_annotations(["upper_name", "unknown"])
+            .filter_select(&mut parts, &(), Entity::find())
+            .await
+            .unwrap()
+            .into_query()
+            .to_string(PostgresQueryBuilder);
+        assert!(
+            query.ends_with(r#"WHERE UPPER("username") ILIKE ('%Joe%' ESCAPE E'\\')"#),
+            "{query}"
+        );
+    }
+}
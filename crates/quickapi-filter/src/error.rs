@@ -5,4 +5,16 @@ pub enum Error {
 
     #[error("No match")]
     NoMatch,
+
+    #[error("Forbidden: {0}")]
+    Forbidden(String),
+
+    #[error("Not found: {0}")]
+    NotFound(String),
+
+    #[error("Database error: {0}")]
+    DatabaseError(#[from] sea_orm::DbErr),
+
+    #[error("Improperly configured: {0}")]
+    ImproperlyConfigured(String),
 }
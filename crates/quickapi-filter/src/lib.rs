@@ -1,9 +1,16 @@
 #![allow(dead_code)]
+pub mod annotation;
+pub mod backend;
 pub mod common;
 mod error;
+pub mod scope;
 pub mod select;
+pub mod soft_delete;
 
+pub use annotation::{Annotation, Annotations};
 pub use error::Error;
+pub use scope::{ExtensionScope, Scope, ScopeRegistry, Scopes};
 pub use select::{SelectFilter, SelectFilters};
+pub use soft_delete::SoftDelete;
 
 pub use select::*;
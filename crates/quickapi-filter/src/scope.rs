@@ -0,0 +1,395 @@
+/*
+ *  The MIT License (MIT)
+ *
+ *  Copyright (c) 2024-2025, Peter Vrba
+ *
+ *  Permission is hereby granted, free of charge, to any person obtaining a copy
+ *  of this software and associated documentation files (the "Software"), to deal
+ *  in the Software without restriction, including without limitation the rights
+ *  to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ *  copies of the Software, and to permit persons to whom the Software is
+ *  furnished to do so, subject to the following conditions:
+ *
+ *  The above copyright notice and this permission notice shall be included in
+ *  all copies or substantial portions of the Software.
+ *
+ *  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ *  IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ *  FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ *  AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ *  LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ *  OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+ *  THE SOFTWARE.
+ *
+ */
+
+use axum::http::request::Parts;
+use dyn_clone::DynClone;
+use sea_orm::sea_query::Expr;
+use sea_orm::{EntityTrait, ModelTrait, QueryFilter, Select};
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Scope restricts rows of entity E visible to the request (e.g. to a tenant of authenticated user).
+/// Scopes are applied to every select query before other filters, and set scoped columns on created models.
+#[async_trait::async_trait]
+pub trait Scope<E, S>: Send + Sync + DynClone
+where
+    E: EntityTrait + Send + Sync + 'static,
+    S: Clone + Send + Sync + 'static,
+{
+    /// scope_select restricts the select query to rows visible for the request.
+    async fn scope_select(
+        &self,
+        parts: &mut Parts,
+        state: &S,
+        query: Select<E>,
+    ) -> Result<Select<E>, crate::Error>;
+
+    /// scope_model sets scoped columns on the model before it's inserted.
+    async fn scope_model(
+        &self,
+        _parts: &mut Parts,
+        _state: &S,
+        model: E::Model,
+    ) -> Result<E::Model, crate::Error> {
+        Ok(model)
+    }
+}
+
+dyn_clone::clone_trait_object!(<E, S> Scope<E, S>);
+
+/// ExtensionScope scopes rows by column equal to value read from request extension T.
+/// When the extension is missing, request is forbidden.
+pub struct ExtensionScope<E, T>
+where
+    E: EntityTrait,
+{
+    column: E::Column,
+    value: Arc<dyn Fn(&T) -> sea_orm::Value + Send + Sync>,
+}
+
+impl<E, T> ExtensionScope<E, T>
+where
+    E: EntityTrait,
+{
+    /// new creates ExtensionScope for given column, value is extracted from extension T.
+    pub fn new<V>(column: E::Column, value: impl Fn(&T) -> V + Send + Sync + 'static) -> Self
+    where
+        V: Into<sea_orm::Value>,
+    {
+        Self {
+            column,
+            value: Arc::new(move |t| value(t).into()),
+        }
+    }
+
+    /// get_value returns the scope value from request extensions.
+    fn get_value(&self, parts: &Parts) -> Result<sea_orm::Value, crate::Error>
+    where
+        T: Send + Sync + 'static,
+    {
+        parts
+            .extensions
+            .get::<T>()
+            .map(|t| (self.value)(t))
+            .ok_or_else(|| crate::Error::Forbidden("missing scope principal".to_owned()))
+    }
+}
+
+impl<E, T> Clone for ExtensionScope<E, T>
+where
+    E: EntityTrait,
+{
+    fn clone(&self) -> Self {
+        Self {
+            column: self.column,
+            value: self.value.clone(),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<E, S, T> Scope<E, S> for ExtensionScope<E, T>
+where
+    E: EntityTrait + Send + Sync + 'static,
+    S: Clone + Send + Sync + 'static,
+    T: Send + Sync + 'static,
+{
+    async fn scope_select(
+        &self,
+        parts: &mut Parts,
+        _state: &S,
+        query: Select<E>,
+    ) -> Result<Select<E>, crate::Error> {
+        let value = self.get_value(parts)?;
+        Ok(query.filter(Expr::col((E::default(), self.column)).eq(value)))
+    }
+
+    async fn scope_model(
+        &self,
+        parts: &mut Parts,
+        _state: &S,
+        model: E::Model,
+    ) -> Result<E::Model, crate::Error> {
+        let value = self.get_value(parts)?;
+        let mut model = model;
+        model.set(self.column, value);
+        Ok(model)
+    }
+}
+
+/// Scopes is a collection of scopes for entity E, all of them are applied.
+pub struct Scopes<E, S>
+where
+    E: EntityTrait + Send + Sync + 'static,
+    S: Clone + Send + Sync + 'static,
+{
+    pub(crate) inner: Vec<Box<dyn Scope<E, S>>>,
+}
+
+impl<E, S> Default for Scopes<E, S>
+where
+    E: EntityTrait + Send + Sync + 'static,
+    S: Clone + Send + Sync + 'static,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Implement Clone for Scopes
+impl<E, S> Clone for Scopes<E, S>
+where
+    E: EntityTrait + Send + Sync + 'static,
+    S: Clone + Send + Sync + 'static,
+{
+    fn clone(&self) -> Self {
+        Self {
+            inner: self
+                .inner
+                .iter()
+                .map(|s| dyn_clone::clone_box(&**s))
+                .collect(),
+        }
+    }
+}
+
+impl<E, S> Scopes<E, S>
+where
+    E: EntityTrait + Send + Sync + 'static,
+    S: Clone + Send + Sync + 'static,
+{
+    /// new creates empty Scopes.
+    pub fn new() -> Self {
+        Self { inner: Vec::new() }
+    }
+
+    /// push adds a scope to the collection.
+    pub fn push(&mut self, scope: impl Scope<E, S> + 'static) {
+        self.inner.push(Box::new(scope));
+    }
+
+    /// extend adds all scopes from other collection.
+    pub fn extend(&mut self, other: &Scopes<E, S>) {
+        self.inner.extend(other.clone().inner);
+    }
+
+    /// is_empty returns true when there are no scopes.
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+}
+
+#[async_trait::async_trait]
+impl<E, S> Scope<E, S> for Scopes<E, S>
+where
+    E: EntityTrait + Send + Sync + 'static,
+    S: Clone + Send + Sync + 'static,
+{
+    async fn scope_select(
+        &self,
+        parts: &mut Parts,
+        state: &S,
+        query: Select<E>,
+    ) -> Result<Select<E>, crate::Error> {
+        let mut query = query;
+        for scope in &self.inner {
+            query = scope.scope_select(parts, state, query).await?;
+        }
+        Ok(query)
+    }
+
+    async fn scope_model(
+        &self,
+        parts: &mut Parts,
+        state: &S,
+        model: E::Model,
+    ) -> Result<E::Model, crate::Error> {
+        let mut model = model;
+        for scope in &self.inner {
+            model = scope.scope_model(parts, state, model).await?;
+        }
+        Ok(model)
+    }
+}
+
+/// ScopeRegistry holds scopes for multiple entities, so they can be registered once
+/// (on QuickApi or Prefix) and applied to every view of given entity.
+pub struct ScopeRegistry<S> {
+    inner: HashMap<TypeId, Vec<Arc<dyn Any + Send + Sync>>>,
+    _phantom: std::marker::PhantomData<S>,
+}
+
+impl<S> Default for ScopeRegistry<S> {
+    fn default() -> Self {
+        Self {
+            inner: HashMap::new(),
+            _phantom: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<S> Clone for ScopeRegistry<S> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            _phantom: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<S> std::fmt::Debug for ScopeRegistry<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ScopeRegistry")
+            .field("entities", &self.inner.len())
+            .finish()
+    }
+}
+
+impl<S> ScopeRegistry<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    /// push registers scope for entity E.
+    pub fn push<E>(&mut self, scope: impl Scope<E, S> + 'static)
+    where
+        E: EntityTrait + Send + Sync + 'static,
+    {
+        let mut scopes = Scopes::<E, S>::new();
+        scopes.push(scope);
+        self.inner
+            .entry(TypeId::of::<E>())
+            .or_default()
+            .push(Arc::new(scopes));
+    }
+
+    /// get returns all scopes registered for entity E (empty when none).
+    pub fn get<E>(&self) -> Scopes<E, S>
+    where
+        E: EntityTrait + Send + Sync + 'static,
+    {
+        let mut result = Scopes::new();
+        for scopes in self.inner.get(&TypeId::of::<E>()).into_iter().flatten() {
+            if let Some(scopes) = scopes.downcast_ref::<Scopes<E, S>>() {
+                result.extend(scopes);
+            }
+        }
+        result
+    }
+
+    /// extend registers all scopes from other registry.
+    pub fn extend(&mut self, other: &ScopeRegistry<S>) {
+        for (type_id, scopes) in &other.inner {
+            self.inner
+                .entry(*type_id)
+                .or_default()
+                .extend(scopes.iter().cloned());
+        }
+    }
+
+    /// is_empty returns true when there are no scopes registered.
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sea_orm::entity::prelude::*;
+    use sea_orm::{DbBackend, QueryTrait};
+
+    #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+    #[sea_orm(table_name = "order")]
+    pub struct Model {
+        #[sea_orm(primary_key)]
+        pub id: i32,
+        pub tenant_id: i32,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+
+    #[derive(Clone)]
+    struct Tenant(i32);
+
+    // parts prepares request parts with optional tenant extension
+    fn parts(tenant: Option<i32>) -> Parts {
+        let (mut parts, _) = axum::http::Request::builder()
+            .uri("/")
+            .body(())
+            .unwrap()
+            .into_parts();
+        if let Some(tenant) = tenant {
+            parts.extensions.insert(Tenant(tenant));
+        }
+        parts
+    }
+
+    #[tokio::test]
+    async fn test_extension_scope() {
+        let mut registry = ScopeRegistry::<()>::default();
+        registry.push::<Entity>(ExtensionScope::<Entity, Tenant>::new(
+            Column::TenantId,
+            |t: &Tenant| t.0,
+        ));
+        let scopes = registry.get::<Entity>();
+
+        let query = scopes
+            .scope_select(&mut parts(Some(7)), &(), Entity::find())
+            .await
+            .unwrap();
+        assert!(
+            query
+                .build(DbBackend::Postgres)
+                .to_string()
+                .contains(r#""order"."tenant_id" = 7"#)
+        );
+
+        let model = scopes
+            .scope_model(&mut parts(Some(7)), &(), Model { id: 1, tenant_id: 3 })
+            .await
+            .unwrap();
+        assert_eq!(model.tenant_id, 7);
+
+        let result = scopes
+            .scope_select(&mut parts(None), &(), Entity::find())
+            .await;
+        assert!(matches!(result, Err(crate::Error::Forbidden(_))));
+    }
+
+    #[test]
+    fn test_registry_extend() {
+        let scope = ExtensionScope::<Entity, Tenant>::new(Column::TenantId, |t: &Tenant| t.0);
+        let mut first = ScopeRegistry::<()>::default();
+        first.push::<Entity>(scope.clone());
+        let mut second = ScopeRegistry::<()>::default();
+        second.push::<Entity>(scope);
+        first.extend(&second);
+        assert_eq!(first.get::<Entity>().inner.len(), 2);
+    }
+}
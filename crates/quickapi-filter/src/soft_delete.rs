@@ -0,0 +1,202 @@
+/*
+ *  The MIT License (MIT)
+ *
+ *  Copyright (c) 2024-2025, Peter Vrba
+ *
+ *  Permission is hereby granted, free of charge, to any person obtaining a copy
+ *  of this software and associated documentation files (the "Software"), to deal
+ *  in the Software without restriction, including without limitation the rights
+ *  to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ *  copies of the Software, and to permit persons to whom the Software is
+ *  furnished to do so, subject to the following conditions:
+ *
+ *  The above copyright notice and this permission notice shall be included in
+ *  all copies or substantial portions of the Software.
+ *
+ *  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ *  IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ *  FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ *  AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ *  LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ *  OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+ *  THE SOFTWARE.
+ *
+ */
+
+use sea_orm::sea_query::{Condition, Expr, SimpleExpr};
+use sea_orm::{ColumnTrait, ColumnType, EntityTrait, QueryFilter, Select};
+use std::str::FromStr;
+
+/// SoftDelete describes soft deletable entity by column that marks row as deleted.
+/// Column can be boolean (true means deleted) or timestamp (null means not deleted).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SoftDelete {
+    column: &'static str,
+}
+
+impl SoftDelete {
+    /// new creates SoftDelete for given column name.
+    pub fn new(column: &'static str) -> Self {
+        Self { column }
+    }
+
+    /// column returns the column name.
+    pub fn column(&self) -> &'static str {
+        self.column
+    }
+
+    /// resolve_column finds column of the entity by its name.
+    pub fn resolve_column<E>(&self) -> Result<E::Column, crate::Error>
+    where
+        E: EntityTrait,
+    {
+        E::Column::from_str(self.column).map_err(|_| {
+            crate::Error::ImproperlyConfigured(format!(
+                "soft delete column `{}` not found",
+                self.column
+            ))
+        })
+    }
+
+    /// is_boolean returns whether the column is boolean (otherwise it's timestamp).
+    fn is_boolean<E>(&self) -> Result<bool, crate::Error>
+    where
+        E: EntityTrait,
+    {
+        match self.resolve_column::<E>()?.def().get_column_type() {
+            ColumnType::Boolean => Ok(true),
+            ColumnType::Date
+            | ColumnType::DateTime
+            | ColumnType::Timestamp
+            | ColumnType::TimestampWithTimeZone => Ok(false),
+            other => Err(crate::Error::ImproperlyConfigured(format!(
+                "soft delete column `{}` has unsupported type {:?}",
+                self.column, other
+            ))),
+        }
+    }
+
+    /// not_deleted returns condition that matches rows that are not deleted.
+    pub fn not_deleted<E>(&self) -> Result<Condition, crate::Error>
+    where
+        E: EntityTrait,
+    {
+        let column = self.resolve_column::<E>()?;
+        Ok(if self.is_boolean::<E>()? {
+            Condition::any().add(column.is_null()).add(column.eq(false))
+        } else {
+            Condition::all().add(column.is_null())
+        })
+    }
+
+    /// deleted returns condition that matches deleted rows.
+    pub fn deleted<E>(&self) -> Result<Condition, crate::Error>
+    where
+        E: EntityTrait,
+    {
+        Ok(self.not_deleted::<E>()?.not())
+    }
+
+    /// exclude_deleted filters out deleted rows from the select.
+    pub fn exclude_deleted<E>(&self, query: Select<E>) -> Result<Select<E>, crate::Error>
+    where
+        E: EntityTrait,
+    {
+        Ok(query.filter(self.not_deleted::<E>()?))
+    }
+
+    /// only_deleted filters select to deleted rows only.
+    pub fn only_deleted<E>(&self, query: Select<E>) -> Result<Select<E>, crate::Error>
+    where
+        E: EntityTrait,
+    {
+        Ok(query.filter(self.deleted::<E>()?))
+    }
+
+    /// deleted_value returns value the column is set to when row is deleted.
+    pub fn deleted_value<E>(&self) -> Result<SimpleExpr, crate::Error>
+    where
+        E: EntityTrait,
+    {
+        Ok(if self.is_boolean::<E>()? {
+            Expr::value(true)
+        } else {
+            Expr::current_timestamp().into()
+        })
+    }
+
+    /// restored_value returns value the column is set to when row is restored.
+    pub fn restored_value<E>(&self) -> Result<SimpleExpr, crate::Error>
+    where
+        E: EntityTrait,
+    {
+        Ok(if self.is_boolean::<E>()? {
+            Expr::value(false)
+        } else {
+            Expr::value(sea_orm::Value::String(None))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sea_orm::entity::prelude::*;
+    use sea_orm::{DbBackend, QueryTrait};
+
+    #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+    #[sea_orm(table_name = "customer")]
+    pub struct Model {
+        #[sea_orm(primary_key)]
+        pub id: i32,
+        pub archived: bool,
+        pub deleted_at: Option<DateTimeWithTimeZone>,
+        pub name: String,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+
+    #[test]
+    fn test_soft_delete_timestamp() {
+        let soft = SoftDelete::new("deleted_at");
+        let sql = soft
+            .exclude_deleted(Entity::find())
+            .unwrap()
+            .build(DbBackend::Postgres)
+            .to_string();
+        assert!(sql.contains(r#""customer"."deleted_at" IS NULL"#));
+
+        let sql = soft
+            .only_deleted(Entity::find())
+            .unwrap()
+            .build(DbBackend::Postgres)
+            .to_string();
+        assert!(sql.contains(r#"NOT "customer"."deleted_at" IS NULL"#));
+    }
+
+    #[test]
+    fn test_soft_delete_boolean() {
+        let soft = SoftDelete::new("archived");
+        let sql = soft
+            .exclude_deleted(Entity::find())
+            .unwrap()
+            .build(DbBackend::Postgres)
+            .to_string();
+        assert!(sql.contains(r#""customer"."archived" IS NULL OR "customer"."archived" = FALSE"#));
+    }
+
+    #[test]
+    fn test_soft_delete_invalid_column() {
+        assert!(matches!(
+            SoftDelete::new("unknown").exclude_deleted(Entity::find()),
+            Err(crate::Error::ImproperlyConfigured(_))
+        ));
+        assert!(matches!(
+            SoftDelete::new("name").deleted_value::<Entity>(),
+            Err(crate::Error::ImproperlyConfigured(_))
+        ));
+    }
+}
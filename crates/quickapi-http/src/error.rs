@@ -30,4 +30,13 @@ pub enum Error {
 
     #[error("Serde error: {0}")]
     SerdeError(#[from] serde_json::Error),
+
+    #[error("Improperly configured: {0}")]
+    ImproperlyConfigured(String),
+
+    #[error("Database error: {0}")]
+    DatabaseError(#[from] sea_orm::DbErr),
+
+    #[error("Internal error: {0}")]
+    InternalError(String),
 }
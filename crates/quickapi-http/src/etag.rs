@@ -0,0 +1,205 @@
+/*
+ *  The MIT License (MIT)
+ *
+ *  Copyright (c) 2024-2025, Peter Vrba
+ *
+ *  Permission is hereby granted, free of charge, to any person obtaining a copy
+ *  of this software and associated documentation files (the "Software"), to deal
+ *  in the Software without restriction, including without limitation the rights
+ *  to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ *  copies of the Software, and to permit persons to whom the Software is
+ *  furnished to do so, subject to the following conditions:
+ *
+ *  The above copyright notice and this permission notice shall be included in
+ *  all copies or substantial portions of the Software.
+ *
+ *  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ *  IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ *  FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ *  AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ *  LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ *  OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+ *  THE SOFTWARE.
+ *
+ */
+
+use axum::http::{HeaderMap, StatusCode, header};
+use sea_orm::{EntityTrait, ModelTrait};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use std::str::FromStr;
+
+/// ETag configures how entity tag of an object is computed.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ETag {
+    /// Hash of serialized output.
+    #[default]
+    Hash,
+    /// Value of version column (e.g. incremented on every update).
+    Column(&'static str),
+}
+
+impl ETag {
+    /// compute returns quoted entity tag for the model, serialized is the output already computed by the view.
+    pub fn compute<E>(
+        &self,
+        model: &E::Model,
+        serialized: &serde_json::Value,
+    ) -> Result<String, crate::Error>
+    where
+        E: EntityTrait,
+    {
+        match self.version_column::<E>()? {
+            None => Ok(hash(serialized.to_string().as_bytes())),
+            Some(column) => Ok(hash(model.get(column).to_string().as_bytes())),
+        }
+    }
+
+    /// version_column returns resolved version column (None when ETag is hash of serialized output).
+    pub fn version_column<E>(&self) -> Result<Option<E::Column>, crate::Error>
+    where
+        E: EntityTrait,
+    {
+        match self {
+            ETag::Hash => Ok(None),
+            ETag::Column(column) => E::Column::from_str(column).map(Some).map_err(|_| {
+                crate::Error::ImproperlyConfigured(format!("etag column `{}` not found", column))
+            }),
+        }
+    }
+}
+
+/// hash returns quoted entity tag built from sha256 of the data.
+pub fn hash(data: &[u8]) -> String {
+    let digest = format!("{:x}", Sha256::digest(data));
+    format!("\"{}\"", &digest[..32])
+}
+
+/// weak_matches returns whether header value (list of entity tags or `*`) matches the entity tag
+/// using weak comparison (RFC 7232 2.3.2), weak tags are compared by their opaque value.
+fn weak_matches(value: &str, etag: &str) -> bool {
+    value
+        .split(',')
+        .map(|tag| tag.trim())
+        .any(|tag| tag == "*" || tag.trim_start_matches("W/") == etag)
+}
+
+/// strong_matches returns whether header value (list of entity tags or `*`) matches the entity tag
+/// using strong comparison (RFC 7232 2.3.2), weak tags never match.
+fn strong_matches(value: &str, etag: &str) -> bool {
+    value
+        .split(',')
+        .map(|tag| tag.trim())
+        .any(|tag| tag == "*" || (!tag.starts_with("W/") && tag == etag))
+}
+
+/// if_match returns whether `If-Match` precondition passes (missing header passes), weak tags never match.
+pub fn if_match(headers: &HeaderMap, etag: &str) -> bool {
+    match headers.get(header::IF_MATCH) {
+        Some(value) => value
+            .to_str()
+            .map(|value| strong_matches(value, etag))
+            .unwrap_or(false),
+        None => true,
+    }
+}
+
+/// if_none_match returns whether `If-None-Match` matches (client has current representation).
+pub fn if_none_match(headers: &HeaderMap, etag: &str) -> bool {
+    headers
+        .get(header::IF_NONE_MATCH)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| weak_matches(value, etag))
+        .unwrap_or(false)
+}
+
+/// not_modified returns `304 Not Modified` response with entity tag.
+pub fn not_modified(etag: &str) -> crate::Response {
+    crate::Response::default()
+        .with_status(StatusCode::NOT_MODIFIED)
+        .with_header(header::ETAG, etag)
+}
+
+/// precondition_failed returns `412 Precondition Failed` response.
+pub fn precondition_failed() -> crate::Response {
+    crate::Response::new(json!({
+        "error": "Precondition Failed",
+        "message": "The resource was modified, If-Match does not match current ETag."
+    }))
+    .with_status(StatusCode::PRECONDITION_FAILED)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+    use sea_orm::entity::prelude::*;
+
+    #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, serde::Serialize)]
+    #[sea_orm(table_name = "order")]
+    pub struct Model {
+        #[sea_orm(primary_key)]
+        pub id: i32,
+        pub version: i32,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+
+    #[test]
+    fn test_compute() {
+        let model = Model { id: 1, version: 3 };
+        let serialized = serde_json::to_value(&model).unwrap();
+
+        let by_hash = ETag::Hash.compute::<Entity>(&model, &serialized).unwrap();
+        assert_eq!(by_hash, hash(serialized.to_string().as_bytes()));
+        assert_eq!(by_hash.len(), 34);
+
+        let by_version = ETag::Column("version")
+            .compute::<Entity>(&model, &serialized)
+            .unwrap();
+        let bumped = ETag::Column("version")
+            .compute::<Entity>(&Model { id: 1, version: 4 }, &serialized)
+            .unwrap();
+        assert_ne!(by_version, bumped);
+
+        assert!(
+            ETag::Column("unknown")
+                .compute::<Entity>(&model, &serialized)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_preconditions() {
+        let etag = hash(b"data");
+        let mut headers = HeaderMap::new();
+        assert!(if_match(&headers, &etag));
+        assert!(!if_none_match(&headers, &etag));
+
+        headers.insert(
+            header::IF_MATCH,
+            HeaderValue::from_str(&format!("\"other\", {}", etag)).unwrap(),
+        );
+        assert!(if_match(&headers, &etag));
+        headers.insert(header::IF_MATCH, HeaderValue::from_static("\"other\""));
+        assert!(!if_match(&headers, &etag));
+        headers.insert(header::IF_MATCH, HeaderValue::from_static("*"));
+        assert!(if_match(&headers, &etag));
+
+        headers.insert(
+            header::IF_NONE_MATCH,
+            HeaderValue::from_str(&format!("W/{}", etag)).unwrap(),
+        );
+        assert!(if_none_match(&headers, &etag));
+
+        // If-Match uses strong comparison
+        headers.insert(
+            header::IF_MATCH,
+            HeaderValue::from_str(&format!("W/{}", etag)).unwrap(),
+        );
+        assert!(!if_match(&headers, &etag));
+    }
+}
@@ -0,0 +1,794 @@
+/*
+ *  The MIT License (MIT)
+ *
+ *  Copyright (c) 2024-2025, Peter Vrba
+ *
+ *  Permission is hereby granted, free of charge, to any person obtaining a copy
+ *  of this software and associated documentation files (the "Software"), to deal
+ *  in the Software without restriction, including without limitation the rights
+ *  to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ *  copies of the Software, and to permit persons to whom the Software is
+ *  furnished to do so, subject to the following conditions:
+ *
+ *  The above copyright notice and this permission notice shall be included in
+ *  all copies or substantial portions of the Software.
+ *
+ *  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ *  IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ *  FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ *  AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ *  LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ *  OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+ *  THE SOFTWARE.
+ *
+ */
+
+use axum::body::Body;
+use axum::http::request::Parts;
+use axum::http::{HeaderName, HeaderValue, StatusCode};
+use axum::response::IntoResponse;
+use sea_orm::sea_query::{
+    Alias, ColumnDef, Condition, Expr, OnConflict, Query, Table, TableCreateStatement,
+};
+use sea_orm::{ConnectionTrait, DatabaseConnection};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// IDEMPOTENCY_KEY_HEADER is the request header with client generated idempotency key.
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+
+/// DEFAULT_TTL is how long stored responses are kept.
+const DEFAULT_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// DEFAULT_PENDING_TIMEOUT is how long key stays reserved for request without stored response,
+/// after that (e.g. request was cancelled) other request with the key can take it over.
+const DEFAULT_PENDING_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+
+/// StoredResponse is the first response of the request with idempotency key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoredResponse {
+    /// request_hash is the hash of request body the response was made for.
+    pub request_hash: String,
+    /// status is the response status code.
+    pub status: u16,
+    /// headers are response headers.
+    pub headers: Vec<(String, String)>,
+    /// body is the response body.
+    pub body: Vec<u8>,
+}
+
+impl IntoResponse for StoredResponse {
+    fn into_response(self) -> axum::response::Response {
+        let mut response = axum::response::Response::new(Body::from(self.body));
+        *response.status_mut() = StatusCode::from_u16(self.status).unwrap_or(StatusCode::OK);
+        for (name, value) in self.headers {
+            if let (Ok(name), Ok(value)) = (
+                HeaderName::from_bytes(name.as_bytes()),
+                HeaderValue::from_str(&value),
+            ) {
+                response.headers_mut().append(name, value);
+            }
+        }
+        response
+    }
+}
+
+/// Reservation is the state of idempotency key when request tries to reserve it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Reservation {
+    /// Key was free, it's now reserved for the request (until response is stored or key released).
+    Reserved,
+    /// Other request with the key is still in progress.
+    InProgress { request_hash: String },
+    /// Response for the key was already stored.
+    Stored(StoredResponse),
+}
+
+/// IdempotencyStore stores first responses of requests by idempotency key.
+/// Key is reserved atomically before the view runs, so concurrent retries never run the view twice.
+#[async_trait::async_trait]
+pub trait IdempotencyStore: Send + Sync {
+    /// reserve atomically reserves the key for the request when it's free (insert if absent),
+    /// otherwise returns current state of the key.
+    async fn reserve(&self, key: &str, request_hash: &str) -> Result<Reservation, crate::Error>;
+
+    /// set stores response for the reserved key, already stored response is kept.
+    async fn set(&self, key: &str, response: StoredResponse) -> Result<(), crate::Error>;
+
+    /// release removes reservation of the key without stored response, so the request can be retried.
+    async fn release(&self, key: &str) -> Result<(), crate::Error>;
+}
+
+/// Idempotency makes view honour Idempotency-Key request header.
+/// First response for the key is stored, requests with the same key and body get the stored
+/// response, requests with the same key and different body (or while the first one is still
+/// in progress) get `409 Conflict`.
+#[derive(Clone)]
+pub struct Idempotency {
+    store: Arc<dyn IdempotencyStore>,
+    scope: Option<KeyScope>,
+}
+
+/// KeyScope extracts scope of idempotency keys from the request (e.g. authenticated principal).
+type KeyScope = Arc<dyn Fn(&Parts) -> Option<String> + Send + Sync>;
+
+/// Idempotency lookup result for the request.
+pub enum Replay {
+    /// Request was not seen yet, key is reserved and response should be stored under it.
+    Store { key: String, request_hash: String },
+    /// Request was already handled, stored (or conflict) response is returned.
+    Respond(axum::response::Response),
+}
+
+impl std::fmt::Debug for Idempotency {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Idempotency").finish_non_exhaustive()
+    }
+}
+
+impl Idempotency {
+    /// new creates Idempotency with given store.
+    pub fn new(store: impl IdempotencyStore + 'static) -> Self {
+        Self {
+            store: Arc::new(store),
+            scope: None,
+        }
+    }
+
+    /// with_scope scopes idempotency keys by value extracted from the request (e.g. authenticated
+    /// principal), so callers cannot replay responses of each other by guessing their keys.
+    /// By default keys are scoped by `Authorization` header of the request.
+    pub fn with_scope(
+        mut self,
+        scope: impl Fn(&Parts) -> Option<String> + Send + Sync + 'static,
+    ) -> Self {
+        self.scope = Some(Arc::new(scope));
+        self
+    }
+
+    /// key returns store key for the request (method, path, scope and header value),
+    /// None when request has no idempotency key.
+    pub fn key(&self, parts: &Parts) -> Option<String> {
+        let value = parts.headers.get(IDEMPOTENCY_KEY_HEADER)?.to_str().ok()?;
+        let scope = match &self.scope {
+            Some(scope) => scope(parts),
+            None => authorization_scope(parts),
+        };
+        Some(match scope {
+            Some(scope) => format!("{} {} {} {}", parts.method, parts.uri.path(), scope, value),
+            None => format!("{} {} {}", parts.method, parts.uri.path(), value),
+        })
+    }
+
+    /// replay reserves the key for the request or returns stored (or conflict) response,
+    /// None when request has no idempotency key.
+    pub async fn replay(&self, parts: &Parts, body: &[u8]) -> Result<Option<Replay>, crate::Error> {
+        let Some(key) = self.key(parts) else {
+            return Ok(None);
+        };
+        let request_hash = format!("{:x}", Sha256::digest(body));
+
+        Ok(Some(
+            match self.store.reserve(&key, &request_hash).await? {
+                Reservation::Reserved => Replay::Store { key, request_hash },
+                Reservation::Stored(stored) if stored.request_hash == request_hash => {
+                    Replay::Respond(stored.into_response())
+                }
+                Reservation::InProgress {
+                    request_hash: pending,
+                } if pending == request_hash => Replay::Respond(in_progress()),
+                _ => Replay::Respond(conflict()),
+            },
+        ))
+    }
+
+    /// store stores the response under the reserved key and returns it back.
+    /// Server errors are not stored (key is released), so the request can be retried.
+    pub async fn store(
+        &self,
+        key: &str,
+        request_hash: String,
+        response: axum::response::Response,
+    ) -> Result<axum::response::Response, crate::Error> {
+        if response.status().is_server_error() {
+            self.store.release(key).await?;
+            return Ok(response);
+        }
+
+        let (parts, body) = response.into_parts();
+        let body = axum::body::to_bytes(body, usize::MAX)
+            .await
+            .map_err(|e| crate::Error::InternalError(e.to_string()))?;
+
+        let stored = StoredResponse {
+            request_hash,
+            status: parts.status.as_u16(),
+            headers: parts
+                .headers
+                .iter()
+                .filter_map(|(name, value)| {
+                    Some((name.to_string(), value.to_str().ok()?.to_owned()))
+                })
+                .collect(),
+            body: body.to_vec(),
+        };
+        self.store.set(key, stored).await?;
+
+        Ok(axum::response::Response::from_parts(
+            parts,
+            Body::from(body),
+        ))
+    }
+}
+
+/// authorization_scope returns hash of `Authorization` header (credentials are not stored in plain text).
+fn authorization_scope(parts: &Parts) -> Option<String> {
+    let value = parts.headers.get(axum::http::header::AUTHORIZATION)?;
+    let digest = format!("{:x}", Sha256::digest(value.as_bytes()));
+    Some(digest[..32].to_owned())
+}
+
+/// Any idempotency store can be used as Idempotency.
+impl<T> From<T> for Idempotency
+where
+    T: IdempotencyStore + 'static,
+{
+    fn from(store: T) -> Self {
+        Self::new(store)
+    }
+}
+
+/// conflict returns 409 response for idempotency key reused with different request body.
+pub fn conflict() -> axum::response::Response {
+    (
+        StatusCode::CONFLICT,
+        axum::Json(json!({
+            "error": "Conflict",
+            "message": "Idempotency-Key was already used with different request body.",
+        })),
+    )
+        .into_response()
+}
+
+/// in_progress returns 409 response for idempotency key of request that is still in progress.
+pub fn in_progress() -> axum::response::Response {
+    (
+        StatusCode::CONFLICT,
+        axum::Json(json!({
+            "error": "Conflict",
+            "message": "Request with the same Idempotency-Key is still in progress.",
+        })),
+    )
+        .into_response()
+}
+
+/// MemoryIdempotencyStore keeps responses in memory, expired responses are removed on write.
+#[derive(Clone)]
+pub struct MemoryIdempotencyStore {
+    ttl: Duration,
+    pending_timeout: Duration,
+    responses: Arc<Mutex<HashMap<String, (Instant, Reservation)>>>,
+}
+
+impl MemoryIdempotencyStore {
+    /// new creates in-memory store, responses are kept for 24 hours.
+    pub fn new() -> Self {
+        Self::with_ttl(DEFAULT_TTL)
+    }
+
+    /// with_ttl creates in-memory store keeping responses for given duration.
+    pub fn with_ttl(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            pending_timeout: DEFAULT_PENDING_TIMEOUT,
+            responses: Default::default(),
+        }
+    }
+
+    /// with_pending_timeout sets how long key stays reserved without stored response (5 minutes by default),
+    /// then other request with the key takes it over.
+    pub fn with_pending_timeout(mut self, pending_timeout: Duration) -> Self {
+        self.pending_timeout = pending_timeout;
+        self
+    }
+}
+
+impl Default for MemoryIdempotencyStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait::async_trait]
+impl IdempotencyStore for MemoryIdempotencyStore {
+    async fn reserve(&self, key: &str, request_hash: &str) -> Result<Reservation, crate::Error> {
+        let mut responses = self
+            .responses
+            .lock()
+            .expect("idempotency store lock poisoned");
+        responses.retain(|_, (created, reservation)| match reservation {
+            Reservation::InProgress { .. } => created.elapsed() < self.pending_timeout,
+            _ => created.elapsed() < self.ttl,
+        });
+        match responses.entry(key.to_owned()) {
+            Entry::Occupied(entry) => Ok(entry.get().1.clone()),
+            Entry::Vacant(entry) => {
+                entry.insert((
+                    Instant::now(),
+                    Reservation::InProgress {
+                        request_hash: request_hash.to_owned(),
+                    },
+                ));
+                Ok(Reservation::Reserved)
+            }
+        }
+    }
+
+    async fn set(&self, key: &str, response: StoredResponse) -> Result<(), crate::Error> {
+        let mut responses = self
+            .responses
+            .lock()
+            .expect("idempotency store lock poisoned");
+        let entry = responses.entry(key.to_owned()).or_insert((
+            Instant::now(),
+            Reservation::InProgress {
+                request_hash: response.request_hash.clone(),
+            },
+        ));
+        if let Reservation::InProgress { .. } = entry.1 {
+            entry.1 = Reservation::Stored(response);
+        }
+        Ok(())
+    }
+
+    async fn release(&self, key: &str) -> Result<(), crate::Error> {
+        let mut responses = self
+            .responses
+            .lock()
+            .expect("idempotency store lock poisoned");
+        if let Some((_, Reservation::InProgress { .. })) = responses.get(key) {
+            responses.remove(key);
+        }
+        Ok(())
+    }
+}
+
+/// DatabaseIdempotencyStore keeps responses in database table (see create_table),
+/// expired rows are deleted when keys are reserved.
+#[derive(Clone)]
+pub struct DatabaseIdempotencyStore {
+    db: DatabaseConnection,
+    table: String,
+    ttl: Duration,
+    pending_timeout: Duration,
+}
+
+impl DatabaseIdempotencyStore {
+    /// new creates store in given table, responses are kept for 24 hours.
+    pub fn new(db: DatabaseConnection, table: impl Into<String>) -> Self {
+        Self {
+            db,
+            table: table.into(),
+            ttl: DEFAULT_TTL,
+            pending_timeout: DEFAULT_PENDING_TIMEOUT,
+        }
+    }
+
+    /// with_ttl sets how long responses are kept.
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// with_pending_timeout sets how long key stays reserved without stored response (5 minutes by default),
+    /// then other request with the key takes it over.
+    pub fn with_pending_timeout(mut self, pending_timeout: Duration) -> Self {
+        self.pending_timeout = pending_timeout;
+        self
+    }
+
+    /// create_table returns statement that creates the table, to be used in migration.
+    /// Reserved keys of requests in progress are stored with status 0, created_at is unix timestamp.
+    pub fn create_table(&self) -> TableCreateStatement {
+        Table::create()
+            .table(Alias::new(&self.table))
+            .if_not_exists()
+            .col(
+                ColumnDef::new(Alias::new("key"))
+                    .string()
+                    .not_null()
+                    .primary_key(),
+            )
+            .col(
+                ColumnDef::new(Alias::new("request_hash"))
+                    .string()
+                    .not_null(),
+            )
+            .col(ColumnDef::new(Alias::new("status")).integer().not_null())
+            .col(ColumnDef::new(Alias::new("headers")).text().not_null())
+            .col(ColumnDef::new(Alias::new("body")).blob().not_null())
+            .col(
+                ColumnDef::new(Alias::new("created_at"))
+                    .big_integer()
+                    .not_null(),
+            )
+            .to_owned()
+    }
+
+    /// delete_expired deletes expired responses and stale reservations (their keys can be reserved again).
+    async fn delete_expired(&self, now: i64) -> Result<(), crate::Error> {
+        let query = Query::delete()
+            .from_table(Alias::new(&self.table))
+            .cond_where(
+                Condition::any()
+                    .add(Expr::col(Alias::new("created_at")).lt(now - self.ttl.as_secs() as i64))
+                    .add(
+                        Condition::all()
+                            .add(Expr::col(Alias::new("status")).eq(PENDING_STATUS))
+                            .add(
+                                Expr::col(Alias::new("created_at"))
+                                    .lt(now - self.pending_timeout.as_secs() as i64),
+                            ),
+                    ),
+            )
+            .to_owned();
+
+        let backend = self.db.get_database_backend();
+        self.db.execute(backend.build(&query)).await?;
+        Ok(())
+    }
+}
+
+/// unix_now returns current unix timestamp in seconds.
+fn unix_now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|now| now.as_secs() as i64)
+        .unwrap_or_default()
+}
+
+/// PENDING_STATUS is status of reserved key without stored response (request in progress).
+const PENDING_STATUS: i32 = 0;
+
+#[async_trait::async_trait]
+impl IdempotencyStore for DatabaseIdempotencyStore {
+    async fn reserve(&self, key: &str, request_hash: &str) -> Result<Reservation, crate::Error> {
+        let now = unix_now();
+        self.delete_expired(now).await?;
+
+        // pending row is inserted under unique key, only one request succeeds
+        let insert = Query::insert()
+            .into_table(Alias::new(&self.table))
+            .columns([
+                Alias::new("key"),
+                Alias::new("request_hash"),
+                Alias::new("status"),
+                Alias::new("headers"),
+                Alias::new("body"),
+                Alias::new("created_at"),
+            ])
+            .values_panic([
+                key.into(),
+                request_hash.into(),
+                PENDING_STATUS.into(),
+                "[]".into(),
+                Vec::<u8>::new().into(),
+                now.into(),
+            ])
+            .on_conflict(
+                OnConflict::column(Alias::new("key"))
+                    .do_nothing()
+                    .to_owned(),
+            )
+            .to_owned();
+
+        let backend = self.db.get_database_backend();
+        let inserted = self.db.execute(backend.build(&insert)).await?;
+        if inserted.rows_affected() > 0 {
+            return Ok(Reservation::Reserved);
+        }
+
+        let query = Query::select()
+            .columns([
+                Alias::new("request_hash"),
+                Alias::new("status"),
+                Alias::new("headers"),
+                Alias::new("body"),
+            ])
+            .from(Alias::new(&self.table))
+            .and_where(Expr::col(Alias::new("key")).eq(key))
+            .to_owned();
+
+        // row was released meanwhile, reserve it again
+        let Some(row) = self.db.query_one(backend.build(&query)).await? else {
+            return self.reserve(key, request_hash).await;
+        };
+
+        let status: i32 = row.try_get("", "status")?;
+        if status == PENDING_STATUS {
+            return Ok(Reservation::InProgress {
+                request_hash: row.try_get("", "request_hash")?,
+            });
+        }
+
+        let headers: String = row.try_get("", "headers")?;
+        Ok(Reservation::Stored(StoredResponse {
+            request_hash: row.try_get("", "request_hash")?,
+            status: status as u16,
+            headers: serde_json::from_str(&headers)?,
+            body: row.try_get("", "body")?,
+        }))
+    }
+
+    async fn set(&self, key: &str, response: StoredResponse) -> Result<(), crate::Error> {
+        let query = Query::update()
+            .table(Alias::new(&self.table))
+            .values([
+                (Alias::new("status"), i32::from(response.status).into()),
+                (
+                    Alias::new("headers"),
+                    serde_json::to_string(&response.headers)?.into(),
+                ),
+                (Alias::new("body"), response.body.into()),
+                (Alias::new("created_at"), unix_now().into()),
+            ])
+            .and_where(Expr::col(Alias::new("key")).eq(key))
+            .and_where(Expr::col(Alias::new("status")).eq(PENDING_STATUS))
+            .to_owned();
+
+        let backend = self.db.get_database_backend();
+        self.db.execute(backend.build(&query)).await?;
+        Ok(())
+    }
+
+    async fn release(&self, key: &str) -> Result<(), crate::Error> {
+        let query = Query::delete()
+            .from_table(Alias::new(&self.table))
+            .and_where(Expr::col(Alias::new("key")).eq(key))
+            .and_where(Expr::col(Alias::new("status")).eq(PENDING_STATUS))
+            .to_owned();
+
+        let backend = self.db.get_database_backend();
+        self.db.execute(backend.build(&query)).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::Request;
+
+    // request prepares request parts with idempotency key
+    fn request(key: Option<&str>) -> Parts {
+        let mut request = Request::post("/api/order");
+        if let Some(key) = key {
+            request = request.header(IDEMPOTENCY_KEY_HEADER, key);
+        }
+        request.body(()).unwrap().into_parts().0
+    }
+
+    #[test]
+    fn test_key() {
+        let idempotency = Idempotency::new(MemoryIdempotencyStore::new());
+        assert_eq!(idempotency.key(&request(None)), None);
+        assert_eq!(
+            idempotency.key(&request(Some("abc"))),
+            Some("POST /api/order abc".to_owned())
+        );
+
+        // key is scoped by principal
+        #[derive(Clone)]
+        struct User(i32);
+        let idempotency =
+            idempotency.with_scope(|parts| Some(parts.extensions.get::<User>()?.0.to_string()));
+        let mut parts = request(Some("abc"));
+        parts.extensions.insert(User(1));
+        assert_eq!(
+            idempotency.key(&parts),
+            Some("POST /api/order 1 abc".to_owned())
+        );
+
+        // by default key is scoped by Authorization header
+        let idempotency = Idempotency::new(MemoryIdempotencyStore::new());
+        let mut parts = request(Some("abc"));
+        parts.headers.insert(
+            axum::http::header::AUTHORIZATION,
+            HeaderValue::from_static("Bearer token"),
+        );
+        let key = idempotency.key(&parts).unwrap();
+        assert_ne!(key, "POST /api/order abc");
+        assert!(!key.contains("token"));
+    }
+
+    #[tokio::test]
+    async fn test_pending_timeout() {
+        let store = MemoryIdempotencyStore::new().with_pending_timeout(Duration::ZERO);
+        assert_eq!(
+            store.reserve("a", "hash").await.unwrap(),
+            Reservation::Reserved
+        );
+
+        // stale reservation (e.g. of cancelled request) is taken over
+        assert_eq!(
+            store.reserve("a", "hash").await.unwrap(),
+            Reservation::Reserved
+        );
+
+        // stored response is kept until ttl expires
+        let stored = StoredResponse {
+            request_hash: "hash".to_owned(),
+            status: 201,
+            headers: vec![],
+            body: vec![],
+        };
+        store.set("a", stored.clone()).await.unwrap();
+        assert_eq!(
+            store.reserve("a", "hash").await.unwrap(),
+            Reservation::Stored(stored)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_in_progress() {
+        let idempotency = Idempotency::new(MemoryIdempotencyStore::new());
+        let parts = request(Some("abc"));
+
+        let Some(Replay::Store { key, request_hash }) =
+            idempotency.replay(&parts, b"{}").await.unwrap()
+        else {
+            panic!("expected store");
+        };
+
+        // key is reserved until the first response is stored
+        let Some(Replay::Respond(response)) = idempotency.replay(&parts, b"{}").await.unwrap()
+        else {
+            panic!("expected in progress");
+        };
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+
+        let response = (StatusCode::CREATED, "created").into_response();
+        idempotency
+            .store(&key, request_hash, response)
+            .await
+            .unwrap();
+        let Some(Replay::Respond(response)) = idempotency.replay(&parts, b"{}").await.unwrap()
+        else {
+            panic!("expected replay");
+        };
+        assert_eq!(response.status(), StatusCode::CREATED);
+    }
+
+    #[tokio::test]
+    async fn test_replay() {
+        let idempotency = Idempotency::new(MemoryIdempotencyStore::new());
+        let parts = request(Some("abc"));
+
+        assert!(
+            idempotency
+                .replay(&request(None), b"{}")
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        let Some(Replay::Store { key, request_hash }) =
+            idempotency.replay(&parts, b"{}").await.unwrap()
+        else {
+            panic!("expected store");
+        };
+        let response = (StatusCode::CREATED, "created").into_response();
+        let response = idempotency
+            .store(&key, request_hash, response)
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+
+        // same body gets stored response
+        let Some(Replay::Respond(response)) = idempotency.replay(&parts, b"{}").await.unwrap()
+        else {
+            panic!("expected replay");
+        };
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(&body[..], b"created");
+
+        // different body gets conflict
+        let Some(Replay::Respond(response)) =
+            idempotency.replay(&parts, b"{\"a\":1}").await.unwrap()
+        else {
+            panic!("expected conflict");
+        };
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+    }
+
+    #[tokio::test]
+    async fn test_server_error_not_stored() {
+        let idempotency = Idempotency::new(MemoryIdempotencyStore::new());
+        let parts = request(Some("abc"));
+
+        let Some(Replay::Store { key, request_hash }) =
+            idempotency.replay(&parts, b"{}").await.unwrap()
+        else {
+            panic!("expected store");
+        };
+        let response = StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        idempotency
+            .store(&key, request_hash, response)
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            idempotency.replay(&parts, b"{}").await.unwrap(),
+            Some(Replay::Store { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_database_reserve() {
+        let db = sea_orm::Database::connect("sqlite::memory:").await.unwrap();
+        let store = DatabaseIdempotencyStore::new(db.clone(), "idempotency_key");
+        db.execute(db.get_database_backend().build(&store.create_table()))
+            .await
+            .unwrap();
+
+        // only first request reserves the key
+        assert_eq!(
+            store.reserve("a", "hash").await.unwrap(),
+            Reservation::Reserved
+        );
+        assert_eq!(
+            store.reserve("a", "hash").await.unwrap(),
+            Reservation::InProgress {
+                request_hash: "hash".to_owned()
+            }
+        );
+
+        // stored response is returned (and kept on release)
+        let stored = StoredResponse {
+            request_hash: "hash".to_owned(),
+            status: 201,
+            headers: vec![("content-type".to_owned(), "application/json".to_owned())],
+            body: b"{}".to_vec(),
+        };
+        store.set("a", stored.clone()).await.unwrap();
+        store.release("a").await.unwrap();
+        assert_eq!(
+            store.reserve("a", "hash").await.unwrap(),
+            Reservation::Stored(stored)
+        );
+
+        // released key can be reserved again
+        store.reserve("b", "hash").await.unwrap();
+        store.release("b").await.unwrap();
+        assert_eq!(
+            store.reserve("b", "hash").await.unwrap(),
+            Reservation::Reserved
+        );
+
+        // expired response is deleted and stale reservation is taken over
+        db.execute_unprepared(r#"UPDATE "idempotency_key" SET "created_at" = 0"#)
+            .await
+            .unwrap();
+        assert_eq!(
+            store.reserve("a", "other").await.unwrap(),
+            Reservation::Reserved
+        );
+        assert_eq!(
+            store.reserve("b", "other").await.unwrap(),
+            Reservation::Reserved
+        );
+        assert_eq!(
+            store.reserve("b", "hash").await.unwrap(),
+            Reservation::InProgress {
+                request_hash: "other".to_owned()
+            }
+        );
+    }
+}
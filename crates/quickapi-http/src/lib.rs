@@ -24,6 +24,8 @@
  */
 
 mod error;
+pub mod etag;
+pub mod idempotency;
 pub mod request;
 pub mod response;
 pub mod serializer;
@@ -0,0 +1,105 @@
+/*
+ *  The MIT License (MIT)
+ *
+ *  Copyright (c) 2024-2025, Peter Vrba
+ *
+ *  Permission is hereby granted, free of charge, to any person obtaining a copy
+ *  of this software and associated documentation files (the "Software"), to deal
+ *  in the Software without restriction, including without limitation the rights
+ *  to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ *  copies of the Software, and to permit persons to whom the Software is
+ *  furnished to do so, subject to the following conditions:
+ *
+ *  The above copyright notice and this permission notice shall be included in
+ *  all copies or substantial portions of the Software.
+ *
+ *  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ *  IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ *  FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ *  AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ *  LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ *  OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+ *  THE SOFTWARE.
+ *
+ */
+use crate::response::partials::Partials;
+
+/// Envelope is a global template of json responses, e.g. `{data, meta, errors}`.
+/// Response data is placed under data key (or errors key for error responses), other template keys
+/// hold their defaults unless partials (meta, links, ...) replace them.
+/// Handler looks it up in request extensions, so it's set for whole router with `Extension` layer.
+#[derive(Clone, Debug)]
+pub struct Envelope {
+    data: String,
+    errors: Option<String>,
+    template: serde_json::Map<String, serde_json::Value>,
+}
+
+impl Envelope {
+    /// new creates envelope that places response data under given key.
+    pub fn new(data: impl Into<String>) -> Self {
+        Self {
+            data: data.into(),
+            errors: None,
+            template: Default::default(),
+        }
+    }
+
+    /// with_key adds template key with default value (e.g. `"meta", json!({})`).
+    pub fn with_key(mut self, key: impl Into<String>, default: serde_json::Value) -> Self {
+        self.template.insert(key.into(), default);
+        self
+    }
+
+    /// with_errors places data of error responses (4xx, 5xx) under given key instead of data key.
+    pub fn with_errors(mut self, key: impl Into<String>) -> Self {
+        self.errors = Some(key.into());
+        self
+    }
+
+    /// wrap wraps response data together with partials into envelope.
+    pub fn wrap<S>(
+        &self,
+        data: serde_json::Value,
+        status: axum::http::StatusCode,
+        partials: &Partials<S>,
+    ) -> serde_json::Value {
+        let mut result = self.template.clone();
+        partials.update_map(&mut result);
+
+        let key = match &self.errors {
+            Some(errors) if status.is_client_error() || status.is_server_error() => errors,
+            _ => &self.data,
+        };
+        result.insert(key.clone(), data);
+
+        serde_json::Value::Object(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::StatusCode;
+    use serde_json::json;
+
+    #[test]
+    fn test_envelope() {
+        let envelope = Envelope::new("data")
+            .with_key("data", json!(null))
+            .with_key("meta", json!({}))
+            .with_key("errors", json!([]))
+            .with_errors("errors");
+        let partials = Partials::<()>::default();
+        partials.insert("meta", json!({"total": 1}));
+
+        assert_eq!(
+            envelope.wrap(json!([{"id": 1}]), StatusCode::OK, &partials),
+            json!({"data": [{"id": 1}], "meta": {"total": 1}, "errors": []})
+        );
+        assert_eq!(
+            envelope.wrap(json!({"error": "Bad Request"}), StatusCode::BAD_REQUEST, &partials),
+            json!({"data": null, "meta": {"total": 1}, "errors": {"error": "Bad Request"}})
+        );
+    }
+}
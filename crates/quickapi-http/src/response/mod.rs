@@ -23,10 +23,13 @@
  *
  */
 
+pub mod envelope;
 pub mod key;
 pub mod partials;
 pub mod response;
 
 /// Does this need to be public?
 pub use response::Response;
-pub use key::Key;
\ No newline at end of file
+pub use key::Key;
+pub use envelope::Envelope;
+pub use partials::Partials;
\ No newline at end of file
@@ -21,20 +21,36 @@
  * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
  * THE SOFTWARE.
  */
+use axum::extract::FromRequestParts;
+use axum::http::request::Parts;
+use std::convert::Infallible;
 use std::marker::PhantomData;
+use std::sync::{Arc, Mutex};
 
-/// Parts struct that holds a collection of parts for a response.
-#[derive(Clone, Debug)]
+/// Partials holds additional top level keys of json response (e.g. meta, links, warnings).
+/// Handler inserts empty partials into request extensions, clones share the same keys, so filters,
+/// callbacks and when handlers can add keys (extractor or extensions) and view merges them into response.
+#[derive(Debug)]
 pub struct Partials<S> {
-    parts: serde_json::Map<String, serde_json::Value>,
+    parts: Arc<Mutex<serde_json::Map<String, serde_json::Value>>>,
     phantom_data: PhantomData<S>,
 }
 
+/// Implementing Clone for Partials<S>, clones share the same parts
+impl<S> Clone for Partials<S> {
+    fn clone(&self) -> Self {
+        Partials {
+            parts: self.parts.clone(),
+            phantom_data: PhantomData,
+        }
+    }
+}
+
 /// Implementing Default for Parts<S>
 impl<S> Default for Partials<S> {
     fn default() -> Self {
         Partials {
-            parts: serde_json::Map::new(),
+            parts: Default::default(),
             phantom_data: PhantomData,
         }
     }
@@ -43,21 +59,99 @@ impl<S> Default for Partials<S> {
 /// Implementing Parts methods
 impl<S> Partials<S> {
     /// Inserts a part into the collection.
-    pub fn insert(&mut self, key: String, value: serde_json::Value) {
-        self.parts.insert(key, value);
+    pub fn insert(&self, key: impl Into<String>, value: serde_json::Value) {
+        self.lock().insert(key.into(), value);
+    }
+
+    /// merge merges object into object part (e.g. meta), non object part is replaced.
+    pub fn merge(&self, key: impl Into<String>, value: serde_json::Map<String, serde_json::Value>) {
+        let mut parts = self.lock();
+        match parts.entry(key.into()).or_insert_with(|| serde_json::json!({})) {
+            serde_json::Value::Object(object) => object.extend(value),
+            other => *other = serde_json::Value::Object(value),
+        }
+    }
+
+    /// push appends value to array part (e.g. warnings), non array part is replaced.
+    pub fn push(&self, key: impl Into<String>, value: serde_json::Value) {
+        let mut parts = self.lock();
+        match parts.entry(key.into()).or_insert_with(|| serde_json::json!([])) {
+            serde_json::Value::Array(array) => array.push(value),
+            other => *other = serde_json::Value::Array(vec![value]),
+        }
+    }
+
+    /// get returns a copy of part by key.
+    pub fn get(&self, key: &str) -> Option<serde_json::Value> {
+        self.lock().get(key).cloned()
+    }
+
+    /// is_empty returns whether no parts were added.
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
     }
 
     /// update_map
     pub fn update_map(&self, other_map: &mut serde_json::Map<String, serde_json::Value>) {
-        for (key, value) in &self.parts {
+        for (key, value) in self.lock().iter() {
             other_map.insert(key.clone(), value.clone());
         }
     }
+
+    /// lock locks parts, poisoned lock is still usable (parts are plain json).
+    fn lock(&self) -> std::sync::MutexGuard<'_, serde_json::Map<String, serde_json::Value>> {
+        self.parts.lock().unwrap_or_else(|err| err.into_inner())
+    }
 }
 
 /// Convert Parts<S> to serde_json::Value
 impl<S> From<Partials<S>> for serde_json::Value {
     fn from(s: Partials<S>) -> Self {
-        serde_json::Value::Object(s.parts.clone())
+        serde_json::Value::Object(s.lock().clone())
+    }
+}
+
+/// Partials extractor returns partials of current request (shared with the view).
+impl<S, T> FromRequestParts<T> for Partials<S>
+where
+    S: Send + Sync + 'static,
+    T: Send + Sync,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &T) -> Result<Self, Self::Rejection> {
+        Ok(parts
+            .extensions
+            .get_or_insert_default::<Partials<S>>()
+            .clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_partials_extractor() {
+        let (mut parts, _) = axum::http::Request::new(()).into_parts();
+        parts.extensions.insert(Partials::<()>::default());
+
+        let partials = Partials::<()>::from_request_parts(&mut parts, &()).await.unwrap();
+        partials.insert("links", json!({"self": "/user"}));
+        partials.merge("meta", json!({"total": 2}).as_object().unwrap().clone());
+        partials.merge("meta", json!({"page": 1}).as_object().unwrap().clone());
+        partials.push("warnings", json!("deprecated"));
+
+        // clone in extensions shares the parts
+        let value: serde_json::Value = parts.extensions.get::<Partials<()>>().unwrap().clone().into();
+        assert_eq!(
+            value,
+            json!({
+                "links": {"self": "/user"},
+                "meta": {"total": 2, "page": 1},
+                "warnings": ["deprecated"],
+            })
+        );
     }
 }
@@ -22,13 +22,42 @@
  *  THE SOFTWARE.
  *
  */
+use crate::response::envelope::Envelope;
 use crate::response::partials::Partials;
+use std::sync::{Arc, Mutex};
+
+/// DEFAULT_DATA_KEY is the key of non object response data when partials are merged into it.
+pub const DEFAULT_DATA_KEY: &str = "data";
 
 #[derive(Clone, Debug)]
 pub struct Response {
     data: serde_json::Value,
     status: axum::http::StatusCode,
     headers: axum::http::HeaderMap,
+    body: Option<Body>,
+}
+
+/// Body is raw (not JSON) response body, e.g. streamed CSV export.
+/// It's shared by response clones and taken when response is sent.
+#[derive(Clone)]
+pub struct Body(Arc<Mutex<Option<axum::body::Body>>>);
+
+impl Body {
+    /// new creates raw response body.
+    pub fn new(body: impl Into<axum::body::Body>) -> Self {
+        Body(Arc::new(Mutex::new(Some(body.into()))))
+    }
+
+    /// take takes the body out, body can be sent only once.
+    fn take(&self) -> Option<axum::body::Body> {
+        self.0.lock().ok()?.take()
+    }
+}
+
+impl std::fmt::Debug for Body {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Body").finish_non_exhaustive()
+    }
 }
 
 /// Default implementation for JsonResponse
@@ -41,6 +70,7 @@ impl Default for Response {
                 axum::http::header::CONTENT_TYPE,
                 axum::http::header::HeaderValue::from_static("application/json"),
             )]),
+            body: None,
         }
     }
 }
@@ -53,6 +83,33 @@ impl Response {
         result
     }
 
+    /// with_body creates a response with raw body of given content type (instead of JSON data).
+    pub fn with_body(content_type: &str, body: impl Into<axum::body::Body>) -> Self {
+        let mut result = Self::default().with_header(axum::http::header::CONTENT_TYPE, content_type);
+        result.body = Some(Body::new(body));
+        result
+    }
+
+    /// has_body returns whether response has raw body (not JSON data).
+    pub fn has_body(&self) -> bool {
+        self.body.is_some()
+    }
+
+    /// status returns the HTTP status code of the response
+    pub fn status(&self) -> axum::http::StatusCode {
+        self.status
+    }
+
+    /// data returns the JSON data of the response
+    pub fn data(&self) -> &serde_json::Value {
+        &self.data
+    }
+
+    /// headers returns the HTTP headers of the response
+    pub fn headers(&self) -> &axum::http::HeaderMap {
+        &self.headers
+    }
+
     /// with_status sets the HTTP status code for the response
     pub fn with_status(mut self, status: axum::http::StatusCode) -> Self {
         self.status = status;
@@ -77,6 +134,36 @@ impl Response {
         self
     }
 
+    /// merge_partials merges partials added during request (extensions) into json response data
+    /// and wraps it into envelope when set in extensions. Raw body and empty responses are kept as they are.
+    pub fn merge_partials<S>(self, parts: &axum::http::request::Parts) -> Self
+    where
+        S: Send + Sync + 'static,
+    {
+        if self.has_body()
+            || matches!(
+                self.status,
+                axum::http::StatusCode::NO_CONTENT | axum::http::StatusCode::NOT_MODIFIED
+            )
+        {
+            return self;
+        }
+
+        let partials = parts.extensions.get::<Partials<S>>().cloned().unwrap_or_default();
+
+        match parts.extensions.get::<Envelope>() {
+            Some(envelope) => self.with_envelope(envelope, &partials),
+            None if partials.is_empty() => self,
+            None => self.with_partials(DEFAULT_DATA_KEY, &partials),
+        }
+    }
+
+    /// with_envelope wraps response data together with partials into envelope template
+    pub fn with_envelope<S>(mut self, envelope: &Envelope, partials: &Partials<S>) -> Self {
+        self.data = envelope.wrap(std::mem::take(&mut self.data), self.status, partials);
+        self
+    }
+
     /// with_header adds a header to the response
     pub fn with_header(
         mut self,
@@ -95,9 +182,37 @@ impl Response {
 /// Implementing IntoResponse for JsonResponse to convert it into an axum response
 impl axum::response::IntoResponse for Response {
     fn into_response(self) -> axum::response::Response {
-        let mut response = axum::response::Response::new(self.data.to_string().into());
+        // responses without content must not have a body
+        let body = match self.status {
+            axum::http::StatusCode::NO_CONTENT | axum::http::StatusCode::NOT_MODIFIED => {
+                axum::body::Body::empty()
+            }
+            _ => match self.body.as_ref().and_then(Body::take) {
+                Some(body) => body,
+                None => self.data.to_string().into(),
+            },
+        };
+        let mut response = axum::response::Response::new(body);
         *response.status_mut() = self.status;
         *response.headers_mut() = self.headers;
         response
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::HttpBody;
+    use axum::response::IntoResponse;
+
+    #[test]
+    fn test_into_response_without_content() {
+        let response = Response::default()
+            .with_status(axum::http::StatusCode::NO_CONTENT)
+            .into_response();
+        assert_eq!(response.body().size_hint().exact(), Some(0));
+
+        let response = Response::new(serde_json::json!({"id": 1})).into_response();
+        assert_eq!(response.body().size_hint().exact(), Some(8));
+    }
+}
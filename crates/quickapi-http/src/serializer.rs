@@ -82,3 +82,95 @@ where
         Ok(_intermediate.into())
     }
 }
+
+/// OutputSerializerJson serializes model into JSON value through output type.
+/// Output type is erased, so it can be changed without changing type of the view.
+pub struct OutputSerializerJson<M>
+where
+    M: 'static,
+{
+    serialize: std::sync::Arc<
+        dyn Fn(M) -> Result<serde_json::Value, crate::error::Error> + Send + Sync,
+    >,
+}
+
+impl<M> Clone for OutputSerializerJson<M>
+where
+    M: 'static,
+{
+    fn clone(&self) -> Self {
+        Self {
+            serialize: self.serialize.clone(),
+        }
+    }
+}
+
+impl<M> Default for OutputSerializerJson<M>
+where
+    M: serde::Serialize + 'static,
+{
+    /// default serializes the model itself.
+    fn default() -> Self {
+        Self::new::<M>()
+    }
+}
+
+impl<M> OutputSerializerJson<M>
+where
+    M: 'static,
+{
+    /// new creates output serializer that converts model into O before serialization.
+    pub fn new<O>() -> Self
+    where
+        O: From<M> + serde::Serialize,
+    {
+        Self {
+            serialize: std::sync::Arc::new(|model: M| Ok(serde_json::to_value(O::from(model))?)),
+        }
+    }
+
+    /// serialize_json serializes the model into a JSON value.
+    pub fn serialize_json(&self, model: M) -> Result<serde_json::Value, crate::error::Error> {
+        (self.serialize)(model)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[derive(Clone, serde::Serialize)]
+    struct User {
+        id: i32,
+        password: String,
+    }
+
+    #[derive(serde::Serialize)]
+    struct UserId {
+        id: i32,
+    }
+
+    impl From<User> for UserId {
+        fn from(user: User) -> Self {
+            UserId { id: user.id }
+        }
+    }
+
+    #[test]
+    fn test_output_serializer() {
+        let user = User {
+            id: 1,
+            password: "secret".to_owned(),
+        };
+
+        let default = OutputSerializerJson::<User>::default();
+        assert_eq!(
+            default.serialize_json(user.clone()).unwrap(),
+            json!({"id": 1, "password": "secret"})
+        );
+
+        let output = OutputSerializerJson::<User>::new::<UserId>();
+        assert_eq!(output.serialize_json(user).unwrap(), json!({"id": 1}));
+    }
+}
@@ -83,15 +83,47 @@ macro_rules! all_the_tuples_with_empty {
     };
 }
 
+/// debug_query prints sql of the query, by default for database backend enabled by features
+/// (postgres, mysql, sqlite in this order).
 #[macro_export]
 macro_rules! debug_query {
     ($q:expr) => {
         $crate::debug_query!($q, "Query");
     };
     ($q:expr, $name:expr) => {
+        $crate::debug_query!($q, $name, $crate::default_backend!());
+    };
+    ($q:expr, $name:expr, $backend:expr) => {
         {
             use sea_orm::QueryTrait;
-            println!("{}: {}", $name, $q.build(sea_orm::DbBackend::Postgres).to_string());
+            println!("{}: {}", $name, $q.build($backend).to_string());
         }
     };
-}
\ No newline at end of file
+}
+
+/// default_backend returns database backend enabled by features (postgres when none is enabled).
+#[cfg(any(feature = "postgres", not(any(feature = "mysql", feature = "sqlite"))))]
+#[macro_export]
+macro_rules! default_backend {
+    () => {
+        sea_orm::DbBackend::Postgres
+    };
+}
+
+/// default_backend returns database backend enabled by features (postgres when none is enabled).
+#[cfg(all(feature = "mysql", not(feature = "postgres")))]
+#[macro_export]
+macro_rules! default_backend {
+    () => {
+        sea_orm::DbBackend::MySql
+    };
+}
+
+/// default_backend returns database backend enabled by features (postgres when none is enabled).
+#[cfg(all(feature = "sqlite", not(any(feature = "postgres", feature = "mysql"))))]
+#[macro_export]
+macro_rules! default_backend {
+    () => {
+        sea_orm::DbBackend::Sqlite
+    };
+}
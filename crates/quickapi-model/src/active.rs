@@ -0,0 +1,138 @@
+/*
+ *  The MIT License (MIT)
+ *
+ *  Copyright (c) 2024-2025, Peter Vrba
+ *
+ *  Permission is hereby granted, free of charge, to any person obtaining a copy
+ *  of this software and associated documentation files (the "Software"), to deal
+ *  in the Software without restriction, including without limitation the rights
+ *  to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ *  copies of the Software, and to permit persons to whom the Software is
+ *  furnished to do so, subject to the following conditions:
+ *
+ *  The above copyright notice and this permission notice shall be included in
+ *  all copies or substantial portions of the Software.
+ *
+ *  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ *  IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ *  FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ *  AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ *  LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ *  OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+ *  THE SOFTWARE.
+ *
+ */
+use sea_orm::sea_query::Expr;
+use sea_orm::{
+    ActiveModelTrait, ConnectionTrait, DbErr, EntityTrait, IntoActiveModel, Iterable, ModelTrait,
+    PrimaryKeyToColumn, PrimaryKeyTrait, QueryFilter,
+};
+
+/// insert_active_model converts model into active model ready for insert.
+/// Auto increment primary key columns are left unset, so the database generates them.
+pub fn insert_active_model<E>(model: E::Model) -> E::ActiveModel
+where
+    E: EntityTrait,
+    E::Model: IntoActiveModel<E::ActiveModel>,
+{
+    let mut active = model.into_active_model();
+    if <E::PrimaryKey as PrimaryKeyTrait>::auto_increment() {
+        for pk in E::PrimaryKey::iter() {
+            active.not_set(pk.into_column());
+        }
+    }
+    active
+}
+
+/// update_active_model converts updated model into active model ready for update.
+/// All columns are marked as changed, primary key is kept from original model.
+pub fn update_active_model<E>(original: &E::Model, updated: E::Model) -> E::ActiveModel
+where
+    E: EntityTrait,
+    E::Model: IntoActiveModel<E::ActiveModel>,
+{
+    let mut updated = updated;
+    for pk in E::PrimaryKey::iter() {
+        let column = pk.into_column();
+        updated.set(column, original.get(column));
+    }
+    updated.into_active_model().reset_all()
+}
+
+/// update_versioned updates the row of original model only when its version column was not changed
+/// since original was read and increments the version (optimistic locking).
+/// None is returned when the row was modified (or deleted) in the meantime.
+pub async fn update_versioned<E, C>(
+    conn: &C,
+    original: &E::Model,
+    active: E::ActiveModel,
+    version: E::Column,
+) -> Result<Option<E::Model>, DbErr>
+where
+    E: EntityTrait,
+    C: ConnectionTrait,
+{
+    let mut active = active;
+    active.not_set(version);
+
+    let result = E::update_many()
+        .set(active)
+        .col_expr(version, Expr::col(version).add(1))
+        .filter(crate::version_condition::<E>(original, version))
+        .exec(conn)
+        .await?;
+    if result.rows_affected == 0 {
+        return Ok(None);
+    }
+
+    E::find()
+        .filter(crate::primary_key_condition::<E>(original))
+        .one(conn)
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sea_orm::entity::prelude::*;
+
+    #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+    #[sea_orm(table_name = "user")]
+    pub struct Model {
+        #[sea_orm(primary_key)]
+        pub id: i32,
+        pub username: String,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+
+    #[test]
+    fn test_insert_active_model() {
+        let active = insert_active_model::<Entity>(Model {
+            id: 0,
+            username: "phonkee".to_owned(),
+        });
+        assert!(active.id.is_not_set());
+        assert!(!active.username.is_not_set());
+    }
+
+    #[test]
+    fn test_update_active_model() {
+        let original = Model {
+            id: 1,
+            username: "old".to_owned(),
+        };
+        let active = update_active_model::<Entity>(
+            &original,
+            Model {
+                id: 5,
+                username: "new".to_owned(),
+            },
+        );
+        assert_eq!(active.id, sea_orm::ActiveValue::Set(1));
+        assert_eq!(active.username, sea_orm::ActiveValue::Set("new".to_owned()));
+    }
+}
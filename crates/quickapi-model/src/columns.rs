@@ -22,7 +22,8 @@
  *  THE SOFTWARE.
  *
  */
-use sea_orm::{EntityTrait, Iden, Iterable};
+use sea_orm::sea_query::Condition;
+use sea_orm::{ColumnTrait, EntityTrait, Iden, Iterable, ModelTrait, PrimaryKeyToColumn};
 
 /// primary_key returns the primary key column name for the given entity type.
 pub fn primary_key<E>() -> Result<String, crate::Error>
@@ -38,6 +39,43 @@ where
         .to_string())
 }
 
+/// primary_key_condition returns condition that matches given model by its primary key values.
+pub fn primary_key_condition<E>(model: &E::Model) -> Condition
+where
+    E: EntityTrait,
+{
+    E::PrimaryKey::iter().fold(Condition::all(), |condition, pk| {
+        let column = pk.into_column();
+        condition.add(column.eq(model.get(column)))
+    })
+}
+
+/// version_condition returns condition that matches given model by its primary key values and
+/// value of version column, so the row matches only when it was not modified since model was read.
+pub fn version_condition<E>(model: &E::Model, version: E::Column) -> Condition
+where
+    E: EntityTrait,
+{
+    primary_key_condition::<E>(model).add(version.eq(model.get(version)))
+}
+
+/// primary_keys_condition returns condition that matches any of given models by their primary key values.
+pub fn primary_keys_condition<E>(models: &[E::Model]) -> Condition
+where
+    E: EntityTrait,
+{
+    let mut pks = E::PrimaryKey::iter();
+    match (pks.next(), pks.next()) {
+        (Some(pk), None) => {
+            let column = pk.into_column();
+            Condition::all().add(column.is_in(models.iter().map(|model| model.get(column))))
+        }
+        _ => models.iter().fold(Condition::any(), |condition, model| {
+            condition.add(primary_key_condition::<E>(model))
+        }),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -59,4 +97,27 @@ mod tests {
     fn test_primary_key() {
         assert_eq!(primary_key::<Entity>().unwrap(), "id".to_owned());
     }
+
+    #[test]
+    fn test_primary_key_condition() {
+        use sea_orm::{DbBackend, QueryFilter, QueryTrait};
+        let sql = Entity::find()
+            .filter(primary_key_condition::<Entity>(&Model { id: 42 }))
+            .build(DbBackend::Postgres)
+            .to_string();
+        assert!(sql.ends_with(r#"WHERE "user"."id" = 42"#));
+    }
+
+    #[test]
+    fn test_primary_keys_condition() {
+        use sea_orm::{DbBackend, QueryFilter, QueryTrait};
+        let sql = Entity::find()
+            .filter(primary_keys_condition::<Entity>(&[
+                Model { id: 1 },
+                Model { id: 2 },
+            ]))
+            .build(DbBackend::Postgres)
+            .to_string();
+        assert!(sql.ends_with(r#"WHERE "user"."id" IN (1, 2)"#));
+    }
 }
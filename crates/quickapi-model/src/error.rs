@@ -32,4 +32,10 @@ pub enum Error {
 
     #[error("Improperly configured: {0}")]
     ImproperlyConfigured(String),
+
+    #[error("Invalid patch: {0}")]
+    InvalidPatch(String),
+
+    #[error("Field `{0}` is read only")]
+    ReadOnlyField(String),
 }
@@ -0,0 +1,254 @@
+/*
+ *  The MIT License (MIT)
+ *
+ *  Copyright (c) 2024-2025, Peter Vrba
+ *
+ *  Permission is hereby granted, free of charge, to any person obtaining a copy
+ *  of this software and associated documentation files (the "Software"), to deal
+ *  in the Software without restriction, including without limitation the rights
+ *  to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ *  copies of the Software, and to permit persons to whom the Software is
+ *  furnished to do so, subject to the following conditions:
+ *
+ *  The above copyright notice and this permission notice shall be included in
+ *  all copies or substantial portions of the Software.
+ *
+ *  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ *  IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ *  FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ *  AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ *  LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ *  OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+ *  THE SOFTWARE.
+ *
+ */
+
+use sea_orm::{ActiveModelTrait, EntityTrait, IdenStatic, ModelTrait};
+
+/// ReadOnlyMode decides what happens when client sets read only field.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ReadOnlyMode {
+    /// Ignore keeps value of read only field (default, or original when updating).
+    #[default]
+    Ignore,
+    /// Reject rejects request that contains read only field.
+    Reject,
+}
+
+/// Fields holds field level access of entity columns.
+/// Read only columns are returned and never written by client, write only columns are written
+/// and never returned. JSON keys are matched by column names.
+#[derive(Clone, Debug)]
+pub struct Fields<E>
+where
+    E: EntityTrait,
+{
+    read_only: Vec<E::Column>,
+    write_only: Vec<E::Column>,
+    mode: ReadOnlyMode,
+}
+
+impl<E> Default for Fields<E>
+where
+    E: EntityTrait,
+{
+    fn default() -> Self {
+        Self {
+            read_only: Vec::new(),
+            write_only: Vec::new(),
+            mode: ReadOnlyMode::default(),
+        }
+    }
+}
+
+impl<E> Fields<E>
+where
+    E: EntityTrait,
+{
+    /// with_read_only adds read only columns.
+    pub fn with_read_only(mut self, columns: impl IntoIterator<Item = E::Column>) -> Self {
+        self.read_only.extend(columns);
+        self
+    }
+
+    /// with_write_only adds write only columns.
+    pub fn with_write_only(mut self, columns: impl IntoIterator<Item = E::Column>) -> Self {
+        self.write_only.extend(columns);
+        self
+    }
+
+    /// with_read_only_mode sets what happens when client sets read only field.
+    pub fn with_read_only_mode(mut self, mode: ReadOnlyMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// is_read_only returns whether the column is read only.
+    pub fn is_read_only(&self, column: &E::Column) -> bool {
+        self.read_only.iter().any(|c| c.as_str() == column.as_str())
+    }
+
+    /// check_input returns error for read only field present in request body (only in Reject mode).
+    /// Body can be single object or array of objects, invalid body is left to the deserializer.
+    pub fn check_input(&self, body: &[u8]) -> Result<(), crate::Error> {
+        if self.mode != ReadOnlyMode::Reject || self.read_only.is_empty() {
+            return Ok(());
+        }
+
+        match serde_json::from_slice(body) {
+            Ok(value) => self.check_value(&value),
+            Err(_) => Ok(()),
+        }
+    }
+
+    /// check_value checks single object or array of objects for read only fields.
+    fn check_value(&self, value: &serde_json::Value) -> Result<(), crate::Error> {
+        match value {
+            serde_json::Value::Array(items) => items.iter().try_for_each(|i| self.check_value(i)),
+            serde_json::Value::Object(object) => match self
+                .read_only
+                .iter()
+                .find(|column| object.contains_key(column.as_str()))
+            {
+                Some(column) => Err(crate::Error::ReadOnlyField(column.as_str().to_owned())),
+                None => Ok(()),
+            },
+            _ => Ok(()),
+        }
+    }
+
+    /// unset_read_only leaves read only columns not set for insert, so database default is used.
+    /// Columns changed after deserialization (e.g. by before save handler) are kept.
+    pub fn unset_read_only(
+        &self,
+        received: &E::Model,
+        instance: &E::Model,
+        active: &mut E::ActiveModel,
+    ) where
+        E::ActiveModel: ActiveModelTrait<Entity = E>,
+    {
+        for column in self.read_only.iter() {
+            if instance.get(*column) == received.get(*column) {
+                active.not_set(*column);
+            }
+        }
+    }
+
+    /// keep_read_only sets read only columns back to original values.
+    /// Columns changed after deserialization (e.g. by before save handler) are kept.
+    pub fn keep_read_only(
+        &self,
+        original: &E::Model,
+        received: &E::Model,
+        instance: E::Model,
+    ) -> E::Model {
+        let mut instance = instance;
+        for column in self.read_only.iter() {
+            if instance.get(*column) == received.get(*column) {
+                instance.set(*column, original.get(*column));
+            }
+        }
+        instance
+    }
+
+    /// strip_output removes write only fields from serialized object (or array of objects).
+    pub fn strip_output(&self, value: &mut serde_json::Value) {
+        match value {
+            serde_json::Value::Array(items) => {
+                items.iter_mut().for_each(|item| self.strip_output(item))
+            }
+            serde_json::Value::Object(object) => {
+                for column in self.write_only.iter() {
+                    object.remove(column.as_str());
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sea_orm::IntoActiveModel;
+    use sea_orm::entity::prelude::*;
+    use serde_json::json;
+
+    #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+    #[sea_orm(table_name = "user")]
+    pub struct Model {
+        #[sea_orm(primary_key)]
+        pub id: i32,
+        pub username: String,
+        pub password: String,
+        pub created: i64,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+
+    fn model(id: i32, password: &str, created: i64) -> Model {
+        Model {
+            id,
+            username: "phonkee".to_owned(),
+            password: password.to_owned(),
+            created,
+        }
+    }
+
+    fn fields() -> Fields<Entity> {
+        Fields::default()
+            .with_read_only([Column::Id, Column::Created])
+            .with_write_only([Column::Password])
+    }
+
+    #[test]
+    fn test_check_input() {
+        let body = br#"{"username": "phonkee", "created": 1}"#;
+        assert!(fields().check_input(body).is_ok());
+
+        let fields = fields().with_read_only_mode(ReadOnlyMode::Reject);
+        assert!(fields.check_input(br#"{"username": "phonkee"}"#).is_ok());
+        assert!(matches!(
+            fields.check_input(body),
+            Err(crate::Error::ReadOnlyField(field)) if field == "created"
+        ));
+        assert!(
+            fields
+                .check_input(br#"[{"username": "a"}, {"id": 1}]"#)
+                .is_err()
+        );
+        assert!(fields.check_input(b"not json").is_ok());
+    }
+
+    #[test]
+    fn test_unset_read_only() {
+        let received = model(5, "secret", 1);
+        // created was changed by before save handler
+        let instance = model(5, "secret", 2);
+        let mut active = instance.clone().into_active_model();
+        fields().unset_read_only(&received, &instance, &mut active);
+
+        assert!(active.id.is_not_set());
+        assert_eq!(active.created, sea_orm::ActiveValue::Unchanged(2));
+        assert!(!active.password.is_not_set());
+    }
+
+    #[test]
+    fn test_keep_read_only() {
+        let original = model(1, "old", 1);
+        let received = model(5, "new", 5);
+        let instance = fields().keep_read_only(&original, &received, received.clone());
+
+        assert_eq!(instance, model(1, "new", 1));
+    }
+
+    #[test]
+    fn test_strip_output() {
+        let mut value = json!([{"id": 1, "password": "secret"}, {"id": 2}]);
+        fields().strip_output(&mut value);
+        assert_eq!(value, json!([{"id": 1}, {"id": 2}]));
+    }
+}
@@ -23,15 +23,21 @@
  *
  */
 
+mod active;
 mod callback;
 mod columns;
 mod error;
 mod expr;
+mod fields;
+mod patch;
 mod update;
 
+pub use active::{insert_active_model, update_active_model, update_versioned};
 pub use callback::{ModelCallback, ModelCallbackErased, ModelCallbacks};
-pub use columns::primary_key;
+pub use columns::{primary_key, primary_key_condition, primary_keys_condition, version_condition};
 pub use error::Error;
 pub use expr::to_simple_expr;
+pub use fields::{Fields, ReadOnlyMode};
+pub use patch::{patch_columns, patch_model};
 
-pub use update::UpdateModelTrait;
+pub use update::{ReplaceModel, UpdateModelTrait};
@@ -0,0 +1,131 @@
+/*
+ *  The MIT License (MIT)
+ *
+ *  Copyright (c) 2024-2025, Peter Vrba
+ *
+ *  Permission is hereby granted, free of charge, to any person obtaining a copy
+ *  of this software and associated documentation files (the "Software"), to deal
+ *  in the Software without restriction, including without limitation the rights
+ *  to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ *  copies of the Software, and to permit persons to whom the Software is
+ *  furnished to do so, subject to the following conditions:
+ *
+ *  The above copyright notice and this permission notice shall be included in
+ *  all copies or substantial portions of the Software.
+ *
+ *  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ *  IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ *  FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ *  AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ *  LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ *  OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+ *  THE SOFTWARE.
+ *
+ */
+
+use sea_orm::{EntityTrait, PrimaryKeyToColumn};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::str::FromStr;
+
+/// patch_columns returns columns set by the patch (JSON object with column names as keys).
+/// Unknown columns and primary key columns are rejected.
+pub fn patch_columns<E>(
+    patch: &serde_json::Map<String, serde_json::Value>,
+) -> Result<Vec<E::Column>, crate::Error>
+where
+    E: EntityTrait,
+{
+    patch
+        .keys()
+        .map(|key| {
+            let column = E::Column::from_str(key)
+                .map_err(|_| crate::Error::InvalidPatch(format!("unknown column `{}`", key)))?;
+            if E::PrimaryKey::from_column(column).is_some() {
+                return Err(crate::Error::InvalidPatch(format!(
+                    "primary key column `{}` cannot be patched",
+                    key
+                )));
+            }
+            Ok(column)
+        })
+        .collect()
+}
+
+/// patch_model applies patch (JSON object) on the model, values are converted by model deserialization.
+pub fn patch_model<E>(
+    model: E::Model,
+    patch: &serde_json::Map<String, serde_json::Value>,
+) -> Result<E::Model, crate::Error>
+where
+    E: EntityTrait,
+    E::Model: Serialize + DeserializeOwned,
+{
+    let mut value =
+        serde_json::to_value(model).map_err(|e| crate::Error::InternalError(e.to_string()))?;
+    let serde_json::Value::Object(object) = &mut value else {
+        return Err(crate::Error::InternalError(
+            "model is not serialized as object".to_owned(),
+        ));
+    };
+    for (key, patched) in patch {
+        object.insert(key.clone(), patched.clone());
+    }
+    serde_json::from_value(value).map_err(|e| crate::Error::InvalidPatch(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sea_orm::entity::prelude::*;
+
+    #[derive(
+        Clone, Debug, PartialEq, Eq, DeriveEntityModel, serde::Serialize, serde::Deserialize,
+    )]
+    #[sea_orm(table_name = "order")]
+    pub struct Model {
+        #[sea_orm(primary_key)]
+        pub id: i32,
+        pub status: String,
+        pub quantity: i32,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+
+    fn patch(value: serde_json::Value) -> serde_json::Map<String, serde_json::Value> {
+        value.as_object().unwrap().clone()
+    }
+
+    #[test]
+    fn test_patch_columns() {
+        let columns = patch_columns::<Entity>(&patch(serde_json::json!({"status": "x"}))).unwrap();
+        assert_eq!(columns.len(), 1);
+        assert!(matches!(columns[0], Column::Status));
+        assert!(patch_columns::<Entity>(&patch(serde_json::json!({"id": 1}))).is_err());
+        assert!(patch_columns::<Entity>(&patch(serde_json::json!({"unknown": 1}))).is_err());
+    }
+
+    #[test]
+    fn test_patch_model() {
+        let model = Model {
+            id: 1,
+            status: "new".to_owned(),
+            quantity: 3,
+        };
+        let patched = patch_model::<Entity>(
+            model.clone(),
+            &patch(serde_json::json!({"status": "shipped"})),
+        )
+        .unwrap();
+        assert_eq!(patched.status, "shipped");
+        assert_eq!(patched.quantity, 3);
+
+        assert!(matches!(
+            patch_model::<Entity>(model, &patch(serde_json::json!({"quantity": "many"}))),
+            Err(crate::Error::InvalidPatch(_))
+        ));
+    }
+}
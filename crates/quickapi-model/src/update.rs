@@ -33,3 +33,53 @@ where
     /// Update model with the given value.
     fn update(&self, value: E::Model) -> Result<E::Model, crate::Error>;
 }
+
+/// ReplaceModel is default update serializer, it replaces all columns of the model
+/// except primary key with values from request body.
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(transparent, bound = "E::Model: DeserializeOwned")]
+pub struct ReplaceModel<E>(pub E::Model)
+where
+    E: sea_orm::EntityTrait;
+
+impl<E> UpdateModelTrait<E> for ReplaceModel<E>
+where
+    E: sea_orm::EntityTrait + Send + Sync + 'static,
+    <E as sea_orm::EntityTrait>::Model: DeserializeOwned + Clone + Send + Sync + 'static,
+{
+    fn update(&self, _value: E::Model) -> Result<E::Model, crate::Error> {
+        Ok(self.0.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sea_orm::entity::prelude::*;
+
+    #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, serde::Deserialize)]
+    #[sea_orm(table_name = "user")]
+    pub struct Model {
+        #[sea_orm(primary_key)]
+        pub id: i32,
+        pub username: String,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+
+    #[test]
+    fn test_replace_model() {
+        let replace: ReplaceModel<Entity> =
+            serde_json::from_str(r#"{"id": 0, "username": "new"}"#).unwrap();
+        let updated = replace
+            .update(Model {
+                id: 1,
+                username: "old".to_owned(),
+            })
+            .unwrap();
+        assert_eq!(updated.username, "new");
+    }
+}
@@ -0,0 +1,242 @@
+/*
+ *  The MIT License (MIT)
+ *
+ *  Copyright (c) 2024-2025, Peter Vrba
+ *
+ *  Permission is hereby granted, free of charge, to any person obtaining a copy
+ *  of this software and associated documentation files (the "Software"), to deal
+ *  in the Software without restriction, including without limitation the rights
+ *  to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ *  copies of the Software, and to permit persons to whom the Software is
+ *  furnished to do so, subject to the following conditions:
+ *
+ *  The above copyright notice and this permission notice shall be included in
+ *  all copies or substantial portions of the Software.
+ *
+ *  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ *  IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ *  FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ *  AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ *  LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ *  OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+ *  THE SOFTWARE.
+ *
+ */
+
+use crate::Permission;
+use axum::http::Method;
+use axum::http::request::Parts;
+use sea_orm::EntityTrait;
+use std::marker::PhantomData;
+
+/// AllowAny allows every request.
+#[derive(Clone, Debug, Default)]
+pub struct AllowAny;
+
+#[async_trait::async_trait]
+impl<E, S> Permission<E, S> for AllowAny
+where
+    E: EntityTrait + Send + Sync + 'static,
+    S: Clone + Send + Sync + 'static,
+{
+    async fn has_permission(&self, _parts: &mut Parts, _state: &S) -> Result<(), crate::Error> {
+        Ok(())
+    }
+}
+
+/// IsAuthenticated allows the request when extension of type U is present in request.
+/// Authentication middleware is expected to insert the principal (e.g. user) into extensions.
+pub struct IsAuthenticated<U>(PhantomData<fn() -> U>);
+
+impl<U> IsAuthenticated<U> {
+    /// new creates IsAuthenticated permission checking for extension of type U.
+    pub fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<U> Default for IsAuthenticated<U> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<U> Clone for IsAuthenticated<U> {
+    fn clone(&self) -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait::async_trait]
+impl<E, S, U> Permission<E, S> for IsAuthenticated<U>
+where
+    E: EntityTrait + Send + Sync + 'static,
+    S: Clone + Send + Sync + 'static,
+    U: Send + Sync + 'static,
+{
+    async fn has_permission(&self, parts: &mut Parts, _state: &S) -> Result<(), crate::Error> {
+        match parts.extensions.get::<U>() {
+            Some(_) => Ok(()),
+            None => Err(crate::Error::Denied("authentication required".to_owned())),
+        }
+    }
+}
+
+/// ReadOnly allows only safe methods (GET, HEAD, OPTIONS).
+#[derive(Clone, Debug, Default)]
+pub struct ReadOnly;
+
+#[async_trait::async_trait]
+impl<E, S> Permission<E, S> for ReadOnly
+where
+    E: EntityTrait + Send + Sync + 'static,
+    S: Clone + Send + Sync + 'static,
+{
+    async fn has_permission(&self, parts: &mut Parts, _state: &S) -> Result<(), crate::Error> {
+        match parts.method {
+            Method::GET | Method::HEAD | Method::OPTIONS => Ok(()),
+            _ => Err(crate::Error::Denied("read only".to_owned())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Error, PermissionExt, Permissions};
+    use sea_orm::entity::prelude::*;
+
+    #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+    #[sea_orm(table_name = "user")]
+    pub struct Model {
+        #[sea_orm(primary_key)]
+        pub id: i32,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+
+    #[derive(Clone)]
+    struct User;
+
+    // parts prepares request parts for given method
+    fn parts(method: Method) -> Parts {
+        axum::http::Request::builder()
+            .method(method)
+            .uri("/")
+            .body(())
+            .unwrap()
+            .into_parts()
+            .0
+    }
+
+    // allowed checks the permission against parts
+    async fn allowed(p: impl Permission<Entity, ()>, mut parts: Parts) -> bool {
+        p.has_permission(&mut parts, &()).await.is_ok()
+    }
+
+    #[tokio::test]
+    async fn test_builtin() {
+        assert!(allowed(AllowAny, parts(Method::DELETE)).await);
+        assert!(allowed(ReadOnly, parts(Method::GET)).await);
+        assert!(!allowed(ReadOnly, parts(Method::POST)).await);
+        assert!(!allowed(IsAuthenticated::<User>::new(), parts(Method::GET)).await);
+
+        let mut authenticated = parts(Method::GET);
+        authenticated.extensions.insert(User);
+        assert!(allowed(IsAuthenticated::<User>::new(), authenticated).await);
+    }
+
+    #[tokio::test]
+    async fn test_compose() {
+        let p = || PermissionExt::<Entity, ()>::or(ReadOnly, IsAuthenticated::<User>::new());
+        assert!(allowed(p(), parts(Method::GET)).await);
+        assert!(!allowed(p(), parts(Method::POST)).await);
+
+        let p = PermissionExt::<Entity, ()>::and(ReadOnly, IsAuthenticated::<User>::new());
+        assert!(!allowed(p, parts(Method::GET)).await);
+
+        let mut permissions = Permissions::<Entity, ()>::new();
+        permissions.push(AllowAny);
+        permissions.push(ReadOnly);
+        assert!(allowed(permissions.clone(), parts(Method::GET)).await);
+        assert!(!allowed(permissions, parts(Method::PATCH)).await);
+    }
+
+    #[derive(Clone)]
+    struct CurrentUser {
+        id: i32,
+        admin: bool,
+    }
+
+    // IsOwner allows objects owned by current user
+    #[derive(Clone)]
+    struct IsOwner;
+
+    #[async_trait::async_trait]
+    impl Permission<Entity, ()> for IsOwner {
+        async fn has_permission(&self, _parts: &mut Parts, _state: &()) -> Result<(), Error> {
+            Ok(())
+        }
+
+        async fn has_object_permission(
+            &self,
+            parts: &mut Parts,
+            _state: &(),
+            model: &Model,
+        ) -> Result<(), Error> {
+            match parts.extensions.get::<CurrentUser>() {
+                Some(user) if user.id == model.id => Ok(()),
+                _ => Err(Error::Denied("not an owner".to_owned())),
+            }
+        }
+    }
+
+    // IsAdmin allows admins to do anything
+    #[derive(Clone)]
+    struct IsAdmin;
+
+    #[async_trait::async_trait]
+    impl Permission<Entity, ()> for IsAdmin {
+        async fn has_permission(&self, parts: &mut Parts, _state: &()) -> Result<(), Error> {
+            match parts.extensions.get::<CurrentUser>() {
+                Some(user) if user.admin => Ok(()),
+                _ => Err(Error::Denied("not an admin".to_owned())),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_or_object_permission() {
+        let p = PermissionExt::<Entity, ()>::or(IsOwner, IsAdmin);
+
+        // object_allowed checks both the request and the object (as views do)
+        let object_allowed = async |user: CurrentUser| {
+            let mut parts = parts(Method::DELETE);
+            parts.extensions.insert(user);
+            p.has_permission(&mut parts, &()).await.is_ok()
+                && p.has_object_permission(&mut parts, &(), &Model { id: 1 })
+                    .await
+                    .is_ok()
+        };
+
+        assert!(
+            object_allowed(CurrentUser {
+                id: 1,
+                admin: false
+            })
+            .await
+        );
+        assert!(object_allowed(CurrentUser { id: 2, admin: true }).await);
+        // neither owner nor admin
+        assert!(
+            !object_allowed(CurrentUser {
+                id: 2,
+                admin: false
+            })
+            .await
+        );
+    }
+}
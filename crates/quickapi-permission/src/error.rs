@@ -0,0 +1,57 @@
+/*
+ *  The MIT License (MIT)
+ *
+ *  Copyright (c) 2024-2025, Peter Vrba
+ *
+ *  Permission is hereby granted, free of charge, to any person obtaining a copy
+ *  of this software and associated documentation files (the "Software"), to deal
+ *  in the Software without restriction, including without limitation the rights
+ *  to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ *  copies of the Software, and to permit persons to whom the Software is
+ *  furnished to do so, subject to the following conditions:
+ *
+ *  The above copyright notice and this permission notice shall be included in
+ *  all copies or substantial portions of the Software.
+ *
+ *  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ *  IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ *  FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ *  AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ *  LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ *  OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+ *  THE SOFTWARE.
+ *
+ */
+use axum::http::StatusCode;
+use quickapi_http::Response;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// Denied means the request is not allowed, view responds with 403.
+    #[error("Permission denied: {0}")]
+    Denied(String),
+
+    /// Response short-circuits the view with a custom response (e.g. 401).
+    #[error("Response: {}", .0.status())]
+    Response(Box<Response>),
+
+    #[error("{0}")]
+    InternalError(String),
+}
+
+/// Convert permission error into view error, denied requests respond with 403.
+impl From<Error> for quickapi_view::Error {
+    fn from(err: Error) -> Self {
+        match err {
+            Error::Denied(reason) => quickapi_view::Error::Response(Box::new(
+                Response::new(serde_json::json!({
+                    "error": "Forbidden",
+                    "message": reason,
+                }))
+                .with_status(StatusCode::FORBIDDEN),
+            )),
+            Error::Response(response) => quickapi_view::Error::Response(response),
+            Error::InternalError(e) => quickapi_view::Error::InternalError(e.into()),
+        }
+    }
+}
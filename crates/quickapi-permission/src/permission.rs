@@ -0,0 +1,265 @@
+/*
+ *  The MIT License (MIT)
+ *
+ *  Copyright (c) 2024-2025, Peter Vrba
+ *
+ *  Permission is hereby granted, free of charge, to any person obtaining a copy
+ *  of this software and associated documentation files (the "Software"), to deal
+ *  in the Software without restriction, including without limitation the rights
+ *  to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ *  copies of the Software, and to permit persons to whom the Software is
+ *  furnished to do so, subject to the following conditions:
+ *
+ *  The above copyright notice and this permission notice shall be included in
+ *  all copies or substantial portions of the Software.
+ *
+ *  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ *  IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ *  FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ *  AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ *  LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ *  OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+ *  THE SOFTWARE.
+ *
+ */
+
+use axum::http::request::Parts;
+use dyn_clone::DynClone;
+use sea_orm::EntityTrait;
+
+/// Permission decides whether the request is allowed to access the view and its objects.
+/// Denied requests return `Error::Denied` and view responds with 403.
+#[async_trait::async_trait]
+pub trait Permission<E, S>: Send + Sync + DynClone
+where
+    E: EntityTrait + Send + Sync + 'static,
+    S: Clone + Send + Sync + 'static,
+{
+    /// has_permission is called before the view runs its query.
+    async fn has_permission(&self, parts: &mut Parts, state: &S) -> Result<(), crate::Error>;
+
+    /// has_object_permission is called for object after it was looked up (or before it's created).
+    async fn has_object_permission(
+        &self,
+        _parts: &mut Parts,
+        _state: &S,
+        _model: &E::Model,
+    ) -> Result<(), crate::Error>
+    where
+        E::Model: Sync,
+    {
+        Ok(())
+    }
+}
+
+dyn_clone::clone_trait_object!(<E, S> Permission<E, S>);
+
+/// PermissionExt provides combinators for permissions.
+pub trait PermissionExt<E, S>: Permission<E, S> + Clone + Sized
+where
+    E: EntityTrait + Send + Sync + 'static,
+    S: Clone + Send + Sync + 'static,
+{
+    /// and allows the request only when both permissions allow it.
+    fn and<P>(self, other: P) -> And<Self, P>
+    where
+        P: Permission<E, S> + Clone,
+    {
+        And(self, other)
+    }
+
+    /// or allows the request when any of the permissions allows it.
+    fn or<P>(self, other: P) -> Or<Self, P>
+    where
+        P: Permission<E, S> + Clone,
+    {
+        Or(self, other)
+    }
+}
+
+impl<E, S, P> PermissionExt<E, S> for P
+where
+    E: EntityTrait + Send + Sync + 'static,
+    S: Clone + Send + Sync + 'static,
+    P: Permission<E, S> + Clone,
+{
+}
+
+/// And allows the request only when both permissions allow it.
+#[derive(Clone, Debug)]
+pub struct And<A, B>(A, B);
+
+#[async_trait::async_trait]
+impl<E, S, A, B> Permission<E, S> for And<A, B>
+where
+    E: EntityTrait + Send + Sync + 'static,
+    S: Clone + Send + Sync + 'static,
+    A: Permission<E, S> + Clone,
+    B: Permission<E, S> + Clone,
+{
+    async fn has_permission(&self, parts: &mut Parts, state: &S) -> Result<(), crate::Error> {
+        self.0.has_permission(parts, state).await?;
+        self.1.has_permission(parts, state).await
+    }
+
+    async fn has_object_permission(
+        &self,
+        parts: &mut Parts,
+        state: &S,
+        model: &E::Model,
+    ) -> Result<(), crate::Error>
+    where
+        E::Model: Sync,
+    {
+        self.0.has_object_permission(parts, state, model).await?;
+        self.1.has_object_permission(parts, state, model).await
+    }
+}
+
+/// Or allows the request when any of the permissions allows it.
+/// Object permission is allowed only by permission that allows both the request and the object,
+/// so `IsOwner.or(IsAdmin)` does not allow object of other user to non admin.
+/// Only denials are recovered from, other errors are returned immediately.
+#[derive(Clone, Debug)]
+pub struct Or<A, B>(A, B);
+
+#[async_trait::async_trait]
+impl<E, S, A, B> Permission<E, S> for Or<A, B>
+where
+    E: EntityTrait + Send + Sync + 'static,
+    S: Clone + Send + Sync + 'static,
+    A: Permission<E, S> + Clone,
+    B: Permission<E, S> + Clone,
+{
+    async fn has_permission(&self, parts: &mut Parts, state: &S) -> Result<(), crate::Error> {
+        match self.0.has_permission(parts, state).await {
+            Err(crate::Error::Denied(_)) => self.1.has_permission(parts, state).await,
+            result => result,
+        }
+    }
+
+    async fn has_object_permission(
+        &self,
+        parts: &mut Parts,
+        state: &S,
+        model: &E::Model,
+    ) -> Result<(), crate::Error>
+    where
+        E::Model: Sync,
+    {
+        match allows_object(&self.0, parts, state, model).await {
+            Err(crate::Error::Denied(_)) => allows_object(&self.1, parts, state, model).await,
+            result => result,
+        }
+    }
+}
+
+/// allows_object checks that permission allows both the request and the object.
+async fn allows_object<E, S, P>(
+    permission: &P,
+    parts: &mut Parts,
+    state: &S,
+    model: &E::Model,
+) -> Result<(), crate::Error>
+where
+    E: EntityTrait + Send + Sync + 'static,
+    S: Clone + Send + Sync + 'static,
+    P: Permission<E, S>,
+    E::Model: Sync,
+{
+    permission.has_permission(parts, state).await?;
+    permission.has_object_permission(parts, state, model).await
+}
+
+/// Permissions is a collection of permissions, all of them must allow the request.
+pub struct Permissions<E, S>
+where
+    E: EntityTrait + Send + Sync + 'static,
+    S: Clone + Send + Sync + 'static,
+{
+    pub(crate) inner: Vec<Box<dyn Permission<E, S>>>,
+}
+
+impl<E, S> Default for Permissions<E, S>
+where
+    E: EntityTrait + Send + Sync + 'static,
+    S: Clone + Send + Sync + 'static,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Implement Clone for Permissions
+impl<E, S> Clone for Permissions<E, S>
+where
+    E: EntityTrait + Send + Sync + 'static,
+    S: Clone + Send + Sync + 'static,
+{
+    fn clone(&self) -> Self {
+        Self {
+            inner: self
+                .inner
+                .iter()
+                .map(|p| dyn_clone::clone_box(&**p))
+                .collect(),
+        }
+    }
+}
+
+impl<E, S> Permissions<E, S>
+where
+    E: EntityTrait + Send + Sync + 'static,
+    S: Clone + Send + Sync + 'static,
+{
+    /// new creates empty Permissions, which allow everything.
+    pub fn new() -> Self {
+        Self { inner: Vec::new() }
+    }
+
+    /// push adds a permission to the collection.
+    pub fn push(&mut self, permission: impl Permission<E, S> + 'static) {
+        self.inner.push(Box::new(permission));
+    }
+
+    /// clear removes all permissions.
+    pub fn clear(&mut self) {
+        self.inner.clear();
+    }
+
+    /// is_empty returns true when there are no permissions.
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+}
+
+#[async_trait::async_trait]
+impl<E, S> Permission<E, S> for Permissions<E, S>
+where
+    E: EntityTrait + Send + Sync + 'static,
+    S: Clone + Send + Sync + 'static,
+{
+    async fn has_permission(&self, parts: &mut Parts, state: &S) -> Result<(), crate::Error> {
+        for permission in &self.inner {
+            permission.has_permission(parts, state).await?;
+        }
+        Ok(())
+    }
+
+    async fn has_object_permission(
+        &self,
+        parts: &mut Parts,
+        state: &S,
+        model: &E::Model,
+    ) -> Result<(), crate::Error>
+    where
+        E::Model: Sync,
+    {
+        for permission in &self.inner {
+            permission
+                .has_object_permission(parts, state, model)
+                .await?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,199 @@
+/*
+ *  The MIT License (MIT)
+ *
+ *  Copyright (c) 2024-2025, Peter Vrba
+ *
+ *  Permission is hereby granted, free of charge, to any person obtaining a copy
+ *  of this software and associated documentation files (the "Software"), to deal
+ *  in the Software without restriction, including without limitation the rights
+ *  to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ *  copies of the Software, and to permit persons to whom the Software is
+ *  furnished to do so, subject to the following conditions:
+ *
+ *  The above copyright notice and this permission notice shall be included in
+ *  all copies or substantial portions of the Software.
+ *
+ *  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ *  IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ *  FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ *  AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ *  LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ *  OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+ *  THE SOFTWARE.
+ *
+ */
+
+use crate::Error;
+use quickapi::QuickApi;
+use sea_orm::{
+    ActiveModelTrait, ConnectionTrait, Database, DatabaseConnection, DbBackend, EntityTrait,
+    IntoActiveModel, Schema,
+};
+use std::sync::{Arc, Mutex};
+
+/// TestDb is in-memory SQLite database for tests.
+/// It records executed SQL statements, so tests can assert on them.
+///
+/// sea-orm MockDatabase is not supported: its `mock` feature makes DatabaseConnection non-Clone,
+/// while quickapi views hold their own connection clones.
+#[derive(Clone, Debug)]
+pub struct TestDb {
+    db: DatabaseConnection,
+    log: Arc<Mutex<Vec<String>>>,
+}
+
+impl TestDb {
+    /// sqlite creates TestDb over fresh in-memory SQLite database.
+    pub async fn sqlite() -> Result<Self, Error> {
+        let mut db = Database::connect("sqlite::memory:").await?;
+        let log: Arc<Mutex<Vec<String>>> = Default::default();
+
+        // record statements (callback must be set before connection is cloned)
+        let recorder = log.clone();
+        db.set_metric_callback(move |info| {
+            recorder
+                .lock()
+                .expect("statement log poisoned")
+                .push(info.statement.to_string());
+        });
+
+        Ok(Self { db, log })
+    }
+
+    /// connection returns underlying database connection.
+    pub fn connection(&self) -> &DatabaseConnection {
+        &self.db
+    }
+
+    /// backend returns database backend of the connection.
+    pub fn backend(&self) -> DbBackend {
+        self.db.get_database_backend()
+    }
+
+    /// api returns new QuickApi instance over this database.
+    pub fn api<S>(&self) -> QuickApi<S> {
+        quickapi::new(self.db.clone())
+    }
+
+    /// create_table creates table for given entity.
+    pub async fn create_table<E>(&self, entity: E) -> Result<(), Error>
+    where
+        E: EntityTrait,
+    {
+        let backend = self.backend();
+        let statement = Schema::new(backend).create_table_from_entity(entity);
+        self.db.execute(backend.build(&statement)).await?;
+        Ok(())
+    }
+
+    /// load_fixtures inserts rows for entity E from JSON array of objects.
+    /// Every object must contain all model fields (including primary key).
+    pub async fn load_fixtures<E>(&self, fixtures: serde_json::Value) -> Result<usize, Error>
+    where
+        E: EntityTrait,
+        E::Model: IntoActiveModel<E::ActiveModel> + for<'de> serde::Deserialize<'de>,
+        E::ActiveModel: ActiveModelTrait<Entity = E> + Send,
+    {
+        let serde_json::Value::Array(rows) = fixtures else {
+            return Err(Error::FixtureError(
+                "fixtures must be an array of objects".to_owned(),
+            ));
+        };
+
+        let count = rows.len();
+        for row in rows {
+            let model: E::Model =
+                serde_json::from_value(row).map_err(|err| Error::FixtureError(err.to_string()))?;
+            E::insert(model.into_active_model()).exec(&self.db).await?;
+        }
+
+        Ok(count)
+    }
+
+    /// statements returns SQL statements executed since last call (and clears the log).
+    pub fn statements(&self) -> Vec<String> {
+        std::mem::take(&mut *self.log.lock().expect("statement log poisoned"))
+    }
+
+    /// assert_sql asserts that exactly given statements were executed (and clears the log).
+    #[track_caller]
+    pub fn assert_sql<I, T>(&self, expected: I)
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        let expected: Vec<String> = expected.into_iter().map(Into::into).collect();
+        assert_eq!(self.statements(), expected);
+    }
+
+    /// assert_sql_contains asserts that some executed statement contains given fragment (and clears the log).
+    #[track_caller]
+    pub fn assert_sql_contains(&self, fragment: &str) {
+        let statements = self.statements();
+        assert!(
+            statements
+                .iter()
+                .any(|statement| statement.contains(fragment)),
+            "no statement contains {:?}, executed: {:#?}",
+            fragment,
+            statements
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sea_orm::entity::prelude::*;
+    use serde::{Deserialize, Serialize};
+    use serde_json::json;
+
+    #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+    #[sea_orm(table_name = "user")]
+    pub struct Model {
+        #[sea_orm(primary_key)]
+        pub id: i32,
+        pub username: String,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+
+    #[tokio::test]
+    async fn test_load_fixtures() {
+        let db = TestDb::sqlite().await.unwrap();
+        db.create_table(Entity).await.unwrap();
+        db.assert_sql_contains("CREATE TABLE \"user\"");
+
+        let loaded = db
+            .load_fixtures::<Entity>(json!([
+                {"id": 1, "username": "joe"},
+                {"id": 2, "username": "anna"},
+            ]))
+            .await
+            .unwrap();
+        assert_eq!(loaded, 2);
+        assert_eq!(db.statements().len(), 2);
+
+        let users = Entity::find().all(db.connection()).await.unwrap();
+        assert_eq!(users.len(), 2);
+        db.assert_sql(["SELECT \"user\".\"id\", \"user\".\"username\" FROM \"user\""]);
+    }
+
+    #[tokio::test]
+    async fn test_load_fixtures_invalid() {
+        let db = TestDb::sqlite().await.unwrap();
+        db.create_table(Entity).await.unwrap();
+
+        assert!(matches!(
+            db.load_fixtures::<Entity>(json!({"id": 1})).await,
+            Err(Error::FixtureError(_))
+        ));
+        assert!(matches!(
+            db.load_fixtures::<Entity>(json!([{"id": 1}])).await,
+            Err(Error::FixtureError(_))
+        ));
+    }
+}
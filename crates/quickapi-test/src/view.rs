@@ -0,0 +1,307 @@
+/*
+ *  The MIT License (MIT)
+ *
+ *  Copyright (c) 2024-2025, Peter Vrba
+ *
+ *  Permission is hereby granted, free of charge, to any person obtaining a copy
+ *  of this software and associated documentation files (the "Software"), to deal
+ *  in the Software without restriction, including without limitation the rights
+ *  to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ *  copies of the Software, and to permit persons to whom the Software is
+ *  furnished to do so, subject to the following conditions:
+ *
+ *  The above copyright notice and this permission notice shall be included in
+ *  all copies or substantial portions of the Software.
+ *
+ *  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ *  IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ *  FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ *  AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ *  LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ *  OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+ *  THE SOFTWARE.
+ *
+ */
+
+use crate::Error;
+use axum_test::{TestRequest, TestServer};
+use quickapi::RouterExt;
+
+/// TestView serves view through axum_test::TestServer.
+/// View is registered to axum router the same way as in application (register_router), so
+/// requests go through quickapi handler (body limit, idempotency, envelope, database backend).
+pub struct TestView {
+    server: TestServer,
+}
+
+impl TestView {
+    /// new registers view (under its own path) with given state and serves it.
+    pub fn new<S, V>(view: V, state: S) -> Result<Self, Error>
+    where
+        S: Clone + Send + Sync + 'static,
+        V: RouterExt<S>,
+    {
+        let router = view
+            .register_router(axum::Router::new())
+            .map_err(|err| Error::RouterError(err.to_string()))?;
+        Self::from_router(router.with_state(state))
+    }
+
+    /// from_router serves already built router (e.g. multiple views or prefix).
+    pub fn from_router(router: axum::Router) -> Result<Self, Error> {
+        let server = TestServer::new(router).map_err(|err| Error::ServerError(err.to_string()))?;
+        Ok(Self { server })
+    }
+
+    /// server returns underlying axum_test::TestServer.
+    pub fn server(&self) -> &TestServer {
+        &self.server
+    }
+
+    /// get prepares GET request.
+    pub fn get(&self, path: &str) -> TestRequest {
+        self.server.get(path)
+    }
+
+    /// post prepares POST request with JSON body.
+    pub fn post(&self, path: &str, body: serde_json::Value) -> TestRequest {
+        self.server.post(path).json(&body)
+    }
+
+    /// put prepares PUT request with JSON body.
+    pub fn put(&self, path: &str, body: serde_json::Value) -> TestRequest {
+        self.server.put(path).json(&body)
+    }
+
+    /// patch prepares PATCH request with JSON body.
+    pub fn patch(&self, path: &str, body: serde_json::Value) -> TestRequest {
+        self.server.patch(path).json(&body)
+    }
+
+    /// delete prepares DELETE request.
+    pub fn delete(&self, path: &str) -> TestRequest {
+        self.server.delete(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestDb;
+    use axum::http::StatusCode;
+    use quickapi::prelude::*;
+    use sea_orm::entity::prelude::*;
+    use serde::{Deserialize, Serialize};
+    use serde_json::json;
+
+    #[derive(Clone, Debug, Default, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+    #[sea_orm(table_name = "user")]
+    pub struct Model {
+        #[sea_orm(primary_key)]
+        pub id: i32,
+        pub username: String,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+
+    async fn setup() -> TestDb {
+        let db = TestDb::sqlite().await.unwrap();
+        db.create_table(Entity).await.unwrap();
+        db.load_fixtures::<Entity>(json!([
+            {"id": 1, "username": "Joe"},
+            {"id": 2, "username": "joey"},
+            {"id": 3, "username": "Anna"},
+        ]))
+        .await
+        .unwrap();
+        db.statements();
+        db
+    }
+
+    #[tokio::test]
+    async fn test_list_view() {
+        let db = setup().await;
+        let view = db.api::<()>().list::<Entity>("/api/user").unwrap();
+        let client = TestView::new(view, ()).unwrap();
+
+        let response = client.get("/api/user").await;
+        response.assert_status_ok();
+        response.assert_json(&json!({"objects": [
+            {"id": 1, "username": "Joe"},
+            {"id": 2, "username": "joey"},
+            {"id": 3, "username": "Anna"},
+        ]}));
+        db.assert_sql_contains("FROM \"user\"");
+    }
+
+    #[tokio::test]
+    async fn test_count_view_search() {
+        let db = setup().await;
+        let view = db
+            .api::<()>()
+            .count::<Entity>("/api/user/count")
+            .unwrap()
+            .with_filter(Search::new([Column::Username]));
+        let client = TestView::new(view, ()).unwrap();
+
+        let response = client.get("/api/user/count?search=JO").await;
+        response.assert_status_ok();
+        response.assert_json(&json!({"count": 2}));
+        db.assert_sql_contains("LOWER(\"user\".\"username\") LIKE '%jo%'");
+    }
+
+    #[derive(Clone, Debug, Serialize, QuickSerializer)]
+    #[quickapi(model = Model, read_only(id))]
+    pub struct SimpleUser {
+        pub id: i32,
+        pub username: String,
+    }
+
+    #[tokio::test]
+    async fn test_derived_serializers() {
+        let db = setup().await;
+        let api = db.api::<()>();
+
+        let create = api
+            .create::<Entity>("/api/user")
+            .unwrap()
+            .with_serializer::<CreateSimpleUser>();
+        let client = TestView::new(create, ()).unwrap();
+
+        // read only id is ignored
+        let response = client
+            .post("/api/user", json!({"id": 10, "username": "Bob"}))
+            .await;
+        response.assert_status(StatusCode::CREATED);
+        response.assert_json(&json!({"object": {"id": 4, "username": "Bob"}}));
+
+        let update = api
+            .update::<Entity>(
+                ("/api/user/{id}", axum::http::Method::PATCH),
+                quickapi_lookup::PrimaryKey::Path("id".into()),
+            )
+            .unwrap()
+            .with_serializer::<UpdateSimpleUser>();
+        let client = TestView::new(update, ()).unwrap();
+
+        // empty body changes nothing
+        client
+            .patch("/api/user/4", json!({}))
+            .await
+            .assert_status_ok();
+        assert_eq!(
+            SimpleUser::from(
+                Entity::find_by_id(4)
+                    .one(db.connection())
+                    .await
+                    .unwrap()
+                    .unwrap()
+            )
+            .username,
+            "Bob"
+        );
+
+        client
+            .patch("/api/user/4", json!({"username": "Robert"}))
+            .await
+            .assert_status_ok();
+        assert_eq!(
+            SimpleUser::from(
+                Entity::find_by_id(4)
+                    .one(db.connection())
+                    .await
+                    .unwrap()
+                    .unwrap()
+            )
+            .username,
+            "Robert"
+        );
+    }
+
+    mod profile {
+        use sea_orm::entity::prelude::*;
+        use serde::{Deserialize, Serialize};
+
+        #[derive(
+            Clone, Debug, Default, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize,
+        )]
+        #[sea_orm(table_name = "profile")]
+        pub struct Model {
+            #[sea_orm(primary_key)]
+            pub id: i32,
+            pub username: String,
+            pub bio: String,
+        }
+
+        #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+        pub enum Relation {}
+
+        impl ActiveModelBehavior for ActiveModel {}
+    }
+
+    // ProfileName covers only some columns of the model
+    #[derive(Clone, Debug, Serialize, QuickSerializer)]
+    #[quickapi(model = profile::Model, read_only(id))]
+    pub struct ProfileName {
+        pub id: i32,
+        pub username: String,
+    }
+
+    #[tokio::test]
+    async fn test_derived_partial_serializer() {
+        let db = TestDb::sqlite().await.unwrap();
+        db.create_table(profile::Entity).await.unwrap();
+
+        let create = db
+            .api::<()>()
+            .create::<profile::Entity>("/api/profile")
+            .unwrap()
+            .with_serializer::<CreateProfileName>()
+            .with_output_serializer::<ProfileName>();
+        let client = TestView::new(create, ()).unwrap();
+
+        // columns not covered by serializer are default
+        let response = client
+            .post("/api/profile", json!({"username": "Bob"}))
+            .await;
+        response.assert_status(StatusCode::CREATED);
+        response.assert_json(&json!({"object": {"id": 1, "username": "Bob"}}));
+        let created = profile::Entity::find_by_id(1)
+            .one(db.connection())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(created.bio, "");
+    }
+
+    #[tokio::test]
+    async fn test_handler() {
+        let db = setup().await;
+        let create = db
+            .api::<()>()
+            .with_idempotency(quickapi_http::idempotency::MemoryIdempotencyStore::new())
+            .create::<Entity>("/api/user")
+            .unwrap()
+            .with_max_body_size(64);
+        let client = TestView::new(create, ()).unwrap();
+
+        // body limit of the view is enforced
+        client
+            .post("/api/user", json!({"username": "x".repeat(64)}))
+            .await
+            .assert_status(StatusCode::PAYLOAD_TOO_LARGE);
+
+        // retry with the same Idempotency-Key is replayed
+        for _ in 0..2 {
+            let response = client
+                .post("/api/user", json!({"id": 0, "username": "Bob"}))
+                .add_header("Idempotency-Key", "abc")
+                .await;
+            response.assert_status(StatusCode::CREATED);
+            response.assert_json(&json!({"object": {"id": 4, "username": "Bob"}}));
+        }
+    }
+}
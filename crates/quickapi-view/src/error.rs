@@ -29,7 +29,7 @@ pub enum Error {
     ImproperlyConfigured(String),
 
     #[error("View error: {0}")]
-    FilterError(#[from] quickapi_filter::Error),
+    FilterError(quickapi_filter::Error),
 
     #[error("Database error: {0}")]
     DatabaseError(#[from] sea_orm::DbErr),
@@ -63,4 +63,33 @@ pub enum Error {
 
     #[error("{0}")]
     InternalError(#[from] Box<dyn std::error::Error>),
+
+    /// Response short-circuits the view and returns given response as is.
+    #[error("Response: {}", .0.status())]
+    Response(Box<quickapi_http::Response>),
+}
+
+/// Convert filter error into view error, forbidden requests (e.g. by scope) respond with 403,
+/// missing objects the scope depends on (e.g. parent of nested view) with 404.
+impl From<quickapi_filter::Error> for Error {
+    fn from(err: quickapi_filter::Error) -> Self {
+        match err {
+            quickapi_filter::Error::Forbidden(reason) => Error::Response(Box::new(
+                quickapi_http::Response::new(serde_json::json!({
+                    "error": "Forbidden",
+                    "message": reason,
+                }))
+                .with_status(axum::http::StatusCode::FORBIDDEN),
+            )),
+            quickapi_filter::Error::NotFound(reason) => Error::Response(Box::new(
+                quickapi_http::Response::new(serde_json::json!({
+                    "error": "Not Found",
+                    "message": reason,
+                }))
+                .with_status(axum::http::StatusCode::NOT_FOUND),
+            )),
+            quickapi_filter::Error::DatabaseError(err) => Error::DatabaseError(err),
+            err => Error::FilterError(err),
+        }
+    }
 }
@@ -37,6 +37,10 @@ pub trait RouterExt<S>: DynClone {
         router: axum::Router<S>,
         prefix: &str,
     ) -> Result<axum::Router<S>, crate::Error>;
+
+    /// apply_scopes applies row-level scopes (registered on QuickApi or Prefix) to the view.
+    /// Views pick scopes for their entity, others are ignored.
+    fn apply_scopes(&mut self, _scopes: &quickapi_filter::ScopeRegistry<S>) {}
 }
 
 impl<S> RouterExt<S> for ()
@@ -72,6 +76,14 @@ macro_rules! impl_router_tuple {
                 let router = $last.register_router_with_prefix(router, _prefix)?;
                 Ok(router)
             }
+
+            fn apply_scopes(&mut self, _scopes: &quickapi_filter::ScopeRegistry<S>) {
+                let ($($ty,)* $last, ) = self;
+                $(
+                    $ty.apply_scopes(_scopes);
+                )*
+                $last.apply_scopes(_scopes);
+            }
         }
     };
 }
@@ -51,6 +51,23 @@ where
     /// has_fallback returns true if the view has a fallback view.
     fn has_fallback(&self) -> bool;
 
+    /// max_body_size returns the maximum size of request body the view reads (0 means no limit).
+    /// Views that never read the body return None, so the body is not buffered at all.
+    fn max_body_size(&self) -> Option<usize> {
+        None
+    }
+
+    /// idempotency returns Idempotency-Key handling of the view (None when view does not honour it).
+    fn idempotency(&self) -> Option<quickapi_http::idempotency::Idempotency> {
+        None
+    }
+
+    /// has_permission checks view permissions before stored Idempotency-Key response is replayed,
+    /// so callers without permission never get it.
+    async fn has_permission(&self, _parts: &mut Parts, _state: &S) -> Result<(), Error> {
+        Ok(())
+    }
+
     /// run runs top level view logic.
     /// This is the entry point for the view and is only implemented in trait. all other trait methods must be implemented to work properly
     async fn run(
@@ -63,7 +80,12 @@ where
 
         // check if we have when views
         // list all views
-        let when_views = self.get_when_views(&mut _original_parts, &_state).await?;
+        let when_views = match self.get_when_views(&mut _original_parts, _state).await {
+            Ok(views) => views,
+            // when condition decided to respond directly
+            Err(Error::Response(response)) => return Ok(*response),
+            Err(e) => return Err(e),
+        };
 
         // when we have when views, we try to run them
         if !when_views.is_empty() {
@@ -98,10 +120,11 @@ where
 
         // now let's run the actual view logic
         match self.handle_view(_parts, &_state, _body).await {
-            Ok(response) => {
-                // if we have a response, we return it
-                Ok(response.with_header(axum::http::header::CONTENT_TYPE, "application/json"))
-            }
+            // response keeps content type set by the view (application/json by default,
+            // raw body and JSON:API documents have their own)
+            Ok(response) => Ok(response),
+            // view decided to respond directly
+            Err(Error::Response(response)) => Ok(*response),
             Err(e) => {
                 // if we have an error, we return it
                 Err(e)
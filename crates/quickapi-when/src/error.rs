@@ -1,5 +1,58 @@
+use axum::http::{StatusCode, header};
+use quickapi_http::Response;
+
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
     #[error("No match")]
     NoMatch,
+
+    /// Response short-circuits the view and returns given response immediately.
+    #[error("Response: {}", .0.status())]
+    Response(Box<Response>),
+}
+
+/// Helpers to short-circuit the view with common responses.
+impl Error {
+    /// unauthorized responds with 401 and WWW-Authenticate header set to the given challenge.
+    pub fn unauthorized(challenge: &str) -> Self {
+        Error::from(
+            Response::new(serde_json::json!({"error": "Unauthorized"}))
+                .with_status(StatusCode::UNAUTHORIZED)
+                .with_header(header::WWW_AUTHENTICATE, challenge),
+        )
+    }
+
+    /// forbidden responds with 403 and the reason in the body.
+    pub fn forbidden(reason: impl Into<String>) -> Self {
+        Error::from(
+            Response::new(serde_json::json!({"error": "Forbidden", "message": reason.into()}))
+                .with_status(StatusCode::FORBIDDEN),
+        )
+    }
+
+    /// redirect responds with 302 and Location header.
+    pub fn redirect(location: &str) -> Self {
+        Error::from(
+            Response::new(serde_json::Value::Null)
+                .with_status(StatusCode::FOUND)
+                .with_header(header::LOCATION, location),
+        )
+    }
+}
+
+/// Convert Response into Error that short-circuits the view.
+impl From<Response> for Error {
+    fn from(response: Response) -> Self {
+        Error::Response(Box::new(response))
+    }
+}
+
+/// Convert when error into view error, responses are passed through.
+impl From<Error> for quickapi_view::Error {
+    fn from(err: Error) -> Self {
+        match err {
+            Error::NoMatch => quickapi_view::Error::NoMatch,
+            Error::Response(response) => quickapi_view::Error::Response(response),
+        }
+    }
 }
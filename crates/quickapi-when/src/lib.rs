@@ -23,7 +23,9 @@
  *
  */
 pub mod error;
+pub mod predicate;
 pub mod when;
 
 pub use error::Error;
+pub use predicate::*;
 pub use when::*;
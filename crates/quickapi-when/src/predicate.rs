@@ -0,0 +1,359 @@
+/*
+ *  The MIT License (MIT)
+ *
+ *  Copyright (c) 2024-2025, Peter Vrba
+ *
+ *  Permission is hereby granted, free of charge, to any person obtaining a copy
+ *  of this software and associated documentation files (the "Software"), to deal
+ *  in the Software without restriction, including without limitation the rights
+ *  to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ *  copies of the Software, and to permit persons to whom the Software is
+ *  furnished to do so, subject to the following conditions:
+ *
+ *  The above copyright notice and this permission notice shall be included in
+ *  all copies or substantial portions of the Software.
+ *
+ *  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ *  IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ *  FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ *  AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ *  LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ *  OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+ *  THE SOFTWARE.
+ *
+ */
+
+use crate::When;
+use axum::extract::{FromRequestParts, Path, Query};
+use axum::http::request::Parts;
+use axum::http::{HeaderName, Method, header};
+use std::collections::HashMap;
+
+/// method returns a condition that matches when the request method is equal to the given one.
+pub fn method(method: Method) -> MethodIs {
+    MethodIs(method)
+}
+
+/// header_present returns a condition that matches when the request contains the given header.
+pub fn header_present(name: impl AsRef<str>) -> HeaderPresent {
+    HeaderPresent(name.as_ref().to_lowercase())
+}
+
+/// header_eq returns a condition that matches when the given header is equal to the value.
+pub fn header_eq(name: impl AsRef<str>, value: impl Into<String>) -> HeaderEq {
+    HeaderEq {
+        name: name.as_ref().to_lowercase(),
+        value: value.into(),
+    }
+}
+
+/// query_present returns a condition that matches when the query parameter is present.
+pub fn query_present(key: impl Into<String>) -> QueryPresent {
+    QueryPresent(key.into())
+}
+
+/// query_eq returns a condition that matches when the query parameter is equal to the value.
+pub fn query_eq(key: impl Into<String>, value: impl Into<String>) -> QueryEq {
+    QueryEq {
+        key: key.into(),
+        value: value.into(),
+    }
+}
+
+/// include_deleted returns a condition that matches `?include_deleted=true` (to include soft deleted rows).
+pub fn include_deleted() -> QueryEq {
+    query_eq("include_deleted", "true")
+}
+
+/// content_type returns a condition that matches the media type of the request body (parameters are ignored).
+pub fn content_type(media_type: impl AsRef<str>) -> ContentType {
+    ContentType(media_type.as_ref().to_lowercase())
+}
+
+/// accepts returns a condition that matches when the Accept header lists the media type,
+/// either explicitly or with a `type/*` range. `*/*` is ignored, so generic clients do not match.
+pub fn accepts(media_type: impl AsRef<str>) -> Accepts {
+    Accepts(media_type.as_ref().to_lowercase())
+}
+
+/// path_param_matches returns a condition that matches when the path parameter matches the regex.
+pub fn path_param_matches(name: impl Into<String>, regex: regex::Regex) -> PathParamMatches {
+    PathParamMatches {
+        name: name.into(),
+        regex,
+    }
+}
+
+/// media_type strips parameters from a media type and normalizes it.
+fn media_type(value: &str) -> String {
+    value
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_lowercase()
+}
+
+/// header_value returns the header value as string, if present and valid.
+fn header_value<'a>(parts: &'a Parts, name: &str) -> Option<&'a str> {
+    let name = HeaderName::from_bytes(name.as_bytes()).ok()?;
+    parts.headers.get(name)?.to_str().ok()
+}
+
+/// query_pairs returns all query parameters as key/value pairs.
+fn query_pairs(parts: &Parts) -> Vec<(String, String)> {
+    Query::<Vec<(String, String)>>::try_from_uri(&parts.uri)
+        .map(|q| q.0)
+        .unwrap_or_default()
+}
+
+/// matched returns Ok when the condition holds, NoMatch otherwise.
+fn matched(condition: bool) -> Result<(), crate::Error> {
+    if condition {
+        Ok(())
+    } else {
+        Err(crate::Error::NoMatch)
+    }
+}
+
+/// MethodIs matches the request method.
+#[derive(Clone, Debug)]
+pub struct MethodIs(Method);
+
+#[async_trait::async_trait]
+impl<S> When<S, ()> for MethodIs
+where
+    S: Clone + Send + Sync + 'static,
+{
+    async fn when(&self, parts: &mut Parts, _state: &S) -> Result<(), crate::Error> {
+        matched(parts.method == self.0)
+    }
+}
+
+/// HeaderPresent matches when the header is present.
+#[derive(Clone, Debug)]
+pub struct HeaderPresent(String);
+
+#[async_trait::async_trait]
+impl<S> When<S, ()> for HeaderPresent
+where
+    S: Clone + Send + Sync + 'static,
+{
+    async fn when(&self, parts: &mut Parts, _state: &S) -> Result<(), crate::Error> {
+        matched(parts.headers.contains_key(self.0.as_str()))
+    }
+}
+
+/// HeaderEq matches when the header is equal to the value.
+#[derive(Clone, Debug)]
+pub struct HeaderEq {
+    name: String,
+    value: String,
+}
+
+#[async_trait::async_trait]
+impl<S> When<S, ()> for HeaderEq
+where
+    S: Clone + Send + Sync + 'static,
+{
+    async fn when(&self, parts: &mut Parts, _state: &S) -> Result<(), crate::Error> {
+        matched(header_value(parts, &self.name) == Some(self.value.as_str()))
+    }
+}
+
+/// QueryPresent matches when the query parameter is present.
+#[derive(Clone, Debug)]
+pub struct QueryPresent(String);
+
+#[async_trait::async_trait]
+impl<S> When<S, ()> for QueryPresent
+where
+    S: Clone + Send + Sync + 'static,
+{
+    async fn when(&self, parts: &mut Parts, _state: &S) -> Result<(), crate::Error> {
+        matched(query_pairs(parts).iter().any(|(k, _)| k == &self.0))
+    }
+}
+
+/// QueryEq matches when any value of the query parameter is equal to the value.
+#[derive(Clone, Debug)]
+pub struct QueryEq {
+    key: String,
+    value: String,
+}
+
+#[async_trait::async_trait]
+impl<S> When<S, ()> for QueryEq
+where
+    S: Clone + Send + Sync + 'static,
+{
+    async fn when(&self, parts: &mut Parts, _state: &S) -> Result<(), crate::Error> {
+        matched(
+            query_pairs(parts)
+                .iter()
+                .any(|(k, v)| k == &self.key && v == &self.value),
+        )
+    }
+}
+
+/// ContentType matches the media type of the request body.
+#[derive(Clone, Debug)]
+pub struct ContentType(String);
+
+#[async_trait::async_trait]
+impl<S> When<S, ()> for ContentType
+where
+    S: Clone + Send + Sync + 'static,
+{
+    async fn when(&self, parts: &mut Parts, _state: &S) -> Result<(), crate::Error> {
+        matched(
+            header_value(parts, header::CONTENT_TYPE.as_str())
+                .map(media_type)
+                .is_some_and(|v| v == self.0),
+        )
+    }
+}
+
+/// Accepts matches media types listed in the Accept header.
+#[derive(Clone, Debug)]
+pub struct Accepts(String);
+
+#[async_trait::async_trait]
+impl<S> When<S, ()> for Accepts
+where
+    S: Clone + Send + Sync + 'static,
+{
+    async fn when(&self, parts: &mut Parts, _state: &S) -> Result<(), crate::Error> {
+        let Some(accept) = header_value(parts, header::ACCEPT.as_str()) else {
+            return Err(crate::Error::NoMatch);
+        };
+
+        let wildcard = match self.0.split_once('/') {
+            Some((kind, _)) => format!("{}/*", kind),
+            None => return Err(crate::Error::NoMatch),
+        };
+
+        matched(accept.split(',').any(|range| {
+            // ranges with q=0 are explicitly not acceptable
+            let rejected = range
+                .split(';')
+                .skip(1)
+                .filter_map(|p| p.trim().strip_prefix("q="))
+                .any(|q| q.parse::<f32>().is_ok_and(|q| q == 0.0));
+            let range = media_type(range);
+            !rejected && (range == self.0 || range == wildcard)
+        }))
+    }
+}
+
+/// PathParamMatches matches a path parameter against a regex.
+#[derive(Clone, Debug)]
+pub struct PathParamMatches {
+    name: String,
+    regex: regex::Regex,
+}
+
+#[async_trait::async_trait]
+impl<S> When<S, ()> for PathParamMatches
+where
+    S: Clone + Send + Sync + 'static,
+{
+    async fn when(&self, parts: &mut Parts, state: &S) -> Result<(), crate::Error> {
+        let params: Path<HashMap<String, String>> = Path::from_request_parts(parts, state)
+            .await
+            .map_err(|_| crate::Error::NoMatch)?;
+
+        matched(
+            params
+                .get(&self.name)
+                .is_some_and(|value| self.regex.is_match(value)),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // parts prepares request parts for given method, uri and headers
+    fn parts(method: Method, uri: &str, headers: &[(&str, &str)]) -> Parts {
+        let mut builder = axum::http::Request::builder().method(method).uri(uri);
+        for (k, v) in headers {
+            builder = builder.header(*k, *v);
+        }
+        builder.body(()).unwrap().into_parts().0
+    }
+
+    // check runs the condition against the parts
+    async fn check(when: impl When<(), ()>, mut parts: Parts) -> bool {
+        when.when(&mut parts, &()).await.is_ok()
+    }
+
+    #[tokio::test]
+    async fn test_method() {
+        assert!(check(method(Method::HEAD), parts(Method::HEAD, "/", &[])).await);
+        assert!(!check(method(Method::HEAD), parts(Method::GET, "/", &[])).await);
+    }
+
+    #[tokio::test]
+    async fn test_header() {
+        let p = || parts(Method::GET, "/", &[("X-Admin", "yes")]);
+        assert!(check(header_present("X-Admin"), p()).await);
+        assert!(check(header_present("x-admin"), p()).await);
+        assert!(!check(header_present("X-Other"), p()).await);
+        assert!(check(header_eq("X-Admin", "yes"), p()).await);
+        assert!(!check(header_eq("X-Admin", "no"), p()).await);
+    }
+
+    #[tokio::test]
+    async fn test_query() {
+        let p = || parts(Method::GET, "/?format=full&tag=a&tag=b", &[]);
+        assert!(check(query_eq("format", "full"), p()).await);
+        assert!(check(query_eq("tag", "b"), p()).await);
+        assert!(!check(query_eq("format", "short"), p()).await);
+        assert!(check(query_present("tag"), p()).await);
+        assert!(!check(query_present("page"), p()).await);
+    }
+
+    #[tokio::test]
+    async fn test_content_type() {
+        let p = parts(
+            Method::POST,
+            "/",
+            &[("Content-Type", "application/json; charset=utf-8")],
+        );
+        assert!(check(content_type("application/json"), p).await);
+        let p = parts(Method::POST, "/", &[("Content-Type", "text/csv")]);
+        assert!(!check(content_type("application/json"), p).await);
+    }
+
+    #[tokio::test]
+    async fn test_accepts() {
+        let accept = |v| parts(Method::GET, "/", &[("Accept", v)]);
+        assert!(check(accepts("text/csv"), accept("application/json, text/csv;q=0.5")).await);
+        assert!(check(accepts("text/csv"), accept("text/*")).await);
+        assert!(!check(accepts("text/csv"), accept("*/*")).await);
+        assert!(!check(accepts("text/csv"), accept("text/csv;q=0")).await);
+        assert!(!check(accepts("text/csv"), parts(Method::GET, "/", &[])).await);
+    }
+
+    #[tokio::test]
+    async fn test_path_param_matches() {
+        use axum::Router;
+        use axum::routing::get;
+        use axum_test::TestServer;
+
+        let app = Router::new().route(
+            "/users/{id}",
+            get(async move |r: axum::extract::Request| {
+                let (mut parts, _body) = r.into_parts();
+                let when = path_param_matches("id", regex::Regex::new(r"^\d+$").unwrap());
+                when.when(&mut parts, &()).await.is_ok().to_string()
+            }),
+        );
+
+        let server = TestServer::new(app).unwrap();
+        assert_eq!(server.get("/users/123").await.text(), "true");
+        assert_eq!(server.get("/users/abc").await.text(), "false");
+    }
+}
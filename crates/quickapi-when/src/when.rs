@@ -183,6 +183,13 @@ where
         });
     }
 
+    /// apply_scopes applies row-level scopes to all views in WhenViews.
+    pub fn apply_scopes(&mut self, scopes: &quickapi_filter::ScopeRegistry<S>) {
+        for when_view in &mut self.inner {
+            when_view.view.apply_scopes(scopes);
+        }
+    }
+
     /// count returns the number of views in WhenViews.
     pub fn count(&self) -> usize {
         self.inner.len()
@@ -200,26 +207,37 @@ where
         state: &'a S,
     ) -> Result<&'a dyn quickapi_view::ViewTrait<S>, crate::Error> {
         for when_view in &self.inner {
-            if when_view.is_match(parts, state).await.is_ok() {
-                return Ok(when_view.view.as_ref());
+            match when_view.is_match(parts, state).await {
+                Ok(()) => return Ok(when_view.view.as_ref()),
+                // condition responds directly, stop evaluating
+                Err(crate::Error::Response(response)) => {
+                    return Err(crate::Error::Response(response));
+                }
+                Err(_) => continue,
             }
         }
         Err(crate::Error::NoMatch)
     }
 
-    /// get_views returns list of all matching views.
+    /// get_views returns list with the first matching view (empty when nothing matches).
+    /// Conditions are evaluated in order of registration and evaluation stops at the first match,
+    /// so conditions registered after it are never called.
     pub async fn get_views<'a>(
         &'a self,
         parts: &'a mut Parts,
         state: &'a S,
     ) -> Result<Vec<&'a (dyn quickapi_view::ViewTrait<S> + Send + Sync)>, crate::Error> {
-        let mut views = Vec::new();
         for when_view in &self.inner {
-            if when_view.is_match(parts, state).await.is_ok() {
-                views.push(when_view.view.as_ref());
+            match when_view.is_match(parts, state).await {
+                Ok(()) => return Ok(vec![when_view.view.as_ref()]),
+                // condition responds directly, stop evaluating
+                Err(crate::Error::Response(response)) => {
+                    return Err(crate::Error::Response(response));
+                }
+                Err(_) => continue,
             }
         }
-        Ok(views)
+        Ok(vec![])
     }
 }
 
@@ -296,4 +314,65 @@ mod tests {
         // let mut ext = Extensions::new();
         // ext.insert()
     }
+
+    #[tokio::test]
+    async fn test_when_views_response() {
+        let mut _when_views = WhenViews::<()>::default();
+        _when_views.add_when(
+            async move || Err(crate::Error::unauthorized("Bearer")),
+            (),
+        );
+        _when_views.add_when(world, ());
+
+        let (mut parts, _) = axum::http::Request::builder()
+            .uri("/")
+            .body(())
+            .unwrap()
+            .into_parts();
+
+        match _when_views.get_views(&mut parts, &()).await {
+            Err(crate::Error::Response(response)) => {
+                assert_eq!(response.status(), axum::http::StatusCode::UNAUTHORIZED);
+                assert_eq!(
+                    response.headers().get(axum::http::header::WWW_AUTHENTICATE).unwrap(),
+                    "Bearer"
+                );
+            }
+            _ => panic!("expected response"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_when_views_first_match() {
+        use std::sync::Arc;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let called = Arc::new(AtomicUsize::new(0));
+        let counter = called.clone();
+
+        let mut _when_views = WhenViews::<()>::default();
+        _when_views.add_when(async move || Err(crate::Error::NoMatch), ());
+        _when_views.add_when(world, ());
+        _when_views.add_when(
+            move || {
+                let counter = counter.clone();
+                async move {
+                    counter.fetch_add(1, Ordering::SeqCst);
+                    Err(crate::Error::unauthorized("Bearer"))
+                }
+            },
+            (),
+        );
+
+        let (mut parts, _) = axum::http::Request::builder()
+            .uri("/")
+            .body(())
+            .unwrap()
+            .into_parts();
+
+        // condition after the first match neither responds nor is called
+        let views = _when_views.get_views(&mut parts, &()).await.unwrap();
+        assert_eq!(views.len(), 1);
+        assert_eq!(called.load(Ordering::SeqCst), 0);
+    }
 }
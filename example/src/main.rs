@@ -27,6 +27,7 @@ mod serializers;
 use axum::extract::Query;
 use quickapi::filter_common::paginator::Paginator;
 use quickapi::prelude::*;
+use quickapi::when;
 use quickapi_lookup::PrimaryKey;
 use sea_orm::ColumnTrait;
 use sea_orm::QueryFilter;
@@ -73,20 +74,6 @@ pub async fn when_condition(_x: axum::extract::OriginalUri) -> Result<(), quicka
     Ok(())
 }
 
-#[derive(Debug, Deserialize)]
-#[allow(dead_code)]
-pub struct QueryFormat {
-    format: Option<String>,
-}
-
-/// when_condition is a condition that will be checked before applying the view
-pub async fn when_condition_format(_x: Query<QueryFormat>) -> Result<(), quickapi_when::Error> {
-    match &_x.format {
-        Some(format) if format == "full" => Ok(()),
-        _ => Err(quickapi_when::Error::NoMatch),
-    }
-}
-
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // prepare tracing subscriber
@@ -124,7 +111,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .with_filter(filter_search_query_username)
         .with_serializer::<serializers::UsernameOnly>()
         .wrap_result_key("users")
-        .when(when_condition_format, |v| {
+        .when(when::query_eq("format", "full"), |v| {
             // change serializer for this condition
             Ok(v.with_serializer::<serializers::SimpleUser>())
         })?.register_router(router)?;
@@ -134,7 +121,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .detail::<entity::User>("/api/user/{id}", PrimaryKey::Path("id".into()))?
         .with_serializer::<serializers::UsernameOnly>()
         .wrap_result_key("user")
-        .when(when_condition_format, |v| {
+        .when(when::query_eq("format", "full"), |v| {
             Ok(v.with_serializer::<serializers::SimpleUser>())
         })?.register_router(router)?;
 
@@ -149,6 +136,12 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         })
         .register_router(router)?;
 
+    // Update View with partial update serializer (only fields present in body are changed)
+    let router = api
+        .update::<entity::User>(("/api/user/{id}", axum::http::Method::PATCH), PrimaryKey::Path("id".into()))?
+        .with_serializer::<serializers::UpdateSimpleUser>()
+        .register_router(router)?;
+
 
     // // add multiple prefixed views as a tuple and use single register_router call
     // let router = (
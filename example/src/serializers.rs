@@ -23,45 +23,16 @@
  *
  */
 
-#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
-pub struct CreateUser {
-    pub username: String,
-}
+use quickapi::QuickSerializer;
 
-// TODO: should this be TryFrom instead of From?
-impl From<CreateUser> for entity::UserModel {
-    fn from(create_user: CreateUser) -> Self {
-        entity::UserModel {
-            id: 0, // Assuming the ID is auto-generated by the database
-            username: create_user.username,
-        }
-    }
-}
-
-#[derive(Debug, Default, Clone, serde::Serialize, serde::Deserialize)]
+/// SimpleUser derives also CreateUser (without read only id) and UpdateSimpleUser (partial update).
+#[derive(Debug, Default, Clone, serde::Serialize, serde::Deserialize, QuickSerializer)]
+#[quickapi(model = entity::UserModel, fields(id, username), read_only(id), create = CreateUser)]
 pub struct SimpleUser {
     pub id: i32,
     pub username: String,
 }
 
-impl From<entity::UserModel> for SimpleUser {
-    fn from(user: entity::UserModel) -> Self {
-        SimpleUser {
-            id: user.id,
-            username: user.username,
-        }
-    }
-}
-
-impl From<SimpleUser> for entity::UserModel {
-    fn from(user: SimpleUser) -> Self {
-        entity::UserModel {
-            id: user.id,
-            username: user.username,
-        }
-    }
-}
-
 #[derive(Debug, Default, Clone, serde::Serialize)]
 pub struct UserIdOnly {
     pub id: i32,
@@ -47,6 +47,9 @@ pub enum Error {
     #[error("Serialization error: {0}")]
     SerializationError(#[from] serde_json::Error),
 
+    #[error("Invalid JSON:API document: {0}")]
+    InvalidDocument(String),
+
     #[error("No match when")]
     NoMatchWhen,
 }
@@ -32,9 +32,19 @@ mod serializer;
 
 pub use error::Error;
 
-pub use quickapi::new;
+pub use quickapi::{DEFAULT_MAX_BODY_SIZE, QuickApi, new};
 
 pub use quickapi_filter::Error as FilterError;
+pub use quickapi_model::Error as ModelError;
 pub use quickapi_filter::common as filter_common;
 
 pub use quickapi_view::{RouterExt, ViewTrait, ViewWrapResultTrait};
+
+/// QuickSerializer derives output, create and update serializers for sea-orm Model.
+pub use quickapi_derive::QuickSerializer;
+
+/// permission contains Permission trait and built-in permissions (AllowAny, IsAuthenticated, ReadOnly)
+pub use quickapi_permission as permission;
+
+/// when contains When trait and ready-made conditions (query_eq, header_present, accepts, ...)
+pub use quickapi_when as when;
@@ -26,9 +26,30 @@
 
 pub use quickapi_filter::Error as FilterError;
 pub use quickapi_filter::common as filter_common;
-pub use quickapi_filter::common::paginator::Paginator;
+pub use quickapi_filter::common::ordering::Ordering;
+pub use quickapi_filter::common::paginator::{Pagination, Paginator};
+pub use quickapi_filter::common::search::Search;
 
 pub use quickapi_view::{RouterExt, ViewTrait, ViewWrapResultTrait};
 
+pub use quickapi_derive::QuickSerializer;
+
+pub use quickapi_filter::annotation::{Annotation, Annotations};
+pub use quickapi_filter::scope::{ExtensionScope, Scope, ScopeRegistry, Scopes};
 pub use quickapi_filter::select::{SelectFilter, SelectFilters};
+pub use quickapi_filter::soft_delete::SoftDelete;
+pub use crate::view::action::{ActionBody, ActionResponse};
+pub use crate::view::aggregate::Aggregate;
+pub use crate::view::jsonapi::JsonApi;
+pub use crate::view::links::Link;
+pub use crate::view::registry::{Route, Routes};
+pub use quickapi_http::etag::ETag;
+pub use quickapi_http::idempotency::{
+    DatabaseIdempotencyStore, Idempotency, IdempotencyStore, MemoryIdempotencyStore,
+};
+pub use quickapi_http::response::{Envelope, Partials};
+pub use quickapi_model::{ReadOnlyMode, ReplaceModel, UpdateModelTrait};
+pub use quickapi_permission::{AllowAny, IsAuthenticated, Permission, PermissionExt, ReadOnly};
 pub use quickapi_when::when::{When, WhenViews};
+
+pub use crate::view::transaction::Transaction;
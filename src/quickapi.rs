@@ -23,16 +23,41 @@
  *
  */
 use crate::Error;
-use crate::view::delete::DeleteView;
+use crate::view::action::Action;
+use crate::view::aggregate::AggregateView;
+use crate::view::bulk::{BulkDeleteView, BulkUpdateView};
+use crate::view::count::CountView;
+use crate::view::delete::{DeleteMode, DeleteView};
+use crate::view::export::ExportView;
+use crate::view::import::ImportView;
+use crate::view::links::Link;
+use crate::view::nested::Nested;
+use crate::view::registry::Routes;
+use crate::view::restore::RestoreView;
+use crate::view::update::UpdateView;
+use crate::view::upsert::UpsertView;
 use crate::view::{detail::DetailView, list::ListView};
 use axum::http::Method;
+use quickapi_filter::{Scope, ScopeRegistry};
+use quickapi_http::idempotency::Idempotency;
 use quickapi_lookup::Lookup;
-use sea_orm::{EntityTrait, Iden, Iterable};
+use sea_orm::{EntityTrait, Iden, Iterable, Related};
+use std::any::TypeId;
+use std::collections::HashMap;
+
+/// DEFAULT_MAX_BODY_SIZE is the default maximum size of request body read by views.
+pub const DEFAULT_MAX_BODY_SIZE: usize = 1_048_576;
 
 /// Create a new instance of QuickApi with the provided database connection.
 pub fn new<S>(db: sea_orm::DatabaseConnection) -> QuickApi<S> {
     QuickApi::<S> {
         db,
+        max_body_size: DEFAULT_MAX_BODY_SIZE,
+        scopes: Default::default(),
+        soft_deletes: Default::default(),
+        transaction: false,
+        idempotency: None,
+        routes: Default::default(),
         _marker: std::marker::PhantomData,
     }
 }
@@ -43,12 +68,117 @@ pub fn new<S>(db: sea_orm::DatabaseConnection) -> QuickApi<S> {
 pub struct QuickApi<S> {
     /// db is the database connection used by the QuickAPI framework.
     pub(crate) db: sea_orm::DatabaseConnection,
+    /// max_body_size is the default maximum size of request body for views that read it (0 means no limit).
+    pub(crate) max_body_size: usize,
+    /// scopes are row-level scopes applied to all views of given entity.
+    pub(crate) scopes: ScopeRegistry<S>,
+    /// soft_deletes holds soft delete columns for entities marked as soft deletable.
+    pub(crate) soft_deletes: HashMap<TypeId, &'static str>,
+    /// transaction runs write views (create, update, delete, restore and bulk) in database transaction.
+    pub(crate) transaction: bool,
+    /// idempotency is Idempotency-Key handling for create views.
+    pub(crate) idempotency: Option<Idempotency>,
+    /// routes is the registry of views registered to router (used for reverse and introspection).
+    pub(crate) routes: Routes,
     /// _marker is a marker type to ensure that QuickApi can be used with different state types.
     pub(crate) _marker: std::marker::PhantomData<S>,
 }
 
 /// QuickApi implements methods to create views in the application.
 impl<S> QuickApi<S> {
+    /// with_max_body_size sets the default maximum size of request body (0 means no limit).
+    /// Views can override it with their own with_max_body_size.
+    pub fn with_max_body_size(mut self, max_body_size: usize) -> Self {
+        self.max_body_size = max_body_size;
+        self
+    }
+
+    /// with_scope registers a row-level scope for entity E.
+    /// All views for the entity created after this call (list, detail, delete, create) will use it.
+    pub fn with_scope<E>(mut self, scope: impl Scope<E, S> + 'static) -> Self
+    where
+        E: EntityTrait,
+        S: Clone + Send + Sync + 'static,
+    {
+        self.scopes.push::<E>(scope);
+        self
+    }
+
+    /// with_soft_delete marks entity E as soft deletable by column (boolean or timestamp).
+    /// Delete views created after this call mark rows as deleted instead of deleting them,
+    /// list and detail views hide soft deleted rows.
+    pub fn with_soft_delete<E>(mut self, column: &'static str) -> Self
+    where
+        E: EntityTrait,
+    {
+        self.soft_deletes.insert(TypeId::of::<E>(), column);
+        self
+    }
+
+    /// with_transaction sets whether write views created after this call run in one database transaction
+    /// per request (create, update, delete, restore and bulk views). Views can override it with their own with_transaction.
+    pub fn with_transaction(mut self, transaction: bool) -> Self {
+        self.transaction = transaction;
+        self
+    }
+
+    /// with_idempotency makes create views created after this call honour Idempotency-Key header,
+    /// responses are stored in given store (e.g. MemoryIdempotencyStore or DatabaseIdempotencyStore).
+    pub fn with_idempotency(mut self, idempotency: impl Into<Idempotency>) -> Self {
+        self.idempotency = Some(idempotency.into());
+        self
+    }
+
+    /// soft_delete returns soft delete column for entity E (if marked as soft deletable).
+    pub(crate) fn soft_delete<E>(&self) -> Option<&'static str>
+    where
+        E: EntityTrait,
+    {
+        self.soft_deletes.get(&TypeId::of::<E>()).copied()
+    }
+
+    /// routes returns registry of views registered to router (name, method, full path, table and kind).
+    /// Views are recorded when registered, so routes are complete after router is built.
+    pub fn routes(&self) -> &Routes {
+        &self.routes
+    }
+
+    /// reverse returns url of named route with path parameters replaced by given values,
+    /// e.g. `api.reverse("user-detail", [("id", 42)])` returns `/api/user/42`.
+    pub fn reverse<K, V>(
+        &self,
+        name: &str,
+        params: impl IntoIterator<Item = (K, V)>,
+    ) -> Result<String, Error>
+    where
+        K: Into<String>,
+        V: ToString,
+    {
+        self.routes.reverse(name, params)
+    }
+
+    /// link returns path template of named route as Link (e.g. to use in with_link of other view).
+    pub fn link(&self, name: &str) -> Result<Link, Error> {
+        self.routes
+            .get(name)
+            .map(|route| route.link())
+            .ok_or_else(|| {
+                Error::ImproperlyConfigured(format!("route `{}` is not registered", name))
+            })
+    }
+
+    /// register_routes_endpoint adds GET endpoint on given path that lists all registered routes.
+    pub fn register_routes_endpoint(&self, router: axum::Router<S>, path: &str) -> axum::Router<S>
+    where
+        S: Clone + Send + Sync + 'static,
+    {
+        let routes = self.routes.clone();
+        router.route(
+            path,
+            axum::routing::get(move || async move { axum::Json(routes.all()) }),
+        )
+    }
+
     /// create creates a new CreateView instance with a specified path and method.
     pub fn create<E>(
         &self,
@@ -65,11 +195,21 @@ impl<S> QuickApi<S> {
             + 'static,
     {
         let _path_method = path_method.into();
-        crate::view::create::CreateView::<E, S, E::Model>::new(
+        let mut view = crate::view::create::CreateView::<E, S, E::Model>::new(
             self.db.clone(),
             _path_method.path,
             _path_method.method,
-        )
+        )?
+        .with_max_body_size(self.max_body_size)
+        .with_scope(self.scopes.get::<E>())
+        .with_routes(self.routes.clone())
+        .with_transaction(self.transaction);
+
+        if let Some(idempotency) = &self.idempotency {
+            view = view.with_idempotency(idempotency.clone());
+        }
+
+        Ok(view)
     }
 
     /// delete creates a new DeleteView instance with a specified path and method.
@@ -80,6 +220,7 @@ impl<S> QuickApi<S> {
     where
         E: EntityTrait,
         S: Clone + Send + Sync + 'static,
+        <E as EntityTrait>::Model: serde::Serialize,
     {
         let path_method = path_method.into();
 
@@ -91,12 +232,153 @@ impl<S> QuickApi<S> {
             ))?
             .to_string();
 
-        Ok(DeleteView::new(
+        let mut view = DeleteView::new(
             self.db.clone(),
             path_method.path,
             path_method.method,
             primary_key,
-        ))
+        )
+        .with_scope(self.scopes.get::<E>())
+        .with_routes(self.routes.clone())
+        .with_transaction(self.transaction);
+
+        if let Some(column) = self.soft_delete::<E>() {
+            view = view
+                .with_soft_delete(column)
+                .with_mode(DeleteMode::Soft { column });
+        }
+
+        Ok(view)
+    }
+
+    /// update creates a new UpdateView instance with a specified path and method.
+    /// By default request body replaces all columns except primary key, use with_serializer to change it.
+    pub fn update<E>(
+        &self,
+        path_method: impl Into<UpdatePathMethod>,
+        lookup: impl Lookup<E, S> + 'static,
+    ) -> Result<UpdateView<E, S>, Error>
+    where
+        E: EntityTrait,
+        S: Clone + Send + Sync + 'static,
+        <E as EntityTrait>::Model:
+            serde::Serialize + for<'a> serde::Deserialize<'a> + Clone + Send + Sync + 'static,
+    {
+        let pm = path_method.into();
+
+        let mut view = UpdateView::new(self.db.clone(), pm.path, pm.method, lookup)
+            .with_max_body_size(self.max_body_size)
+            .with_scope(self.scopes.get::<E>())
+            .with_routes(self.routes.clone())
+            .with_transaction(self.transaction);
+
+        if let Some(column) = self.soft_delete::<E>() {
+            view = view.with_soft_delete(column);
+        }
+
+        Ok(view)
+    }
+
+    /// upsert creates a new UpsertView instance with a specified path and method.
+    /// Object found by lookup is updated (200), otherwise new object is created (201).
+    /// By default both create and update use request body as model, serializers can be set
+    /// with with_create_serializer and with_update_serializer.
+    pub fn upsert<E>(
+        &self,
+        path_method: impl Into<UpsertPathMethod>,
+        lookup: impl Lookup<E, S> + 'static,
+    ) -> Result<UpsertView<E, S>, Error>
+    where
+        E: EntityTrait,
+        S: Clone + Send + Sync + 'static,
+        <E as EntityTrait>::Model:
+            serde::Serialize + for<'a> serde::Deserialize<'a> + Clone + Send + Sync + 'static,
+    {
+        let pm = path_method.into();
+
+        let mut view = UpsertView::new(self.db.clone(), pm.path, pm.method, lookup)
+            .with_max_body_size(self.max_body_size)
+            .with_scope(self.scopes.get::<E>())
+            .with_routes(self.routes.clone())
+            .with_transaction(self.transaction);
+
+        if let Some(column) = self.soft_delete::<E>() {
+            view = view.with_soft_delete(column);
+        }
+
+        Ok(view)
+    }
+
+    /// bulk_update creates a new BulkUpdateView that sets fields on all rows matching the filters.
+    pub fn bulk_update<E>(
+        &self,
+        path_method: impl Into<BulkUpdatePathMethod>,
+    ) -> Result<BulkUpdateView<E, S>, Error>
+    where
+        E: EntityTrait,
+        S: Clone + Send + Sync + 'static,
+    {
+        let pm = path_method.into();
+
+        let mut view = BulkUpdateView::new(self.db.clone(), pm.path, pm.method)
+            .with_max_body_size(self.max_body_size)
+            .with_scope(self.scopes.get::<E>())
+            .with_routes(self.routes.clone())
+            .with_transaction(self.transaction);
+
+        if let Some(column) = self.soft_delete::<E>() {
+            view = view.with_soft_delete(column);
+        }
+
+        Ok(view)
+    }
+
+    /// bulk_delete creates a new BulkDeleteView that deletes objects by list of primary keys.
+    /// Soft deletable entities are soft deleted.
+    pub fn bulk_delete<E>(
+        &self,
+        path_method: impl Into<BulkDeletePathMethod>,
+    ) -> Result<BulkDeleteView<E, S>, Error>
+    where
+        E: EntityTrait,
+        S: Clone + Send + Sync + 'static,
+    {
+        let pm = path_method.into();
+
+        let mut view = BulkDeleteView::new(self.db.clone(), pm.path, pm.method)
+            .with_max_body_size(self.max_body_size)
+            .with_scope(self.scopes.get::<E>())
+            .with_routes(self.routes.clone())
+            .with_transaction(self.transaction);
+
+        if let Some(column) = self.soft_delete::<E>() {
+            view = view.with_soft_delete(column);
+        }
+
+        Ok(view)
+    }
+
+    /// restore creates a new RestoreView for soft deletable entity (see with_soft_delete).
+    pub fn restore<E>(
+        &self,
+        path_method: impl Into<RestorePathMethod>,
+        lookup: impl Lookup<E, S> + 'static,
+    ) -> Result<RestoreView<E, S>, Error>
+    where
+        E: EntityTrait,
+        S: Clone + Send + Sync + 'static,
+    {
+        let pm = path_method.into();
+        let column = self.soft_delete::<E>().ok_or(Error::ImproperlyConfigured(
+            "Entity is not marked as soft deletable".to_string(),
+        ))?;
+
+        Ok(
+            RestoreView::new(self.db.clone(), pm.path, pm.method, column, lookup)
+                .with_scope(self.scopes.get::<E>())
+                .with_routes(self.routes.clone())
+                .with_transaction(self.transaction),
+        )
     }
 
     /// detail
@@ -112,12 +394,16 @@ impl<S> QuickApi<S> {
     {
         let pm = path_method.into();
 
-        Ok(DetailView::<E, S, E::Model>::new(
-            self.db.clone(),
-            pm.path,
-            pm.method,
-            lookup,
-        ))
+        let mut view =
+            DetailView::<E, S, E::Model>::new(self.db.clone(), pm.path, pm.method, lookup)
+                .with_scope(self.scopes.get::<E>())
+                .with_routes(self.routes.clone());
+
+        if let Some(column) = self.soft_delete::<E>() {
+            view = view.with_soft_delete(column);
+        }
+
+        Ok(view)
     }
 
     /// list creates a new ListView instance with a specified path and method.
@@ -131,11 +417,156 @@ impl<S> QuickApi<S> {
         <E as EntityTrait>::Model: serde::Serialize + Clone + Send + Sync + 'static,
     {
         let pm = path_method.into();
-        Ok(ListView::<E, S, E::Model>::new(
+        let mut view = ListView::<E, S, E::Model>::new(self.db.clone(), pm.path, pm.method)
+            .with_scope(self.scopes.get::<E>())
+            .with_routes(self.routes.clone());
+
+        if let Some(column) = self.soft_delete::<E>() {
+            view = view.with_soft_delete(column);
+        }
+
+        Ok(view)
+    }
+
+    /// count creates a new CountView that returns number of entities matching the filters.
+    pub fn count<E>(
+        &self,
+        path_method: impl Into<CountPathMethod>,
+    ) -> Result<CountView<E, S>, Error>
+    where
+        E: EntityTrait,
+        S: Clone + Send + Sync + 'static,
+    {
+        let pm = path_method.into();
+        Ok(self.count_view(CountView::new(self.db.clone(), pm.path, pm.method, false)))
+    }
+
+    /// exists creates a new CountView that returns whether any entity matches the filters.
+    pub fn exists<E>(
+        &self,
+        path_method: impl Into<CountPathMethod>,
+    ) -> Result<CountView<E, S>, Error>
+    where
+        E: EntityTrait,
+        S: Clone + Send + Sync + 'static,
+    {
+        let pm = path_method.into();
+        Ok(self.count_view(CountView::new(self.db.clone(), pm.path, pm.method, true)))
+    }
+
+    /// count_view applies entity scopes and soft delete to the CountView.
+    fn count_view<E>(&self, mut view: CountView<E, S>) -> CountView<E, S>
+    where
+        E: EntityTrait,
+        S: Clone + Send + Sync + 'static,
+    {
+        view = view
+            .with_scope(self.scopes.get::<E>())
+            .with_routes(self.routes.clone());
+
+        if let Some(column) = self.soft_delete::<E>() {
+            view = view.with_soft_delete(column);
+        }
+
+        view
+    }
+
+    /// aggregate creates a new AggregateView that returns aggregates of entities matching the filters.
+    /// Allowed aggregates and group by columns are configured on the view.
+    pub fn aggregate<E>(
+        &self,
+        path_method: impl Into<AggregatePathMethod>,
+    ) -> Result<AggregateView<E, S>, Error>
+    where
+        E: EntityTrait,
+        S: Clone + Send + Sync + 'static,
+    {
+        let pm = path_method.into();
+        let mut view = AggregateView::new(self.db.clone(), pm.path, pm.method)
+            .with_scope(self.scopes.get::<E>())
+            .with_routes(self.routes.clone());
+
+        if let Some(column) = self.soft_delete::<E>() {
+            view = view.with_soft_delete(column);
+        }
+
+        Ok(view)
+    }
+
+    /// export creates a new ExportView that streams entities matching the filters as CSV.
+    pub fn export<E>(
+        &self,
+        path_method: impl Into<ExportPathMethod>,
+    ) -> Result<ExportView<E, S>, Error>
+    where
+        E: EntityTrait,
+        S: Clone + Send + Sync + 'static,
+        <E as EntityTrait>::Model: serde::Serialize + Clone + Send + Sync + 'static,
+    {
+        let pm = path_method.into();
+        let mut view = ExportView::new(self.db.clone(), pm.path, pm.method)
+            .with_scope(self.scopes.get::<E>())
+            .with_routes(self.routes.clone());
+
+        if let Some(column) = self.soft_delete::<E>() {
+            view = view.with_soft_delete(column);
+        }
+
+        Ok(view)
+    }
+
+    /// import creates a new ImportView that inserts entities from uploaded CSV or NDJSON file.
+    pub fn import<E>(
+        &self,
+        path_method: impl Into<ImportPathMethod>,
+    ) -> Result<ImportView<E, S, E::Model>, Error>
+    where
+        E: EntityTrait,
+        S: Clone + Send + Sync + 'static,
+        <E as EntityTrait>::Model: for<'a> serde::Deserialize<'a> + Clone + Send + Sync + 'static,
+    {
+        let pm = path_method.into();
+        Ok(ImportView::new(self.db.clone(), pm.path, pm.method)
+            .with_max_body_size(self.max_body_size)
+            .with_scope(self.scopes.get::<E>())
+            .with_routes(self.routes.clone())
+            .with_transaction(self.transaction))
+    }
+
+    /// action creates a new Action for custom endpoints (e.g. `POST /orders/{id}/cancel`).
+    /// Use `detail` for action on object found by lookup or `collection` for action on filtered select.
+    pub fn action<E>(&self, path_method: impl Into<ActionPathMethod>) -> Result<Action<E, S>, Error>
+    where
+        E: EntityTrait,
+        S: Clone + Send + Sync + 'static,
+    {
+        let pm = path_method.into();
+        Ok(Action::new(
             self.db.clone(),
             pm.path,
             pm.method,
-        ))
+            self.max_body_size,
+            self.scopes.get::<E>(),
+            self.soft_delete::<E>(),
+            self.transaction,
+        )
+        .with_routes(self.routes.clone()))
+    }
+
+    /// nested creates views of child entity C under parent entity P (e.g. `/users/{user_id}` and `/orders`).
+    /// Parent is looked up by primary key from the last parent path parameter (404 when not found),
+    /// child queries are scoped by foreign key of relation from C to P, created children get it set.
+    pub fn nested<P, C>(
+        &self,
+        parent_path: impl Into<String>,
+        path: impl Into<String>,
+    ) -> Result<Nested<'_, P, C, S>, Error>
+    where
+        P: EntityTrait,
+        C: EntityTrait + Related<P>,
+        S: Clone + Send + Sync + 'static,
+    {
+        Nested::new(self, parent_path, path)
     }
 
     /// viewset returns object to create viewsets in the application.
@@ -152,8 +583,8 @@ macro_rules! impl_into_path_method {
         #[derive(Clone, Debug)]
         #[allow(dead_code, missing_docs)]
         pub struct $ty {
-            path: String,
-            method: axum::http::Method,
+            pub(crate) path: String,
+            pub(crate) method: axum::http::Method,
         }
         impl From<String> for $ty {
             fn from(path: String) -> Self {
@@ -190,7 +621,17 @@ macro_rules! impl_into_path_method {
 impl_into_path_method!(DetailPathMethod, Method::GET);
 impl_into_path_method!(CreatePathMethod, Method::POST);
 impl_into_path_method!(ListPathMethod, Method::GET);
+impl_into_path_method!(CountPathMethod, Method::GET);
+impl_into_path_method!(AggregatePathMethod, Method::GET);
+impl_into_path_method!(ExportPathMethod, Method::GET);
+impl_into_path_method!(ImportPathMethod, Method::POST);
 impl_into_path_method!(DeletePathMethod, Method::DELETE);
+impl_into_path_method!(UpdatePathMethod, Method::PUT);
+impl_into_path_method!(UpsertPathMethod, Method::PUT);
+impl_into_path_method!(RestorePathMethod, Method::POST);
+impl_into_path_method!(BulkUpdatePathMethod, Method::PATCH);
+impl_into_path_method!(BulkDeletePathMethod, Method::DELETE);
+impl_into_path_method!(ActionPathMethod, Method::POST);
 
 #[cfg(test)]
 mod tests {
@@ -271,4 +712,139 @@ mod tests {
         assert_eq!(x.path, "test");
         assert_eq!(x.method, Method::GET);
     }
+
+    #[test]
+    fn test_restore_path_method() {
+        let x: RestorePathMethod = "test".into();
+        assert_eq!(x.path, "test");
+        assert_eq!(x.method, Method::POST);
+
+        let x: RestorePathMethod = ("test", Method::PUT).into();
+        assert_eq!(x.path, "test");
+        assert_eq!(x.method, Method::PUT);
+    }
+
+    #[test]
+    fn test_update_path_method() {
+        let x: UpdatePathMethod = "test".into();
+        assert_eq!(x.path, "test");
+        assert_eq!(x.method, Method::PUT);
+
+        let x: UpdatePathMethod = ("test", Method::PATCH).into();
+        assert_eq!(x.path, "test");
+        assert_eq!(x.method, Method::PATCH);
+    }
+
+    #[test]
+    fn test_upsert_path_method() {
+        let x: UpsertPathMethod = "test".into();
+        assert_eq!(x.path, "test");
+        assert_eq!(x.method, Method::PUT);
+
+        let x: UpsertPathMethod = ("test", Method::POST).into();
+        assert_eq!(x.path, "test");
+        assert_eq!(x.method, Method::POST);
+    }
+
+    #[test]
+    fn test_count_path_method() {
+        let x: CountPathMethod = "test".into();
+        assert_eq!(x.path, "test");
+        assert_eq!(x.method, Method::GET);
+
+        let x: AggregatePathMethod = "test".into();
+        assert_eq!(x.path, "test");
+        assert_eq!(x.method, Method::GET);
+    }
+
+    #[test]
+    fn test_export_import_path_method() {
+        let x: ExportPathMethod = "test".into();
+        assert_eq!(x.path, "test");
+        assert_eq!(x.method, Method::GET);
+
+        let x: ImportPathMethod = "test".into();
+        assert_eq!(x.path, "test");
+        assert_eq!(x.method, Method::POST);
+    }
+
+    #[test]
+    fn test_bulk_path_method() {
+        let x: BulkUpdatePathMethod = "test".into();
+        assert_eq!(x.path, "test");
+        assert_eq!(x.method, Method::PATCH);
+
+        let x: BulkDeletePathMethod = "test".into();
+        assert_eq!(x.path, "test");
+        assert_eq!(x.method, Method::DELETE);
+
+        let x: BulkDeletePathMethod = ("test", Method::POST).into();
+        assert_eq!(x.path, "test");
+        assert_eq!(x.method, Method::POST);
+    }
+
+    #[test]
+    fn test_action_path_method() {
+        let x: ActionPathMethod = "test".into();
+        assert_eq!(x.path, "test");
+        assert_eq!(x.method, Method::POST);
+
+        let x: ActionPathMethod = ("test", Method::PUT).into();
+        assert_eq!(x.path, "test");
+        assert_eq!(x.method, Method::PUT);
+    }
+
+    mod user {
+        use sea_orm::entity::prelude::*;
+
+        #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, serde::Serialize)]
+        #[sea_orm(table_name = "user")]
+        pub struct Model {
+            #[sea_orm(primary_key)]
+            pub id: i32,
+        }
+
+        #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+        pub enum Relation {}
+
+        impl ActiveModelBehavior for ActiveModel {}
+    }
+
+    #[tokio::test]
+    async fn test_routes() {
+        use quickapi_lookup::PrimaryKey;
+        use quickapi_view::RouterExt;
+
+        let api = new::<()>(sea_orm::DatabaseConnection::Disconnected);
+        let router = api
+            .prefix("/api")
+            .with_view(api.list::<user::Entity>("/user").unwrap())
+            .with_view(
+                api.detail::<user::Entity>("/user/{id}", PrimaryKey::Path("id".to_owned()))
+                    .unwrap()
+                    .with_name("user"),
+            )
+            .register_router(axum::Router::new())
+            .unwrap();
+        let router = api.register_routes_endpoint(router, "/routes");
+
+        assert_eq!(api.routes().all().len(), 2);
+        assert_eq!(api.reverse("user", [("id", 42)]).unwrap(), "/api/user/42");
+        assert_eq!(
+            api.reverse("user-list", Vec::<(String, i32)>::new())
+                .unwrap(),
+            "/api/user"
+        );
+        assert!(api.reverse("user", [("pk", 42)]).is_err());
+        assert_eq!(api.link("user").unwrap().template(), "/api/user/{id}");
+        assert!(api.link("user-detail").is_err());
+
+        let server = axum_test::TestServer::new(router).unwrap();
+        let response = server.get("/routes").await;
+        response.assert_status_ok();
+        response.assert_json(&serde_json::json!([
+            {"name": "user-list", "method": "GET", "path": "/api/user", "table": "user", "kind": "list"},
+            {"name": "user", "method": "GET", "path": "/api/user/{id}", "table": "user", "kind": "detail"},
+        ]));
+    }
 }
@@ -0,0 +1,382 @@
+/*
+ *  The MIT License (MIT)
+ *
+ *  Copyright (c) 2024-2025, Peter Vrba
+ *
+ *  Permission is hereby granted, free of charge, to any person obtaining a copy
+ *  of this software and associated documentation files (the "Software"), to deal
+ *  in the Software without restriction, including without limitation the rights
+ *  to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ *  copies of the Software, and to permit persons to whom the Software is
+ *  furnished to do so, subject to the following conditions:
+ *
+ *  The above copyright notice and this permission notice shall be included in
+ *  all copies or substantial portions of the Software.
+ *
+ *  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ *  IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ *  FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ *  AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ *  LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ *  OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+ *  THE SOFTWARE.
+ *
+ */
+
+use axum::extract::FromRequestParts;
+use axum::http::StatusCode;
+use axum::http::request::Parts;
+use axum::response::IntoResponse;
+use quickapi_http::response::Response;
+use quickapi_view::Error;
+use sea_orm::{EntityTrait, Select};
+use serde_json::json;
+use std::marker::PhantomData;
+use std::pin::Pin;
+
+/// ActionResponse is the result of action handler, model(s) are serialized by the view serializer,
+/// response is returned as is.
+#[derive(Debug)]
+pub enum ActionResponse<M> {
+    Model(M),
+    Response(Response),
+}
+
+/// ActionBody is the request body of action view, available to action handlers as an extractor.
+#[derive(Clone, Debug)]
+pub struct ActionBody(pub bytes::Bytes);
+
+impl ActionBody {
+    /// json deserializes the body, invalid body responds with 400.
+    pub fn json<T>(&self) -> Result<T, Error>
+    where
+        T: for<'a> serde::Deserialize<'a>,
+    {
+        serde_json::from_slice(&self.0).map_err(|e| {
+            Error::Response(Box::new(
+                Response::new(json!({
+                    "error": "Bad Request",
+                    "message": e.to_string(),
+                }))
+                .with_status(StatusCode::BAD_REQUEST),
+            ))
+        })
+    }
+}
+
+/// ActionBody extractor, rejected when used outside of action view.
+impl<S> FromRequestParts<S> for ActionBody
+where
+    S: Send + Sync,
+{
+    type Rejection = (StatusCode, &'static str);
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        parts.extensions.get::<ActionBody>().cloned().ok_or((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "action body is only available in action views",
+        ))
+    }
+}
+
+/// DetailAction is a handler of detail action, it's called with looked up model and extractors.
+#[async_trait::async_trait]
+pub trait DetailAction<E, S, T>
+where
+    E: EntityTrait + Send + Sync + 'static,
+    S: Clone + Send + Sync + 'static,
+    T: 'static,
+{
+    /// call runs the action on looked up model.
+    async fn call(
+        &self,
+        parts: &mut Parts,
+        state: &S,
+        model: E::Model,
+    ) -> Result<ActionResponse<E::Model>, Error>;
+}
+
+/// CollectionAction is a handler of collection action, it's called with filtered select and extractors.
+#[async_trait::async_trait]
+pub trait CollectionAction<E, S, T>
+where
+    E: EntityTrait + Send + Sync + 'static,
+    S: Clone + Send + Sync + 'static,
+    T: 'static,
+{
+    /// call runs the action on filtered select.
+    async fn call(
+        &self,
+        parts: &mut Parts,
+        state: &S,
+        query: Select<E>,
+    ) -> Result<ActionResponse<Vec<E::Model>>, Error>;
+}
+
+/// ActionFuture is the boxed future returned by erased action handlers.
+pub(crate) type ActionFuture<'a, M> =
+    Pin<Box<dyn Future<Output = Result<ActionResponse<M>, Error>> + Send + 'a>>;
+
+pub(crate) trait DetailActionErased<E, S>: Send + Sync
+where
+    E: EntityTrait + Send + Sync + 'static,
+    S: Clone + Send + Sync + 'static,
+{
+    fn call<'a>(
+        &'a self,
+        parts: &'a mut Parts,
+        state: &'a S,
+        model: E::Model,
+    ) -> ActionFuture<'a, E::Model>;
+}
+
+pub(crate) trait CollectionActionErased<E, S>: Send + Sync
+where
+    E: EntityTrait + Send + Sync + 'static,
+    S: Clone + Send + Sync + 'static,
+{
+    fn call<'a>(
+        &'a self,
+        parts: &'a mut Parts,
+        state: &'a S,
+        query: Select<E>,
+    ) -> ActionFuture<'a, Vec<E::Model>>;
+}
+
+/// ActionBoxed erases extractor types of action handler.
+pub(crate) struct ActionBoxed<F, E, S, T> {
+    inner: F,
+    _phantom: PhantomData<(E, S, T)>,
+}
+
+impl<F, E, S, T> ActionBoxed<F, E, S, T> {
+    /// new wraps action handler.
+    pub(crate) fn new(inner: F) -> Self {
+        Self {
+            inner,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<F, E, S, T> DetailActionErased<E, S> for ActionBoxed<F, E, S, T>
+where
+    F: DetailAction<E, S, T> + Send + Sync + 'static,
+    E: EntityTrait + Send + Sync + 'static,
+    S: Clone + Send + Sync + 'static,
+    T: Sync + Send + 'static,
+{
+    fn call<'a>(
+        &'a self,
+        parts: &'a mut Parts,
+        state: &'a S,
+        model: E::Model,
+    ) -> ActionFuture<'a, E::Model> {
+        Box::pin(self.inner.call(parts, state, model))
+    }
+}
+
+impl<F, E, S, T> CollectionActionErased<E, S> for ActionBoxed<F, E, S, T>
+where
+    F: CollectionAction<E, S, T> + Send + Sync + 'static,
+    E: EntityTrait + Send + Sync + 'static,
+    S: Clone + Send + Sync + 'static,
+    T: Sync + Send + 'static,
+{
+    fn call<'a>(
+        &'a self,
+        parts: &'a mut Parts,
+        state: &'a S,
+        query: Select<E>,
+    ) -> ActionFuture<'a, Vec<E::Model>> {
+        Box::pin(self.inner.call(parts, state, query))
+    }
+}
+
+/// rejected converts extractor rejection into view error, so it's returned to the client as is.
+async fn rejected(rejection: impl IntoResponse) -> Error {
+    let response = rejection.into_response();
+    let status = response.status();
+    let message = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .map(|body| String::from_utf8_lossy(&body).into_owned())
+        .unwrap_or_default();
+
+    Error::Response(Box::new(
+        Response::new(json!({
+            "error": status.canonical_reason().unwrap_or_default(),
+            "message": message,
+        }))
+        .with_status(status),
+    ))
+}
+
+macro_rules! impl_action_tuple {
+    ([$($ty:ident),*]) => {
+        #[async_trait::async_trait]
+        #[allow(missing_docs, non_snake_case, unused_variables)]
+        impl<F, Fut, E, S, $($ty,)*> DetailAction<E, S, ($($ty,)* )> for F
+        where
+            E: sea_orm::EntityTrait + Send + Sync + 'static,
+            S: Sync + Send + Clone + 'static,
+            F: Fn(E::Model, $($ty,)*) -> Fut + Send + Sync + 'static,
+            Fut: std::future::Future<Output = Result<ActionResponse<E::Model>, Error>> + Send + 'static,
+            $(
+                $ty: axum::extract::FromRequestParts<S> + Send + 'static,
+                <$ty as axum::extract::FromRequestParts<S>>::Rejection: Send,
+            )*
+        {
+            async fn call(
+                &self,
+                _parts: &mut Parts,
+                _state: &S,
+                model: E::Model,
+            ) -> Result<ActionResponse<E::Model>, Error> {
+                $(
+                    let $ty = match $ty::from_request_parts(_parts, _state).await {
+                        Ok(value) => value,
+                        Err(rejection) => return Err(rejected(rejection).await),
+                    };
+                )*
+
+                (self)(model, $($ty,)* ).await
+            }
+        }
+
+        #[async_trait::async_trait]
+        #[allow(missing_docs, non_snake_case, unused_variables)]
+        impl<F, Fut, E, S, $($ty,)*> CollectionAction<E, S, ($($ty,)* )> for F
+        where
+            E: sea_orm::EntityTrait + Send + Sync + 'static,
+            S: Sync + Send + Clone + 'static,
+            F: Fn(Select<E>, $($ty,)*) -> Fut + Send + Sync + 'static,
+            Fut: std::future::Future<Output = Result<ActionResponse<Vec<E::Model>>, Error>> + Send + 'static,
+            $(
+                $ty: axum::extract::FromRequestParts<S> + Send + 'static,
+                <$ty as axum::extract::FromRequestParts<S>>::Rejection: Send,
+            )*
+        {
+            async fn call(
+                &self,
+                _parts: &mut Parts,
+                _state: &S,
+                query: Select<E>,
+            ) -> Result<ActionResponse<Vec<E::Model>>, Error> {
+                $(
+                    let $ty = match $ty::from_request_parts(_parts, _state).await {
+                        Ok(value) => value,
+                        Err(rejection) => return Err(rejected(rejection).await),
+                    };
+                )*
+
+                (self)(query, $($ty,)* ).await
+            }
+        }
+    };
+}
+
+quickapi_macro::all_the_tuples_with_empty!(impl_action_tuple);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sea_orm::entity::prelude::*;
+
+    #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, serde::Serialize)]
+    #[sea_orm(table_name = "order")]
+    pub struct Model {
+        #[sea_orm(primary_key)]
+        pub id: i32,
+        pub status: String,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+
+    #[derive(serde::Deserialize)]
+    struct Cancel {
+        reason: String,
+    }
+
+    // cancel sets status from request body
+    async fn cancel(mut model: Model, body: ActionBody) -> Result<ActionResponse<Model>, Error> {
+        let input: Cancel = body.json()?;
+        model.status = format!("cancelled: {}", input.reason);
+        Ok(ActionResponse::Model(model))
+    }
+
+    fn request(body: &'static str) -> Parts {
+        let (mut parts, _) = axum::http::Request::builder()
+            .method(axum::http::Method::POST)
+            .uri("/order/1/cancel")
+            .body(axum::body::Body::empty())
+            .unwrap()
+            .into_parts();
+        parts
+            .extensions
+            .insert(ActionBody(bytes::Bytes::from_static(body.as_bytes())));
+        parts
+    }
+
+    #[tokio::test]
+    async fn test_detail_action() {
+        let handler = ActionBoxed::<_, Entity, (), _>::new(cancel);
+        let model = Model {
+            id: 1,
+            status: "new".to_owned(),
+        };
+
+        let mut parts = request(r#"{"reason": "duplicate"}"#);
+        let result = DetailActionErased::call(&handler, &mut parts, &(), model.clone()).await;
+        match result {
+            Ok(ActionResponse::Model(model)) => assert_eq!(model.status, "cancelled: duplicate"),
+            _ => panic!("expected model"),
+        }
+
+        // invalid body is rejected with 400
+        let mut parts = request("{}");
+        let result = DetailActionErased::call(&handler, &mut parts, &(), model.clone()).await;
+        match result {
+            Err(Error::Response(response)) => {
+                assert_eq!(response.status(), StatusCode::BAD_REQUEST)
+            }
+            _ => panic!("expected bad request"),
+        }
+
+        // extractor rejection is returned as response
+        let mut parts = request("{}");
+        parts.extensions.clear();
+        let result = DetailActionErased::call(&handler, &mut parts, &(), model).await;
+        match result {
+            Err(Error::Response(response)) => {
+                assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR)
+            }
+            _ => panic!("expected extractor rejection"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_collection_action() {
+        let handler = ActionBoxed::<_, Entity, (), _>::new(async move |query: Select<Entity>| {
+            assert!(
+                sea_orm::QueryTrait::build(&query, sea_orm::DbBackend::Postgres)
+                    .to_string()
+                    .contains(r#"FROM "order""#)
+            );
+            Ok(ActionResponse::Response(
+                Response::new(json!({"archived": 0})).with_status(StatusCode::ACCEPTED),
+            ))
+        });
+
+        let mut parts = request("");
+        let result = CollectionActionErased::call(&handler, &mut parts, &(), Entity::find()).await;
+        match result {
+            Ok(ActionResponse::Response(response)) => {
+                assert_eq!(response.status(), StatusCode::ACCEPTED)
+            }
+            _ => panic!("expected response"),
+        }
+    }
+}
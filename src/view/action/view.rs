@@ -0,0 +1,908 @@
+/*
+ *  The MIT License (MIT)
+ *
+ *  Copyright (c) 2024-2025, Peter Vrba
+ *
+ *  Permission is hereby granted, free of charge, to any person obtaining a copy
+ *  of this software and associated documentation files (the "Software"), to deal
+ *  in the Software without restriction, including without limitation the rights
+ *  to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ *  copies of the Software, and to permit persons to whom the Software is
+ *  furnished to do so, subject to the following conditions:
+ *
+ *  The above copyright notice and this permission notice shall be included in
+ *  all copies or substantial portions of the Software.
+ *
+ *  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ *  IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ *  FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ *  AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ *  LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ *  OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+ *  THE SOFTWARE.
+ *
+ */
+
+use crate::view::action::handler::{
+    ActionBody, ActionBoxed, ActionResponse, CollectionAction, CollectionActionErased,
+    DetailAction, DetailActionErased,
+};
+use crate::view::handler::{Handler, check_when_max_body_size};
+use crate::view::registry::{Registration, Routes};
+use axum::Router;
+use axum::http::Method;
+use axum::http::request::Parts;
+use axum::routing::on;
+use quickapi_filter::{Scope, SelectFilter, SelectFilterErased};
+use quickapi_http::ModelSerializerJson;
+use quickapi_http::idempotency::Idempotency;
+use quickapi_http::response::{Key, Response};
+use quickapi_lookup::Lookup;
+use quickapi_permission::Permission;
+use quickapi_view::{Error, RouterExt, ViewTrait, as_method_filter};
+use quickapi_when::WhenViews;
+use sea_orm::{ConnectionTrait, DatabaseConnection, EntityTrait, TransactionTrait};
+use serde_json::json;
+use std::marker::PhantomData;
+use std::sync::Arc;
+use tracing::debug;
+
+const DEFAULT_DETAIL_JSON_KEY: &str = "object";
+const DEFAULT_COLLECTION_JSON_KEY: &str = "objects";
+
+/// Action creates custom action views (e.g. `POST /orders/{id}/cancel`) for entity.
+/// Detail action runs handler on object found by lookup, collection action on filtered select.
+pub struct Action<E, S>
+where
+    E: EntityTrait,
+    S: Clone + Send + Sync + 'static,
+{
+    db: DatabaseConnection,
+    path: String,
+    method: Method,
+    registration: Registration,
+    max_body_size: usize,
+    scopes: quickapi_filter::Scopes<E, S>,
+    soft_delete: Option<&'static str>,
+    transaction: bool,
+}
+
+impl<E, S> Action<E, S>
+where
+    E: EntityTrait,
+    S: Clone + Send + Sync + 'static,
+{
+    /// new creates a new Action with defaults applied to created views.
+    pub(crate) fn new(
+        db: DatabaseConnection,
+        path: impl Into<String>,
+        method: Method,
+        max_body_size: usize,
+        scopes: quickapi_filter::Scopes<E, S>,
+        soft_delete: Option<&'static str>,
+        transaction: bool,
+    ) -> Self {
+        Self {
+            db,
+            path: path.into(),
+            method,
+            registration: Registration::new::<E>("action"),
+            max_body_size,
+            scopes,
+            soft_delete,
+            transaction,
+        }
+    }
+
+    /// with_name sets name of the route in QuickApi routes (`{table}-{kind}` by default, e.g. `user-action`).
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.registration = self.registration.with_name(name);
+        self
+    }
+
+    /// with_routes sets registry of routes the view is recorded into when registered.
+    pub(crate) fn with_routes(mut self, routes: Routes) -> Self {
+        self.registration = self.registration.with_routes(routes);
+        self
+    }
+
+    /// detail creates a DetailActionView, handler is called with object found by lookup
+    /// and returns model (serialized by the view serializer) or response.
+    pub fn detail<F, T>(
+        self,
+        lookup: impl Lookup<E, S> + 'static,
+        handler: F,
+    ) -> DetailActionView<E, S, E::Model>
+    where
+        F: DetailAction<E, S, T> + Send + Sync + 'static,
+        T: Sync + Send + 'static,
+        <E as EntityTrait>::Model: serde::Serialize + Clone + Send + Sync + 'static,
+    {
+        DetailActionView {
+            db: self.db,
+            path: self.path,
+            method: self.method,
+            registration: self.registration,
+            when: Default::default(),
+            lookup: Arc::new(lookup),
+            handler: Arc::new(ActionBoxed::new(handler)),
+            filters: quickapi_filter::SelectFilters::new(),
+            scopes: self.scopes,
+            soft_delete: self.soft_delete.map(quickapi_filter::SoftDelete::new),
+            permissions: Default::default(),
+            ser: ModelSerializerJson::new(),
+            max_body_size: self.max_body_size,
+            transaction: self.transaction,
+            idempotency: None,
+            fallback: false,
+            wrap_json_key: Some(DEFAULT_DETAIL_JSON_KEY.into()),
+            _phantom_data: PhantomData,
+        }
+    }
+
+    /// collection creates a CollectionActionView, handler is called with select restricted
+    /// by scopes and filters and returns models (serialized by the view serializer) or response.
+    pub fn collection<F, T>(self, handler: F) -> CollectionActionView<E, S, E::Model>
+    where
+        F: CollectionAction<E, S, T> + Send + Sync + 'static,
+        T: Sync + Send + 'static,
+        <E as EntityTrait>::Model: serde::Serialize + Clone + Send + Sync + 'static,
+    {
+        CollectionActionView {
+            db: self.db,
+            path: self.path,
+            method: self.method,
+            registration: self.registration,
+            when: Default::default(),
+            handler: Arc::new(ActionBoxed::new(handler)),
+            filters: quickapi_filter::SelectFilters::new(),
+            scopes: self.scopes,
+            soft_delete: self.soft_delete.map(quickapi_filter::SoftDelete::new),
+            permissions: Default::default(),
+            ser: ModelSerializerJson::new(),
+            max_body_size: self.max_body_size,
+            transaction: self.transaction,
+            idempotency: None,
+            fallback: false,
+            wrap_json_key: Some(DEFAULT_COLLECTION_JSON_KEY.into()),
+            _phantom_data: PhantomData,
+        }
+    }
+}
+
+/// DetailActionView runs custom action on single object (found by lookup).
+/// It uses the same lookup, filters, scopes and permissions as DetailView.
+pub struct DetailActionView<E, S, O>
+where
+    E: EntityTrait,
+    S: Clone + Send + Sync + 'static,
+    O: serde::Serialize + Clone + Send + Sync + 'static,
+{
+    db: DatabaseConnection,
+    path: String,
+    method: Method,
+    registration: Registration,
+    when: WhenViews<S>,
+    lookup: Arc<dyn Lookup<E, S>>,
+    handler: Arc<dyn DetailActionErased<E, S>>,
+    filters: quickapi_filter::SelectFilters<E, S>,
+    scopes: quickapi_filter::Scopes<E, S>,
+    soft_delete: Option<quickapi_filter::SoftDelete>,
+    permissions: quickapi_permission::Permissions<E, S>,
+    ser: ModelSerializerJson<O>,
+    max_body_size: usize,
+    transaction: bool,
+    idempotency: Option<Idempotency>,
+    fallback: bool,
+    wrap_json_key: Option<Key>,
+    _phantom_data: PhantomData<(E, S)>,
+}
+
+impl<E, S, O> Clone for DetailActionView<E, S, O>
+where
+    E: EntityTrait,
+    S: Clone + Send + Sync + 'static,
+    O: serde::Serialize + Clone + Send + Sync + 'static,
+{
+    fn clone(&self) -> Self {
+        Self {
+            db: self.db.clone(),
+            path: self.path.clone(),
+            method: self.method.clone(),
+            registration: self.registration.clone(),
+            when: self.when.clone(),
+            lookup: self.lookup.clone(),
+            handler: self.handler.clone(),
+            filters: self.filters.clone(),
+            scopes: self.scopes.clone(),
+            soft_delete: self.soft_delete,
+            permissions: self.permissions.clone(),
+            ser: self.ser.clone(),
+            max_body_size: self.max_body_size,
+            transaction: self.transaction,
+            idempotency: self.idempotency.clone(),
+            fallback: self.fallback,
+            wrap_json_key: self.wrap_json_key.clone(),
+            _phantom_data: PhantomData,
+        }
+    }
+}
+
+impl<E, S, O> DetailActionView<E, S, O>
+where
+    E: EntityTrait,
+    S: Clone + Send + Sync + 'static,
+    O: serde::Serialize + Clone + Send + Sync + 'static,
+{
+    /// with_lookup sets the lookup for the DetailActionView.
+    pub fn with_lookup(mut self, lookup: impl Lookup<E, S> + 'static) -> Self {
+        self.lookup = Arc::new(lookup);
+        self
+    }
+
+    /// with_filter adds a filter that is applied before the lookup.
+    pub fn with_filter<F, T>(mut self, f: F) -> Self
+    where
+        F: SelectFilter<E, S, T> + Clone + Send + Sync + 'static,
+        T: Sync + Send + 'static,
+    {
+        self.filters.push(f);
+        self
+    }
+
+    /// with_scope adds a row-level scope to the DetailActionView.
+    pub fn with_scope(mut self, scope: impl Scope<E, S> + 'static) -> Self {
+        self.scopes.push(scope);
+        self
+    }
+
+    /// with_soft_delete marks entity as soft deletable by column, soft deleted objects are not found.
+    pub fn with_soft_delete(mut self, column: &'static str) -> Self {
+        self.soft_delete = Some(quickapi_filter::SoftDelete::new(column));
+        self
+    }
+
+    /// with_deleted runs the action also on soft deleted objects.
+    pub fn with_deleted(mut self) -> Self {
+        self.soft_delete = None;
+        self
+    }
+
+    /// with_permission adds a permission to the DetailActionView.
+    /// Permission is checked before the query runs, object permission before the handler is called.
+    pub fn with_permission(mut self, permission: impl Permission<E, S> + 'static) -> Self {
+        self.permissions.push(permission);
+        self
+    }
+
+    /// with_serializer sets serializer of model returned by the handler.
+    pub fn with_serializer<Ser>(self) -> DetailActionView<E, S, Ser>
+    where
+        Ser: serde::Serialize + Clone + Send + Sync + 'static,
+    {
+        DetailActionView::<E, S, Ser> {
+            db: self.db,
+            path: self.path,
+            method: self.method,
+            registration: self.registration,
+            when: self.when,
+            lookup: self.lookup,
+            handler: self.handler,
+            filters: self.filters,
+            scopes: self.scopes,
+            soft_delete: self.soft_delete,
+            permissions: self.permissions,
+            ser: ModelSerializerJson::<Ser>::new(),
+            max_body_size: self.max_body_size,
+            transaction: self.transaction,
+            idempotency: self.idempotency,
+            fallback: self.fallback,
+            wrap_json_key: self.wrap_json_key,
+            _phantom_data: PhantomData,
+        }
+    }
+
+    /// with_max_body_size sets the maximum size of request body (0 means no limit).
+    pub fn with_max_body_size(mut self, max_body_size: usize) -> Self {
+        self.max_body_size = max_body_size;
+        self
+    }
+
+    /// with_transaction runs the whole DetailActionView (lookup and handler) in one database transaction.
+    /// Transaction is committed on success response, rolled back otherwise.
+    pub fn with_transaction(mut self, transaction: bool) -> Self {
+        self.transaction = transaction;
+        self
+    }
+
+    /// with_idempotency makes the DetailActionView honour Idempotency-Key header.
+    pub fn with_idempotency(mut self, idempotency: impl Into<Idempotency>) -> Self {
+        self.idempotency = Some(idempotency.into());
+        self
+    }
+
+    /// with_fallback sets the fallback for the DetailActionView.
+    pub fn with_fallback(mut self, fallback: bool) -> Self {
+        self.fallback = fallback;
+        self
+    }
+
+    /// when adds a condition to the DetailActionView.
+    pub fn when<F, T, Ser>(
+        mut self,
+        _when: impl quickapi_when::When<S, T> + Clone + Sync + 'static,
+        _f: F,
+    ) -> Result<Self, crate::Error>
+    where
+        Ser: serde::Serialize + From<<E as EntityTrait>::Model> + Clone + Send + Sync + 'static,
+        F: Fn(DetailActionView<E, S, O>) -> Result<DetailActionView<E, S, Ser>, crate::Error>,
+        T: Sync + Send + 'static,
+        <E as EntityTrait>::Model: Sync,
+    {
+        let mut clone = self.clone();
+        clone.when = Default::default();
+        let _result = _f(clone)?;
+        check_when_max_body_size(self.max_body_size, _result.max_body_size)?;
+        self.when.add_when(_when, _result);
+        Ok(self)
+    }
+}
+
+/// DetailActionView implementation of the request pipeline.
+impl<E, S, O> DetailActionView<E, S, O>
+where
+    E: EntityTrait,
+    S: Clone + Send + Sync + 'static,
+    O: serde::Serialize + From<<E as EntityTrait>::Model> + Clone + Send + Sync + 'static,
+    <E as EntityTrait>::Model: Sync,
+{
+    /// handle runs the view pipeline on given connection (database or transaction).
+    async fn handle<C>(
+        &self,
+        conn: &C,
+        _parts: &mut Parts,
+        _state: &S,
+        _body: &bytes::Bytes,
+    ) -> Result<Response, Error>
+    where
+        C: ConnectionTrait + TransactionTrait,
+    {
+        let mut parts = _parts.clone();
+
+        // check permissions before the query runs
+        self.permissions.has_permission(&mut parts, _state).await?;
+
+        // restrict rows by scopes before any user filters
+        let mut query = self
+            .scopes
+            .scope_select(&mut parts, _state, E::find())
+            .await?;
+
+        // soft deleted objects are not found (unless deleted are included)
+        if let Some(soft_delete) = &self.soft_delete {
+            query = soft_delete.exclude_deleted(query)?;
+        }
+
+        let query = self
+            .filters
+            .filter_select_boxed(&mut parts, _state, query)
+            .await
+            .map_err(|e| Error::InternalError(Box::new(e)))?;
+
+        // prepare lookup
+        let query = self
+            .lookup
+            .lookup(&mut parts, _state, query)
+            .await
+            .map_err(|e| Error::InternalError(Box::new(e)))?;
+
+        let Some(object) = query.one(conn).await? else {
+            return Ok(Response::new(json!({
+                "error": "Not Found",
+                "message": "The requested resource was not found."
+            }))
+            .with_status(axum::http::StatusCode::NOT_FOUND));
+        };
+
+        // check object permissions before the action runs
+        self.permissions
+            .has_object_permission(&mut parts, _state, &object)
+            .await?;
+
+        // make request body available to the handler
+        parts.extensions.insert(ActionBody(_body.clone()));
+
+        let model = match self.handler.call(&mut parts, _state, object).await? {
+            ActionResponse::Model(model) => model,
+            ActionResponse::Response(response) => return Ok(response),
+        };
+
+        let serialized = self
+            .ser
+            .serialize_json(model)
+            .map_err(|e| Error::InternalError(Box::new(e)))?;
+
+        let object = match &self.wrap_json_key {
+            Some(key) => json!({ Into::<String>::into(key.clone()): serialized }),
+            None => serialized,
+        };
+
+        Ok(Response::new(object))
+    }
+}
+
+/// Implement the ViewTrait for DetailActionView
+#[async_trait::async_trait]
+impl<E, S, O> ViewTrait<S> for DetailActionView<E, S, O>
+where
+    E: EntityTrait,
+    S: Clone + Send + Sync + 'static,
+    O: serde::Serialize + From<<E as EntityTrait>::Model> + Clone + Send + Sync + 'static,
+    <E as EntityTrait>::Model: Sync,
+{
+    async fn handle_view(
+        &self,
+        _parts: &mut Parts,
+        _state: &S,
+        _body: &bytes::Bytes,
+    ) -> Result<Response, Error> {
+        if !self.transaction {
+            return self.handle(&self.db, _parts, _state, _body).await;
+        }
+
+        // run the whole pipeline in one transaction, rolled back on any error
+        let transaction = crate::view::transaction::begin(&self.db, _parts).await?;
+        let response = match self.handle(&*transaction, _parts, _state, _body).await {
+            Ok(response) => response,
+            Err(err) => {
+                crate::view::transaction::discard(transaction, _parts);
+                return Err(err);
+            }
+        };
+        crate::view::transaction::finish(transaction, _parts, response).await
+    }
+
+    /// get_when_views returns a list of when views for the DetailActionView.
+    async fn get_when_views<'a>(
+        &'a self,
+        _parts: &'a mut Parts,
+        _state: &'a S,
+    ) -> Result<Vec<&'a (dyn ViewTrait<S> + Send + Sync)>, Error> {
+        self.when
+            .get_views(_parts, _state)
+            .await
+            .map_err(Into::into)
+    }
+
+    fn has_fallback(&self) -> bool {
+        self.fallback
+    }
+
+    /// max_body_size returns the maximum size of request body (available to handler as ActionBody).
+    fn max_body_size(&self) -> Option<usize> {
+        Some(self.max_body_size)
+    }
+
+    /// idempotency returns Idempotency-Key handling of the DetailActionView.
+    fn idempotency(&self) -> Option<Idempotency> {
+        self.idempotency.clone()
+    }
+
+    /// has_permission checks DetailActionView permissions before stored response is replayed.
+    async fn has_permission(&self, _parts: &mut Parts, _state: &S) -> Result<(), Error> {
+        Ok(self.permissions.has_permission(_parts, _state).await?)
+    }
+}
+
+/// Implement the RouterExt trait for DetailActionView
+impl<E, S, O> RouterExt<S> for DetailActionView<E, S, O>
+where
+    E: EntityTrait,
+    S: Clone + Send + Sync + 'static,
+    O: serde::Serialize + From<<E as EntityTrait>::Model> + Clone + Send + Sync + 'static,
+    <E as EntityTrait>::Model: Sync,
+{
+    fn register_router_with_prefix(
+        &self,
+        router: Router<S>,
+        prefix: &str,
+    ) -> Result<Router<S>, Error> {
+        let mf = as_method_filter(&self.method)?;
+        let path = format!("{}{}", prefix, self.path);
+
+        debug!(
+            method = self.method.to_string(),
+            path = &path,
+            "API detail action",
+        );
+
+        self.registration.register(&self.method, &path)?;
+        let handler = Handler::new(self.clone()).with_db(&self.db);
+        Ok(router.route(&path, on(mf, handler)))
+    }
+
+    /// apply_scopes adds scopes registered for the entity (also to when views)
+    fn apply_scopes(&mut self, scopes: &quickapi_filter::ScopeRegistry<S>) {
+        self.scopes.extend(&scopes.get::<E>());
+        self.when.apply_scopes(scopes);
+    }
+}
+
+/// Implementing ViewWrapResultTrait for DetailActionView to handle JSON response wrapping
+impl<E, S, O> quickapi_view::ViewWrapResultTrait<S> for DetailActionView<E, S, O>
+where
+    E: EntityTrait,
+    S: Clone + Send + Sync + 'static,
+    O: serde::Serialize + From<<E as EntityTrait>::Model> + Clone + Send + Sync + 'static,
+    <E as EntityTrait>::Model: Sync,
+{
+    /// wrap_result_key method to set a custom key for the JSON response
+    fn wrap_result_key(mut self, key: impl Into<Key>) -> Self {
+        self.wrap_json_key = Some(key.into());
+        self
+    }
+
+    /// no_wrap_result_key method to disable wrapping the JSON response in a key
+    fn no_wrap_result_key(mut self) -> Self {
+        self.wrap_json_key = None;
+        self
+    }
+
+    /// get_wrap_result_key method to retrieve the key used for wrapping the JSON response
+    fn get_wrap_result_key(&self) -> Option<Key> {
+        self.wrap_json_key.clone()
+    }
+}
+
+/// CollectionActionView runs custom action on all objects matching the filters.
+/// It uses the same filters, scopes and permissions as ListView.
+pub struct CollectionActionView<E, S, O>
+where
+    E: EntityTrait,
+    S: Clone + Send + Sync + 'static,
+    O: serde::Serialize + Clone + Send + Sync + 'static,
+{
+    db: DatabaseConnection,
+    path: String,
+    method: Method,
+    registration: Registration,
+    when: WhenViews<S>,
+    handler: Arc<dyn CollectionActionErased<E, S>>,
+    filters: quickapi_filter::SelectFilters<E, S>,
+    scopes: quickapi_filter::Scopes<E, S>,
+    soft_delete: Option<quickapi_filter::SoftDelete>,
+    permissions: quickapi_permission::Permissions<E, S>,
+    ser: ModelSerializerJson<O>,
+    max_body_size: usize,
+    transaction: bool,
+    idempotency: Option<Idempotency>,
+    fallback: bool,
+    wrap_json_key: Option<Key>,
+    _phantom_data: PhantomData<(E, S)>,
+}
+
+impl<E, S, O> Clone for CollectionActionView<E, S, O>
+where
+    E: EntityTrait,
+    S: Clone + Send + Sync + 'static,
+    O: serde::Serialize + Clone + Send + Sync + 'static,
+{
+    fn clone(&self) -> Self {
+        Self {
+            db: self.db.clone(),
+            path: self.path.clone(),
+            method: self.method.clone(),
+            registration: self.registration.clone(),
+            when: self.when.clone(),
+            handler: self.handler.clone(),
+            filters: self.filters.clone(),
+            scopes: self.scopes.clone(),
+            soft_delete: self.soft_delete,
+            permissions: self.permissions.clone(),
+            ser: self.ser.clone(),
+            max_body_size: self.max_body_size,
+            transaction: self.transaction,
+            idempotency: self.idempotency.clone(),
+            fallback: self.fallback,
+            wrap_json_key: self.wrap_json_key.clone(),
+            _phantom_data: PhantomData,
+        }
+    }
+}
+
+impl<E, S, O> CollectionActionView<E, S, O>
+where
+    E: EntityTrait,
+    S: Clone + Send + Sync + 'static,
+    O: serde::Serialize + Clone + Send + Sync + 'static,
+{
+    /// with_filter adds a filter to the CollectionActionView (same filters as in ListView).
+    pub fn with_filter<F, T>(mut self, f: F) -> Self
+    where
+        F: SelectFilter<E, S, T> + Clone + Send + Sync + 'static,
+        T: Sync + Send + 'static,
+    {
+        self.filters.push(f);
+        self
+    }
+
+    /// with_scope adds a row-level scope that is applied before filters
+    pub fn with_scope(mut self, scope: impl Scope<E, S> + 'static) -> Self {
+        self.scopes.push(scope);
+        self
+    }
+
+    /// with_soft_delete marks entity as soft deletable by column, soft deleted rows are excluded.
+    pub fn with_soft_delete(mut self, column: &'static str) -> Self {
+        self.soft_delete = Some(quickapi_filter::SoftDelete::new(column));
+        self
+    }
+
+    /// with_deleted runs the action also on soft deleted rows.
+    pub fn with_deleted(mut self) -> Self {
+        self.soft_delete = None;
+        self
+    }
+
+    /// with_permission adds a permission that is checked before the handler is called
+    pub fn with_permission(mut self, permission: impl Permission<E, S> + 'static) -> Self {
+        self.permissions.push(permission);
+        self
+    }
+
+    /// with_serializer sets serializer of models returned by the handler.
+    pub fn with_serializer<Ser>(self) -> CollectionActionView<E, S, Ser>
+    where
+        Ser: serde::Serialize + Clone + Send + Sync + 'static,
+    {
+        CollectionActionView::<E, S, Ser> {
+            db: self.db,
+            path: self.path,
+            method: self.method,
+            registration: self.registration,
+            when: self.when,
+            handler: self.handler,
+            filters: self.filters,
+            scopes: self.scopes,
+            soft_delete: self.soft_delete,
+            permissions: self.permissions,
+            ser: ModelSerializerJson::<Ser>::new(),
+            max_body_size: self.max_body_size,
+            transaction: self.transaction,
+            idempotency: self.idempotency,
+            fallback: self.fallback,
+            wrap_json_key: self.wrap_json_key,
+            _phantom_data: PhantomData,
+        }
+    }
+
+    /// with_max_body_size sets the maximum size of request body (0 means no limit).
+    pub fn with_max_body_size(mut self, max_body_size: usize) -> Self {
+        self.max_body_size = max_body_size;
+        self
+    }
+
+    /// with_transaction runs the whole CollectionActionView (filters and handler) in one database transaction.
+    /// Transaction is committed on success response, rolled back otherwise.
+    pub fn with_transaction(mut self, transaction: bool) -> Self {
+        self.transaction = transaction;
+        self
+    }
+
+    /// with_idempotency makes the CollectionActionView honour Idempotency-Key header.
+    pub fn with_idempotency(mut self, idempotency: impl Into<Idempotency>) -> Self {
+        self.idempotency = Some(idempotency.into());
+        self
+    }
+
+    /// with_fallback sets the fallback for the CollectionActionView.
+    pub fn with_fallback(mut self, fallback: bool) -> Self {
+        self.fallback = fallback;
+        self
+    }
+
+    /// when adds a condition to the CollectionActionView.
+    pub fn when<F, T, Ser>(
+        mut self,
+        _when: impl quickapi_when::When<S, T> + Clone + Sync + 'static,
+        _f: F,
+    ) -> Result<Self, crate::Error>
+    where
+        Ser: serde::Serialize + From<<E as EntityTrait>::Model> + Clone + Send + Sync + 'static,
+        F: Fn(
+            CollectionActionView<E, S, O>,
+        ) -> Result<CollectionActionView<E, S, Ser>, crate::Error>,
+        T: Sync + Send + 'static,
+        <E as EntityTrait>::Model: Sync,
+    {
+        let mut clone = self.clone();
+        clone.when = Default::default();
+        let _result = _f(clone)?;
+        check_when_max_body_size(self.max_body_size, _result.max_body_size)?;
+        self.when.add_when(_when, _result);
+        Ok(self)
+    }
+}
+
+/// CollectionActionView implementation of the request pipeline.
+impl<E, S, O> CollectionActionView<E, S, O>
+where
+    E: EntityTrait,
+    S: Clone + Send + Sync + 'static,
+    O: serde::Serialize + From<<E as EntityTrait>::Model> + Clone + Send + Sync + 'static,
+    <E as EntityTrait>::Model: Sync,
+{
+    /// handle runs the view pipeline, handler can use Transaction extractor to run in the same transaction.
+    async fn handle(
+        &self,
+        _parts: &mut Parts,
+        _state: &S,
+        _body: &bytes::Bytes,
+    ) -> Result<Response, Error> {
+        let mut parts = _parts.clone();
+
+        // check permissions before the handler is called
+        self.permissions.has_permission(&mut parts, _state).await?;
+
+        // restrict rows by scopes before any user filters
+        let mut query = self
+            .scopes
+            .scope_select(&mut parts, _state, E::find())
+            .await?;
+
+        // soft deleted rows are excluded (unless deleted are included)
+        if let Some(soft_delete) = &self.soft_delete {
+            query = soft_delete.exclude_deleted(query)?;
+        }
+
+        let query = self
+            .filters
+            .filter_select_boxed(&mut parts, _state, query)
+            .await
+            .map_err(|e| Error::InternalError(Box::new(e)))?;
+
+        // make request body available to the handler
+        parts.extensions.insert(ActionBody(_body.clone()));
+
+        let models = match self.handler.call(&mut parts, _state, query).await? {
+            ActionResponse::Model(models) => models,
+            ActionResponse::Response(response) => return Ok(response),
+        };
+
+        let serialized = models
+            .into_iter()
+            .map(|model| self.ser.serialize_json(model))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| Error::InternalError(Box::new(e)))?;
+
+        let objects = match &self.wrap_json_key {
+            Some(key) => json!({ Into::<String>::into(key.clone()): serialized }),
+            None => json!(serialized),
+        };
+
+        Ok(Response::new(objects))
+    }
+}
+
+/// Implement the ViewTrait for CollectionActionView
+#[async_trait::async_trait]
+impl<E, S, O> ViewTrait<S> for CollectionActionView<E, S, O>
+where
+    E: EntityTrait,
+    S: Clone + Send + Sync + 'static,
+    O: serde::Serialize + From<<E as EntityTrait>::Model> + Clone + Send + Sync + 'static,
+    <E as EntityTrait>::Model: Sync,
+{
+    async fn handle_view(
+        &self,
+        _parts: &mut Parts,
+        _state: &S,
+        _body: &bytes::Bytes,
+    ) -> Result<Response, Error> {
+        if !self.transaction {
+            return self.handle(_parts, _state, _body).await;
+        }
+
+        // run the whole pipeline in one transaction, rolled back on any error
+        let transaction = crate::view::transaction::begin(&self.db, _parts).await?;
+        let response = match self.handle(_parts, _state, _body).await {
+            Ok(response) => response,
+            Err(err) => {
+                crate::view::transaction::discard(transaction, _parts);
+                return Err(err);
+            }
+        };
+        crate::view::transaction::finish(transaction, _parts, response).await
+    }
+
+    /// get_when_views returns a list of when views for the CollectionActionView.
+    async fn get_when_views<'a>(
+        &'a self,
+        _parts: &'a mut Parts,
+        _state: &'a S,
+    ) -> Result<Vec<&'a (dyn ViewTrait<S> + Send + Sync)>, Error> {
+        self.when
+            .get_views(_parts, _state)
+            .await
+            .map_err(Into::into)
+    }
+
+    fn has_fallback(&self) -> bool {
+        self.fallback
+    }
+
+    /// max_body_size returns the maximum size of request body (available to handler as ActionBody).
+    fn max_body_size(&self) -> Option<usize> {
+        Some(self.max_body_size)
+    }
+
+    /// idempotency returns Idempotency-Key handling of the CollectionActionView.
+    fn idempotency(&self) -> Option<Idempotency> {
+        self.idempotency.clone()
+    }
+
+    /// has_permission checks CollectionActionView permissions before stored response is replayed.
+    async fn has_permission(&self, _parts: &mut Parts, _state: &S) -> Result<(), Error> {
+        Ok(self.permissions.has_permission(_parts, _state).await?)
+    }
+}
+
+/// Implement the RouterExt trait for CollectionActionView
+impl<E, S, O> RouterExt<S> for CollectionActionView<E, S, O>
+where
+    E: EntityTrait,
+    S: Clone + Send + Sync + 'static,
+    O: serde::Serialize + From<<E as EntityTrait>::Model> + Clone + Send + Sync + 'static,
+    <E as EntityTrait>::Model: Sync,
+{
+    fn register_router_with_prefix(
+        &self,
+        router: Router<S>,
+        prefix: &str,
+    ) -> Result<Router<S>, Error> {
+        let mf = as_method_filter(&self.method)?;
+        let path = format!("{}{}", prefix, self.path);
+
+        debug!(
+            method = self.method.to_string(),
+            path = &path,
+            "API collection action",
+        );
+
+        self.registration.register(&self.method, &path)?;
+        let handler = Handler::new(self.clone()).with_db(&self.db);
+        Ok(router.route(&path, on(mf, handler)))
+    }
+
+    /// apply_scopes adds scopes registered for the entity (also to when views)
+    fn apply_scopes(&mut self, scopes: &quickapi_filter::ScopeRegistry<S>) {
+        self.scopes.extend(&scopes.get::<E>());
+        self.when.apply_scopes(scopes);
+    }
+}
+
+/// Implementing ViewWrapResultTrait for CollectionActionView to handle JSON response wrapping
+impl<E, S, O> quickapi_view::ViewWrapResultTrait<S> for CollectionActionView<E, S, O>
+where
+    E: EntityTrait,
+    S: Clone + Send + Sync + 'static,
+    O: serde::Serialize + From<<E as EntityTrait>::Model> + Clone + Send + Sync + 'static,
+    <E as EntityTrait>::Model: Sync,
+{
+    /// wrap_result_key method to set a custom key for the JSON response
+    fn wrap_result_key(mut self, key: impl Into<Key>) -> Self {
+        self.wrap_json_key = Some(key.into());
+        self
+    }
+
+    /// no_wrap_result_key method to disable wrapping the JSON response in a key
+    fn no_wrap_result_key(mut self) -> Self {
+        self.wrap_json_key = None;
+        self
+    }
+
+    /// get_wrap_result_key method to retrieve the key used for wrapping the JSON response
+    fn get_wrap_result_key(&self) -> Option<Key> {
+        self.wrap_json_key.clone()
+    }
+}
@@ -0,0 +1,518 @@
+/*
+ *  The MIT License (MIT)
+ *
+ *  Copyright (c) 2024-2025, Peter Vrba
+ *
+ *  Permission is hereby granted, free of charge, to any person obtaining a copy
+ *  of this software and associated documentation files (the "Software"), to deal
+ *  in the Software without restriction, including without limitation the rights
+ *  to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ *  copies of the Software, and to permit persons to whom the Software is
+ *  furnished to do so, subject to the following conditions:
+ *
+ *  The above copyright notice and this permission notice shall be included in
+ *  all copies or substantial portions of the Software.
+ *
+ *  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ *  IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ *  FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ *  AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ *  LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ *  OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+ *  THE SOFTWARE.
+ *
+ */
+
+use crate::view::handler::Handler;
+use crate::view::registry::{Registration, Routes};
+use axum::Router;
+use axum::extract::Query;
+use axum::http::Method;
+use axum::http::request::Parts;
+use axum::routing::on;
+use quickapi_filter::{Scope, SelectFilter, SelectFilterErased};
+use quickapi_http::response::Response;
+use quickapi_http::response::key::Key;
+use quickapi_permission::Permission;
+use quickapi_view::{Error, RouterExt, ViewTrait};
+use sea_orm::sea_query::{Alias, Expr, Func, SimpleExpr};
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QuerySelect};
+use serde_json::json;
+use std::marker::PhantomData;
+use std::str::FromStr;
+use tracing::debug;
+
+const DEFAULT_JSON_KEY: &str = "aggregate";
+const GROUP_BY_QUERY_PARAM: &str = "group_by";
+
+/// Aggregate is aggregate function computed by AggregateView.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Aggregate {
+    Sum,
+    Avg,
+    Min,
+    Max,
+}
+
+impl Aggregate {
+    /// name returns name of the aggregate, used as query parameter and prefix of result key.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Aggregate::Sum => "sum",
+            Aggregate::Avg => "avg",
+            Aggregate::Min => "min",
+            Aggregate::Max => "max",
+        }
+    }
+
+    /// expr returns aggregate expression for the column.
+    /// Average is cast to double precision, so it's not returned as numeric.
+    fn expr(&self, column: impl ColumnTrait) -> SimpleExpr {
+        let column = Expr::col(column.as_column_ref());
+        match self {
+            Aggregate::Sum => Func::sum(column).into(),
+            Aggregate::Avg => Expr::expr(Func::avg(column)).cast_as(Alias::new("double precision")),
+            Aggregate::Min => Func::min(column).into(),
+            Aggregate::Max => Func::max(column).into(),
+        }
+    }
+}
+
+/// Selected are aggregates and group by columns selected by client.
+type Selected = (Vec<(Aggregate, &'static str)>, Vec<&'static str>);
+
+/// AggregateView is a view that returns aggregates (sum, avg, min, max) of allowed columns
+/// for entities matching the filters, optionally grouped by allowed columns.
+/// Client selects aggregates by query parameters (`?sum=amount&avg=amount`, all allowed when none given)
+/// and grouping by `?group_by=status,country`. Result is object (`{"sum_amount": 10}`), or array
+/// of objects with group columns when grouped.
+#[derive(Clone)]
+pub struct AggregateView<E, S>
+where
+    E: EntityTrait,
+    S: Clone + Send + Sync + 'static,
+{
+    db: DatabaseConnection,
+    path: String,
+    method: Method,
+    registration: Registration,
+    aggregates: Vec<(Aggregate, &'static str)>,
+    group_by: Vec<&'static str>,
+    filters: quickapi_filter::SelectFilters<E, S>,
+    scopes: quickapi_filter::Scopes<E, S>,
+    soft_delete: Option<quickapi_filter::SoftDelete>,
+    permissions: quickapi_permission::Permissions<E, S>,
+    when: quickapi_when::WhenViews<S>,
+    fallback: bool,
+    wrap_json_key: Option<Key>,
+    _phantom_data: PhantomData<(E, S)>,
+}
+
+impl<E, S> AggregateView<E, S>
+where
+    E: EntityTrait,
+    S: Clone + Send + Sync + 'static,
+{
+    /// new creates a new AggregateView instance.
+    pub(crate) fn new(db: DatabaseConnection, path: impl Into<String>, method: Method) -> Self {
+        Self {
+            db,
+            path: path.into(),
+            method,
+            registration: Registration::new::<E>("aggregate"),
+            aggregates: Vec::new(),
+            group_by: Vec::new(),
+            filters: quickapi_filter::SelectFilters::new(),
+            scopes: quickapi_filter::Scopes::new(),
+            soft_delete: None,
+            permissions: Default::default(),
+            when: Default::default(),
+            fallback: false,
+            wrap_json_key: Some(DEFAULT_JSON_KEY.into()),
+            _phantom_data: PhantomData,
+        }
+    }
+
+    /// with_name sets name of the route in QuickApi routes (`{table}-{kind}` by default, e.g. `user-aggregate`).
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.registration = self.registration.with_name(name);
+        self
+    }
+
+    /// with_routes sets registry of routes the view is recorded into when registered.
+    pub(crate) fn with_routes(mut self, routes: Routes) -> Self {
+        self.registration = self.registration.with_routes(routes);
+        self
+    }
+
+    /// with_aggregate allows aggregate of the column.
+    pub fn with_aggregate(mut self, aggregate: Aggregate, column: &'static str) -> Self {
+        self.aggregates.push((aggregate, column));
+        self
+    }
+
+    /// with_sum allows sum of the column.
+    pub fn with_sum(self, column: &'static str) -> Self {
+        self.with_aggregate(Aggregate::Sum, column)
+    }
+
+    /// with_avg allows average of the column.
+    pub fn with_avg(self, column: &'static str) -> Self {
+        self.with_aggregate(Aggregate::Avg, column)
+    }
+
+    /// with_min allows minimum of the column.
+    pub fn with_min(self, column: &'static str) -> Self {
+        self.with_aggregate(Aggregate::Min, column)
+    }
+
+    /// with_max allows maximum of the column.
+    pub fn with_max(self, column: &'static str) -> Self {
+        self.with_aggregate(Aggregate::Max, column)
+    }
+
+    /// with_group_by allows grouping by the column.
+    pub fn with_group_by(mut self, column: &'static str) -> Self {
+        self.group_by.push(column);
+        self
+    }
+
+    /// with_filter adds a filter to the AggregateView (same filters as in ListView).
+    pub fn with_filter<F, T>(mut self, f: F) -> Self
+    where
+        F: SelectFilter<E, S, T> + Clone + Send + Sync + 'static,
+        T: Sync + Send + 'static,
+    {
+        self.filters.push(f);
+        self
+    }
+
+    /// with_scope adds a row-level scope that is applied before filters
+    pub fn with_scope(mut self, scope: impl Scope<E, S> + 'static) -> Self {
+        self.scopes.push(scope);
+        self
+    }
+
+    /// with_soft_delete marks entity as soft deletable by column, soft deleted rows are not aggregated.
+    pub fn with_soft_delete(mut self, column: &'static str) -> Self {
+        self.soft_delete = Some(quickapi_filter::SoftDelete::new(column));
+        self
+    }
+
+    /// with_deleted aggregates also soft deleted rows (usually in when condition for admins).
+    pub fn with_deleted(mut self) -> Self {
+        self.soft_delete = None;
+        self
+    }
+
+    /// with_permission adds a permission that is checked before the query runs
+    pub fn with_permission(mut self, permission: impl Permission<E, S> + 'static) -> Self {
+        self.permissions.push(permission);
+        self
+    }
+
+    /// with_fallback sets a fallback AggregateView that will be used if when conditions are not met.
+    pub fn with_fallback(mut self, fallback: bool) -> Self {
+        self.fallback = fallback;
+        self
+    }
+
+    /// when adds a condition to the AggregateView.
+    pub fn when<F, T>(
+        mut self,
+        _when: impl quickapi_when::When<S, T> + Clone + Sync + 'static,
+        _f: F,
+    ) -> Result<Self, crate::Error>
+    where
+        F: Fn(AggregateView<E, S>) -> Result<AggregateView<E, S>, crate::Error>,
+        T: Sync + Send + 'static,
+    {
+        let mut clone = self.clone();
+        clone.when = Default::default();
+        let _result = _f(clone)?;
+        self.when.add_when(_when, _result);
+        Ok(self)
+    }
+
+    /// column returns entity column by name.
+    fn column(name: &str) -> Result<E::Column, Error> {
+        E::Column::from_str(name)
+            .map_err(|_| Error::ImproperlyConfigured(format!("column `{}` not found", name)))
+    }
+
+    /// selected returns aggregates and group by columns selected by query parameters,
+    /// or message for client when it selects column that is not allowed.
+    fn selected(&self, parts: &Parts) -> Result<Selected, String> {
+        let pairs = Query::<Vec<(String, String)>>::try_from_uri(&parts.uri)
+            .map(|q| q.0)
+            .unwrap_or_default();
+        let values = |key: &str| -> Vec<String> {
+            pairs
+                .iter()
+                .filter(|(k, _)| k == key)
+                .flat_map(|(_, v)| v.split(','))
+                .map(|v| v.trim().to_owned())
+                .filter(|v| !v.is_empty())
+                .collect()
+        };
+
+        let mut aggregates = Vec::new();
+        for aggregate in [
+            Aggregate::Sum,
+            Aggregate::Avg,
+            Aggregate::Min,
+            Aggregate::Max,
+        ] {
+            for column in values(aggregate.name()) {
+                let allowed = self
+                    .aggregates
+                    .iter()
+                    .find(|(a, c)| *a == aggregate && *c == column)
+                    .ok_or(format!(
+                        "{} of `{}` is not allowed",
+                        aggregate.name(),
+                        column
+                    ))?;
+                aggregates.push(*allowed);
+            }
+        }
+
+        // all allowed aggregates when client does not select any
+        if aggregates.is_empty() {
+            aggregates = self.aggregates.clone();
+        }
+
+        let mut group_by = Vec::new();
+        for column in values(GROUP_BY_QUERY_PARAM) {
+            let allowed = self
+                .group_by
+                .iter()
+                .find(|c| **c == column)
+                .ok_or(format!("group by `{}` is not allowed", column))?;
+            group_by.push(*allowed);
+        }
+
+        Ok((aggregates, group_by))
+    }
+}
+
+/// Implementing RouterExt for AggregateView to register the router
+impl<E, S> RouterExt<S> for AggregateView<E, S>
+where
+    E: EntityTrait,
+    S: Clone + Send + Sync + 'static,
+{
+    fn register_router_with_prefix(
+        &self,
+        router: Router<S>,
+        prefix: &str,
+    ) -> Result<Router<S>, Error> {
+        let mf = quickapi_view::as_method_filter(&self.method)?;
+        let path = format!("{}{}", prefix, self.path);
+
+        // check configured columns early, so typos are found at startup
+        for column in self
+            .aggregates
+            .iter()
+            .map(|(_, column)| column)
+            .chain(self.group_by.iter())
+        {
+            Self::column(column)?;
+        }
+
+        debug!(
+            method = self.method.to_string(),
+            path = &path,
+            "API aggregate",
+        );
+
+        self.registration.register(&self.method, &path)?;
+        let handler = Handler::new(self.clone()).with_db(&self.db);
+        Ok(router.route(&path, on(mf, handler)))
+    }
+
+    /// apply_scopes adds scopes registered for the entity (also to when views)
+    fn apply_scopes(&mut self, scopes: &quickapi_filter::ScopeRegistry<S>) {
+        self.scopes.extend(&scopes.get::<E>());
+        self.when.apply_scopes(scopes);
+    }
+}
+
+/// Implementing ViewTrait for AggregateView to handle view logic
+#[async_trait::async_trait]
+impl<E, S> ViewTrait<S> for AggregateView<E, S>
+where
+    E: EntityTrait,
+    S: Clone + Send + Sync + 'static,
+{
+    async fn handle_view(
+        &self,
+        _parts: &mut Parts,
+        _state: &S,
+        _body: &bytes::Bytes,
+    ) -> Result<Response, Error> {
+        // check permissions before the query runs
+        self.permissions.has_permission(_parts, _state).await?;
+
+        let (aggregates, group_by) = match self.selected(_parts) {
+            Ok(selected) => selected,
+            Err(message) => {
+                return Ok(Response::new(json!({
+                    "error": "Bad Request",
+                    "message": message,
+                }))
+                .with_status(axum::http::StatusCode::BAD_REQUEST));
+            }
+        };
+
+        // restrict rows by scopes before any user filters
+        let mut query = self.scopes.scope_select(_parts, _state, E::find()).await?;
+
+        // soft deleted rows are not aggregated (unless deleted are included)
+        if let Some(soft_delete) = &self.soft_delete {
+            query = soft_delete.exclude_deleted(query)?;
+        }
+
+        let query = self
+            .filters
+            .filter_select_boxed(_parts, _state, query)
+            .await
+            .map_err(|e| Error::InternalError(Box::new(e)))?;
+
+        // select only group by columns and aggregates
+        let mut query = query.select_only();
+        for name in &group_by {
+            let column = Self::column(name)?;
+            query = query.column(column).group_by(column);
+        }
+        for (aggregate, name) in &aggregates {
+            query = query.column_as(
+                aggregate.expr(Self::column(name)?),
+                format!("{}_{}", aggregate.name(), name),
+            );
+        }
+
+        let mut rows = query.into_json().all(&self.db).await?;
+
+        let value = match group_by.is_empty() {
+            true => rows.pop().unwrap_or_else(|| json!({})),
+            false => serde_json::Value::Array(rows),
+        };
+
+        let value = match self.wrap_json_key.clone() {
+            Some(key) => json!({ Into::<String>::into(key): value }),
+            None => value,
+        };
+
+        Ok(Response::new(value))
+    }
+
+    /// get_when_views returns a vector of views that should be executed when the AggregateView is called.
+    async fn get_when_views<'a>(
+        &'a self,
+        _parts: &'a mut Parts,
+        _state: &'a S,
+    ) -> Result<Vec<&'a (dyn ViewTrait<S> + Send + Sync)>, Error> {
+        self.when
+            .get_views(_parts, _state)
+            .await
+            .map_err(Into::into)
+    }
+
+    /// has_fallback returns true if the AggregateView has a fallback defined (if when does not matches).
+    fn has_fallback(&self) -> bool {
+        self.fallback
+    }
+}
+
+/// Implementing ViewWrapResultTrait for AggregateView to handle JSON response wrapping
+impl<E, S> quickapi_view::ViewWrapResultTrait<S> for AggregateView<E, S>
+where
+    E: EntityTrait,
+    S: Clone + Send + Sync + 'static,
+{
+    /// wrap_result_key method to set a custom key for the JSON response
+    fn wrap_result_key(mut self, key: impl Into<Key>) -> Self {
+        self.wrap_json_key = Some(key.into());
+        self
+    }
+
+    /// no_wrap_result_key method to disable wrapping the JSON response in a key
+    fn no_wrap_result_key(mut self) -> Self {
+        self.wrap_json_key = None;
+        self
+    }
+
+    /// get_wrap_result_key method to retrieve the key used for wrapping the JSON response
+    fn get_wrap_result_key(&self) -> Option<Key> {
+        self.wrap_json_key.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sea_orm::entity::prelude::*;
+    use sea_orm::{DbBackend, QueryTrait};
+
+    #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+    #[sea_orm(table_name = "order")]
+    pub struct Model {
+        #[sea_orm(primary_key)]
+        pub id: i32,
+        pub status: String,
+        pub amount: i32,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+
+    fn view() -> AggregateView<Entity, ()> {
+        AggregateView::new(DatabaseConnection::Disconnected, "/order", Method::GET)
+            .with_sum("amount")
+            .with_avg("amount")
+            .with_group_by("status")
+    }
+
+    fn request(uri: &str) -> Parts {
+        axum::http::Request::get(uri)
+            .body(())
+            .unwrap()
+            .into_parts()
+            .0
+    }
+
+    #[test]
+    fn test_selected() {
+        let (aggregates, group_by) = view().selected(&request("/order")).unwrap();
+        assert_eq!(
+            aggregates,
+            vec![(Aggregate::Sum, "amount"), (Aggregate::Avg, "amount")]
+        );
+        assert!(group_by.is_empty());
+
+        let (aggregates, group_by) = view()
+            .selected(&request("/order?sum=amount&group_by=status"))
+            .unwrap();
+        assert_eq!(aggregates, vec![(Aggregate::Sum, "amount")]);
+        assert_eq!(group_by, vec!["status"]);
+
+        assert!(view().selected(&request("/order?max=amount")).is_err());
+        assert!(view().selected(&request("/order?group_by=amount")).is_err());
+    }
+
+    #[test]
+    fn test_aggregate_expr() {
+        let sql = Entity::find()
+            .select_only()
+            .column_as(Aggregate::Avg.expr(Column::Amount), "avg_amount")
+            .build(DbBackend::Postgres)
+            .to_string();
+        assert_eq!(
+            sql,
+            r#"SELECT CAST(AVG("order"."amount") AS double precision) AS "avg_amount" FROM "order""#
+        );
+    }
+}
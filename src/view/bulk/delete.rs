@@ -0,0 +1,548 @@
+/*
+ *  The MIT License (MIT)
+ *
+ *  Copyright (c) 2024-2025, Peter Vrba
+ *
+ *  Permission is hereby granted, free of charge, to any person obtaining a copy
+ *  of this software and associated documentation files (the "Software"), to deal
+ *  in the Software without restriction, including without limitation the rights
+ *  to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ *  copies of the Software, and to permit persons to whom the Software is
+ *  furnished to do so, subject to the following conditions:
+ *
+ *  The above copyright notice and this permission notice shall be included in
+ *  all copies or substantial portions of the Software.
+ *
+ *  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ *  IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ *  FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ *  AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ *  LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ *  OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+ *  THE SOFTWARE.
+ *
+ */
+
+use crate::view::handler::{Handler, check_when_max_body_size};
+use crate::view::registry::{Registration, Routes};
+use axum::Router;
+use axum::http::Method;
+use axum::http::request::Parts;
+use axum::routing::on;
+use quickapi_filter::{Scope, SelectFilter, SelectFilterErased};
+use quickapi_http::Response;
+use quickapi_permission::Permission;
+use quickapi_view::{Error, ViewTrait, as_method_filter};
+use sea_orm::sea_query::Expr;
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, Iterable, PrimaryKeyToColumn,
+    QueryFilter, TransactionTrait,
+};
+use serde_json::json;
+use std::marker::PhantomData;
+use tracing::debug;
+
+/// BulkDeleteRequest is request body of BulkDeleteView.
+#[derive(Debug, serde::Deserialize)]
+struct BulkDeleteRequest {
+    ids: Vec<serde_json::Value>,
+}
+
+/// BulkDeleteView deletes all objects by primary key list from request body (`{"ids": [...]}`)
+/// that also match the filters.
+#[derive(Clone)]
+pub struct BulkDeleteView<E, S>
+where
+    E: EntityTrait,
+    S: Clone + Send + Sync + 'static,
+{
+    db: DatabaseConnection,
+    path: String,
+    method: Method,
+    registration: Registration,
+    when: quickapi_when::WhenViews<S>,
+    filters: quickapi_filter::SelectFilters<E, S>,
+    scopes: quickapi_filter::Scopes<E, S>,
+    soft_delete: Option<quickapi_filter::SoftDelete>,
+    permissions: quickapi_permission::Permissions<E, S>,
+    fallback: bool,
+    transaction: bool,
+    max_body_size: usize,
+    _phantom_data: PhantomData<(E, S)>,
+}
+
+impl<E, S> BulkDeleteView<E, S>
+where
+    E: EntityTrait,
+    S: Clone + Send + Sync + 'static,
+{
+    /// new creates a new BulkDeleteView instance.
+    pub(crate) fn new(db: DatabaseConnection, path: impl Into<String>, method: Method) -> Self {
+        Self {
+            db,
+            path: path.into(),
+            method,
+            registration: Registration::new::<E>("bulk_delete"),
+            when: Default::default(),
+            filters: quickapi_filter::SelectFilters::new(),
+            scopes: quickapi_filter::Scopes::new(),
+            soft_delete: None,
+            permissions: Default::default(),
+            fallback: false,
+            transaction: false,
+            max_body_size: crate::quickapi::DEFAULT_MAX_BODY_SIZE,
+            _phantom_data: PhantomData,
+        }
+    }
+
+    /// with_name sets name of the route in QuickApi routes (`{table}-{kind}` by default, e.g. `user-bulk_delete`).
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.registration = self.registration.with_name(name);
+        self
+    }
+
+    /// with_routes sets registry of routes the view is recorded into when registered.
+    pub(crate) fn with_routes(mut self, routes: Routes) -> Self {
+        self.registration = self.registration.with_routes(routes);
+        self
+    }
+
+    /// with_filter adds a filter that restricts rows that can be deleted (same filters as in ListView).
+    pub fn with_filter<F, T>(mut self, f: F) -> Self
+    where
+        F: SelectFilter<E, S, T> + Clone + Send + Sync + 'static,
+        T: Sync + Send + 'static,
+    {
+        self.filters.push(f);
+        self
+    }
+
+    /// with_scope adds a row-level scope, rows out of scope are never deleted.
+    pub fn with_scope(mut self, scope: impl Scope<E, S> + 'static) -> Self {
+        self.scopes.push(scope);
+        self
+    }
+
+    /// with_soft_delete marks rows as deleted by column instead of deleting them.
+    pub fn with_soft_delete(mut self, column: &'static str) -> Self {
+        self.soft_delete = Some(quickapi_filter::SoftDelete::new(column));
+        self
+    }
+
+    /// with_permission adds a permission to the BulkDeleteView.
+    /// Object permission is checked for every object before anything is deleted.
+    pub fn with_permission(mut self, permission: impl Permission<E, S> + 'static) -> Self {
+        self.permissions.push(permission);
+        self
+    }
+
+    /// with_max_body_size sets the maximum size of request body (0 means no limit).
+    pub fn with_max_body_size(mut self, max_body_size: usize) -> Self {
+        self.max_body_size = max_body_size;
+        self
+    }
+
+    /// with_transaction runs the whole BulkDeleteView (callbacks and queries) in one database transaction.
+    /// Transaction is committed on success response, rolled back otherwise.
+    pub fn with_transaction(mut self, transaction: bool) -> Self {
+        self.transaction = transaction;
+        self
+    }
+
+    /// with_fallback sets the fallback for the BulkDeleteView.
+    pub fn with_fallback(mut self, fallback: bool) -> Self {
+        self.fallback = fallback;
+        self
+    }
+
+    /// when adds a condition to the BulkDeleteView.
+    pub fn when<F, T>(
+        mut self,
+        _when: impl quickapi_when::When<S, T> + Clone + Sync + 'static,
+        _f: F,
+    ) -> Result<Self, crate::Error>
+    where
+        F: Fn(BulkDeleteView<E, S>) -> Result<BulkDeleteView<E, S>, crate::Error>,
+        T: Sync + Send + 'static,
+        <E as EntityTrait>::Model: Sync,
+    {
+        let mut clone = self.clone();
+        clone.when = Default::default();
+        let _result = _f(clone)?;
+        check_when_max_body_size(self.max_body_size, _result.max_body_size)?;
+        self.when.add_when(_when, _result);
+        Ok(self)
+    }
+}
+
+/// BulkDeleteView implementation of the request pipeline.
+impl<E, S> BulkDeleteView<E, S>
+where
+    E: EntityTrait,
+    S: Clone + Send + Sync + 'static,
+    <E as EntityTrait>::Model: Sync,
+{
+    /// handle runs the view pipeline on given connection (database or transaction).
+    async fn handle<C>(
+        &self,
+        conn: &C,
+        _parts: &mut Parts,
+        _state: &S,
+        _body: &bytes::Bytes,
+    ) -> Result<Response, Error>
+    where
+        C: ConnectionTrait + TransactionTrait,
+    {
+        let mut parts = _parts.clone();
+
+        // check permissions before anything else
+        self.permissions.has_permission(&mut parts, _state).await?;
+
+        // bulk delete works with single column primary key
+        let column = {
+            let mut pks = E::PrimaryKey::iter();
+            match (pks.next(), pks.next()) {
+                (Some(pk), None) => pk.into_column(),
+                _ => {
+                    return Err(Error::ImproperlyConfigured(
+                        "Bulk delete needs entity with single primary key column".to_string(),
+                    ));
+                }
+            }
+        };
+
+        // parse ids (duplicates removed) and convert them to primary key column values
+        let ids = match serde_json::from_slice::<BulkDeleteRequest>(_body)
+            .map_err(|e| e.to_string())
+            .and_then(|request| {
+                let mut seen = std::collections::HashSet::new();
+                request
+                    .ids
+                    .into_iter()
+                    .map(|id| match id {
+                        serde_json::Value::String(id) => id,
+                        other => other.to_string(),
+                    })
+                    .filter(|id| seen.insert(id.clone()))
+                    .map(|id| quickapi_model::to_simple_expr(column, id).map_err(|e| e.to_string()))
+                    .collect::<Result<Vec<_>, _>>()
+            }) {
+            Ok(ids) if !ids.is_empty() => ids,
+            Ok(_) => {
+                return Ok(Response::new(json!({
+                    "error": "Bad Request",
+                    "message": "ids must not be empty",
+                }))
+                .with_status(axum::http::StatusCode::BAD_REQUEST));
+            }
+            Err(message) => {
+                return Ok(Response::new(json!({
+                    "error": "Bad Request",
+                    "message": message,
+                }))
+                .with_status(axum::http::StatusCode::BAD_REQUEST));
+            }
+        };
+
+        // restrict rows by scopes before any user filters
+        let mut query = self
+            .scopes
+            .scope_select(&mut parts, _state, E::find())
+            .await?;
+        if let Some(soft_delete) = &self.soft_delete {
+            query = soft_delete.exclude_deleted(query)?;
+        }
+        let query = self
+            .filters
+            .filter_select_boxed(&mut parts, _state, query)
+            .await
+            .map_err(|e| Error::InternalError(Box::new(e)))?;
+
+        // find objects in batches of ids
+        let mut objects = Vec::with_capacity(ids.len());
+        for batch in ids.chunks(super::BATCH_SIZE) {
+            objects.extend(
+                query
+                    .clone()
+                    .filter(Expr::col(column.as_column_ref()).is_in(batch.iter().cloned()))
+                    .all(conn)
+                    .await?,
+            );
+        }
+        if objects.is_empty() {
+            return Ok(Response::new(json!({"deleted": 0})));
+        }
+
+        // check object permissions for all objects before anything is deleted
+        for object in &objects {
+            self.permissions
+                .has_object_permission(&mut parts, _state, object)
+                .await?;
+        }
+
+        // delete rows in batches of primary keys
+        let mut deleted = 0;
+        for batch in objects.chunks(super::BATCH_SIZE) {
+            let condition = quickapi_model::primary_keys_condition::<E>(batch);
+            deleted += match &self.soft_delete {
+                Some(soft_delete) => {
+                    E::update_many()
+                        .col_expr(
+                            soft_delete.resolve_column::<E>()?,
+                            soft_delete.deleted_value::<E>()?,
+                        )
+                        .filter(condition)
+                        .exec(conn)
+                        .await?
+                        .rows_affected
+                }
+                None => {
+                    E::delete_many()
+                        .filter(condition)
+                        .exec(conn)
+                        .await?
+                        .rows_affected
+                }
+            };
+        }
+
+        Ok(Response::new(json!({"deleted": deleted})))
+    }
+}
+
+/// Implement the ViewTrait for BulkDeleteView
+#[async_trait::async_trait]
+impl<E, S> ViewTrait<S> for BulkDeleteView<E, S>
+where
+    E: EntityTrait,
+    S: Clone + Send + Sync + 'static,
+    <E as EntityTrait>::Model: Sync,
+{
+    async fn handle_view(
+        &self,
+        _parts: &mut Parts,
+        _state: &S,
+        _body: &bytes::Bytes,
+    ) -> Result<Response, Error> {
+        if !self.transaction {
+            return self.handle(&self.db, _parts, _state, _body).await;
+        }
+
+        // run the whole pipeline in one transaction, rolled back on any error
+        let transaction = crate::view::transaction::begin(&self.db, _parts).await?;
+        let response = match self.handle(&*transaction, _parts, _state, _body).await {
+            Ok(response) => response,
+            Err(err) => {
+                crate::view::transaction::discard(transaction, _parts);
+                return Err(err);
+            }
+        };
+        crate::view::transaction::finish(transaction, _parts, response).await
+    }
+
+    /// get_when_views returns a list of when views for the BulkDeleteView.
+    async fn get_when_views<'a>(
+        &'a self,
+        _parts: &'a mut Parts,
+        _state: &'a S,
+    ) -> Result<Vec<&'a (dyn ViewTrait<S> + Send + Sync)>, Error> {
+        self.when
+            .get_views(_parts, _state)
+            .await
+            .map_err(Into::into)
+    }
+
+    fn has_fallback(&self) -> bool {
+        self.fallback
+    }
+
+    /// max_body_size returns the maximum size of request body for the BulkDeleteView.
+    fn max_body_size(&self) -> Option<usize> {
+        Some(self.max_body_size)
+    }
+}
+
+/// Implement the RouterExt trait for BulkDeleteView
+impl<E, S> quickapi_view::RouterExt<S> for BulkDeleteView<E, S>
+where
+    E: EntityTrait,
+    S: Clone + Send + Sync + 'static,
+    <E as EntityTrait>::Model: Sync,
+{
+    fn register_router_with_prefix(
+        &self,
+        router: Router<S>,
+        prefix: &str,
+    ) -> Result<Router<S>, Error> {
+        let mf = as_method_filter(&self.method)?;
+        let path = format!("{}{}", prefix, self.path);
+
+        debug!(
+            method = self.method.to_string(),
+            path = &path,
+            "API bulk delete",
+        );
+
+        self.registration.register(&self.method, &path)?;
+        let handler = Handler::new(self.clone()).with_db(&self.db);
+        Ok(router.route(&path, on(mf, handler)))
+    }
+
+    /// apply_scopes adds scopes registered for the entity (also to when views)
+    fn apply_scopes(&mut self, scopes: &quickapi_filter::ScopeRegistry<S>) {
+        self.scopes.extend(&scopes.get::<E>());
+        self.when.apply_scopes(scopes);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::StatusCode;
+    use axum::http::request::Parts;
+    use axum_test::TestServer;
+    use quickapi_view::RouterExt;
+    use sea_orm::entity::prelude::*;
+    use sea_orm::{Database, QueryOrder, Schema};
+    use serde_json::json;
+
+    #[derive(
+        Clone, Debug, PartialEq, Eq, DeriveEntityModel, serde::Deserialize, serde::Serialize,
+    )]
+    #[sea_orm(table_name = "user")]
+    pub struct Model {
+        #[sea_orm(primary_key)]
+        pub id: i32,
+        pub tenant_id: i32,
+        pub username: String,
+        pub deleted: bool,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+
+    #[derive(Clone)]
+    struct Tenant(i32);
+
+    // NoRoot denies deleting user named root
+    #[derive(Clone)]
+    struct NoRoot;
+
+    #[async_trait::async_trait]
+    impl quickapi_permission::Permission<Entity, ()> for NoRoot {
+        async fn has_permission(
+            &self,
+            _parts: &mut Parts,
+            _state: &(),
+        ) -> Result<(), quickapi_permission::Error> {
+            Ok(())
+        }
+
+        async fn has_object_permission(
+            &self,
+            _parts: &mut Parts,
+            _state: &(),
+            model: &Model,
+        ) -> Result<(), quickapi_permission::Error> {
+            match model.username.as_str() {
+                "root" => Err(quickapi_permission::Error::Denied(
+                    "root is reserved".to_owned(),
+                )),
+                _ => Ok(()),
+            }
+        }
+    }
+
+    async fn database() -> DatabaseConnection {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        let backend = db.get_database_backend();
+        db.execute(backend.build(&Schema::new(backend).create_table_from_entity(Entity)))
+            .await
+            .unwrap();
+        db.execute_unprepared(
+            r#"INSERT INTO "user" ("tenant_id", "username", "deleted") VALUES
+                   (1, 'root', false),
+                   (1, 'joe', false),
+                   (1, 'anna', true),
+                   (2, 'bob', false)"#,
+        )
+        .await
+        .unwrap();
+        db
+    }
+
+    // deleted returns soft deleted flags of all users ordered by id
+    async fn deleted(db: &DatabaseConnection) -> Vec<bool> {
+        Entity::find()
+            .order_by_asc(Column::Id)
+            .all(db)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|user| user.deleted)
+            .collect()
+    }
+
+    fn server(view: super::BulkDeleteView<Entity, ()>) -> TestServer {
+        let router = view
+            .register_router(axum::Router::new())
+            .unwrap()
+            .layer(axum::Extension(Tenant(1)));
+        TestServer::new(router).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_bulk_delete() {
+        let db = database().await;
+        let view = crate::new::<()>(db.clone())
+            .bulk_delete::<Entity>("/user")
+            .unwrap()
+            .with_soft_delete("deleted")
+            .with_scope(quickapi_filter::ExtensionScope::<Entity, Tenant>::new(
+                Column::TenantId,
+                |tenant: &Tenant| tenant.0,
+            ));
+
+        // object permission denied for one row, nothing is deleted
+        server(view.clone().with_permission(NoRoot))
+            .delete("/user")
+            .json(&json!({"ids": [1, 2]}))
+            .await
+            .assert_status(StatusCode::FORBIDDEN);
+        assert_eq!(deleted(&db).await, [false, false, true, false]);
+
+        // rows out of scope and already deleted rows are skipped, ids are queried in batches
+        let ids: Vec<i32> = (2..=1200).chain([2]).collect();
+        let response = server(view)
+            .delete("/user")
+            .json(&json!({"ids": ids}))
+            .await;
+        response.assert_status_ok();
+        response.assert_json(&json!({"deleted": 1}));
+        assert_eq!(deleted(&db).await, [false, true, true, false]);
+    }
+
+    #[tokio::test]
+    async fn test_bulk_delete_rows() {
+        let db = database().await;
+        let server = server(
+            crate::new::<()>(db.clone())
+                .bulk_delete::<Entity>("/user")
+                .unwrap(),
+        );
+
+        let response = server
+            .delete("/user")
+            .json(&json!({"ids": ["1", 4, 10]}))
+            .await;
+        response.assert_json(&json!({"deleted": 2}));
+        assert_eq!(Entity::find().count(&db).await.unwrap(), 2);
+
+        server
+            .delete("/user")
+            .json(&json!({"ids": []}))
+            .await
+            .assert_status(StatusCode::BAD_REQUEST);
+    }
+}
@@ -0,0 +1,539 @@
+/*
+ *  The MIT License (MIT)
+ *
+ *  Copyright (c) 2024-2025, Peter Vrba
+ *
+ *  Permission is hereby granted, free of charge, to any person obtaining a copy
+ *  of this software and associated documentation files (the "Software"), to deal
+ *  in the Software without restriction, including without limitation the rights
+ *  to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ *  copies of the Software, and to permit persons to whom the Software is
+ *  furnished to do so, subject to the following conditions:
+ *
+ *  The above copyright notice and this permission notice shall be included in
+ *  all copies or substantial portions of the Software.
+ *
+ *  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ *  IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ *  FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ *  AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ *  LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ *  OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+ *  THE SOFTWARE.
+ *
+ */
+
+use crate::view::handler::{Handler, check_when_max_body_size};
+use crate::view::registry::{Registration, Routes};
+use axum::Router;
+use axum::http::Method;
+use axum::http::request::Parts;
+use axum::routing::on;
+use quickapi_filter::{Scope, SelectFilter, SelectFilterErased};
+use quickapi_http::Response;
+use quickapi_model::{Fields, ReadOnlyMode};
+use quickapi_permission::Permission;
+use quickapi_view::{Error, ViewTrait, as_method_filter};
+use sea_orm::sea_query::Expr;
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, ModelTrait, QueryFilter,
+    TransactionTrait,
+};
+use serde::de::DeserializeOwned;
+use serde_json::json;
+use std::marker::PhantomData;
+use tracing::debug;
+
+/// BulkUpdateView sets fields from request body (JSON object) on every row matching the filters.
+#[derive(Clone)]
+pub struct BulkUpdateView<E, S>
+where
+    E: EntityTrait,
+    S: Clone + Send + Sync + 'static,
+{
+    db: DatabaseConnection,
+    path: String,
+    method: Method,
+    registration: Registration,
+    when: quickapi_when::WhenViews<S>,
+    filters: quickapi_filter::SelectFilters<E, S>,
+    scopes: quickapi_filter::Scopes<E, S>,
+    soft_delete: Option<quickapi_filter::SoftDelete>,
+    permissions: quickapi_permission::Permissions<E, S>,
+    fields: Fields<E>,
+    fallback: bool,
+    transaction: bool,
+    max_body_size: usize,
+    _phantom_data: PhantomData<(E, S)>,
+}
+
+impl<E, S> BulkUpdateView<E, S>
+where
+    E: EntityTrait,
+    S: Clone + Send + Sync + 'static,
+{
+    /// new creates a new BulkUpdateView instance.
+    pub(crate) fn new(db: DatabaseConnection, path: impl Into<String>, method: Method) -> Self {
+        Self {
+            db,
+            path: path.into(),
+            method,
+            registration: Registration::new::<E>("bulk_update"),
+            when: Default::default(),
+            filters: quickapi_filter::SelectFilters::new(),
+            scopes: quickapi_filter::Scopes::new(),
+            soft_delete: None,
+            permissions: Default::default(),
+            fields: Default::default(),
+            fallback: false,
+            transaction: false,
+            max_body_size: crate::quickapi::DEFAULT_MAX_BODY_SIZE,
+            _phantom_data: PhantomData,
+        }
+    }
+
+    /// with_name sets name of the route in QuickApi routes (`{table}-{kind}` by default, e.g. `user-bulk_update`).
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.registration = self.registration.with_name(name);
+        self
+    }
+
+    /// with_routes sets registry of routes the view is recorded into when registered.
+    pub(crate) fn with_routes(mut self, routes: Routes) -> Self {
+        self.registration = self.registration.with_routes(routes);
+        self
+    }
+
+    /// with_filter adds a filter that selects rows to be updated (same filters as in ListView).
+    pub fn with_filter<F, T>(mut self, f: F) -> Self
+    where
+        F: SelectFilter<E, S, T> + Clone + Send + Sync + 'static,
+        T: Sync + Send + 'static,
+    {
+        self.filters.push(f);
+        self
+    }
+
+    /// with_scope adds a row-level scope, rows out of scope are never updated.
+    pub fn with_scope(mut self, scope: impl Scope<E, S> + 'static) -> Self {
+        self.scopes.push(scope);
+        self
+    }
+
+    /// with_soft_delete marks entity as soft deletable by column, soft deleted rows are not updated.
+    pub fn with_soft_delete(mut self, column: &'static str) -> Self {
+        self.soft_delete = Some(quickapi_filter::SoftDelete::new(column));
+        self
+    }
+
+    /// with_permission adds a permission to the BulkUpdateView.
+    /// Object permission is checked for every matching object before anything is updated.
+    pub fn with_permission(mut self, permission: impl Permission<E, S> + 'static) -> Self {
+        self.permissions.push(permission);
+        self
+    }
+
+    /// with_read_only marks columns as read only, client cannot change them.
+    pub fn with_read_only(mut self, columns: impl IntoIterator<Item = E::Column>) -> Self {
+        self.fields = self.fields.with_read_only(columns);
+        self
+    }
+
+    /// with_read_only_mode sets whether read only fields sent by client are ignored (default)
+    /// or rejected with 400.
+    pub fn with_read_only_mode(mut self, mode: ReadOnlyMode) -> Self {
+        self.fields = self.fields.with_read_only_mode(mode);
+        self
+    }
+
+    /// with_max_body_size sets the maximum size of request body (0 means no limit).
+    pub fn with_max_body_size(mut self, max_body_size: usize) -> Self {
+        self.max_body_size = max_body_size;
+        self
+    }
+
+    /// with_transaction runs the whole BulkUpdateView (callbacks and queries) in one database transaction.
+    /// Transaction is committed on success response, rolled back otherwise.
+    pub fn with_transaction(mut self, transaction: bool) -> Self {
+        self.transaction = transaction;
+        self
+    }
+
+    /// with_fallback sets the fallback for the BulkUpdateView.
+    pub fn with_fallback(mut self, fallback: bool) -> Self {
+        self.fallback = fallback;
+        self
+    }
+
+    /// when adds a condition to the BulkUpdateView.
+    pub fn when<F, T>(
+        mut self,
+        _when: impl quickapi_when::When<S, T> + Clone + Sync + 'static,
+        _f: F,
+    ) -> Result<Self, crate::Error>
+    where
+        F: Fn(BulkUpdateView<E, S>) -> Result<BulkUpdateView<E, S>, crate::Error>,
+        T: Sync + Send + 'static,
+        <E as EntityTrait>::Model: serde::Serialize + DeserializeOwned + Sync,
+    {
+        let mut clone = self.clone();
+        clone.when = Default::default();
+        let _result = _f(clone)?;
+        check_when_max_body_size(self.max_body_size, _result.max_body_size)?;
+        self.when.add_when(_when, _result);
+        Ok(self)
+    }
+}
+
+/// BulkUpdateView implementation of the request pipeline.
+impl<E, S> BulkUpdateView<E, S>
+where
+    E: EntityTrait,
+    S: Clone + Send + Sync + 'static,
+    <E as EntityTrait>::Model: serde::Serialize + DeserializeOwned + Sync,
+{
+    /// handle runs the view pipeline on given connection (database or transaction).
+    async fn handle<C>(
+        &self,
+        conn: &C,
+        _parts: &mut Parts,
+        _state: &S,
+        _body: &bytes::Bytes,
+    ) -> Result<Response, Error>
+    where
+        C: ConnectionTrait + TransactionTrait,
+    {
+        let mut parts = _parts.clone();
+
+        // check permissions before anything else
+        self.permissions.has_permission(&mut parts, _state).await?;
+
+        // patch is JSON object with column names as keys
+        let patch =
+            match serde_json::from_slice::<serde_json::Map<String, serde_json::Value>>(_body)
+                .map_err(|e| e.to_string())
+                .and_then(|patch| {
+                    quickapi_model::patch_columns::<E>(&patch)
+                        .map(|columns| (patch, columns))
+                        .map_err(|e| e.to_string())
+                }) {
+                Ok(patch) => patch,
+                Err(message) => {
+                    return Ok(Response::new(json!({
+                        "error": "Bad Request",
+                        "message": message,
+                    }))
+                    .with_status(axum::http::StatusCode::BAD_REQUEST));
+                }
+            };
+        let (patch, mut columns) = patch;
+
+        // reject read only fields sent by client (when configured), otherwise they are ignored
+        if let Err(e) = self.fields.check_input(_body) {
+            return Ok(Response::new(json!({
+                "error": "Bad Request",
+                "message": e.to_string(),
+            }))
+            .with_status(axum::http::StatusCode::BAD_REQUEST));
+        }
+        columns.retain(|column| !self.fields.is_read_only(column));
+
+        // restrict rows by scopes before any user filters
+        let mut query = self
+            .scopes
+            .scope_select(&mut parts, _state, E::find())
+            .await?;
+        if let Some(soft_delete) = &self.soft_delete {
+            query = soft_delete.exclude_deleted(query)?;
+        }
+        let query = self
+            .filters
+            .filter_select_boxed(&mut parts, _state, query)
+            .await
+            .map_err(|e| Error::InternalError(Box::new(e)))?;
+
+        let objects = query.all(conn).await?;
+        let Some(first) = objects.first() else {
+            return Ok(Response::new(json!({"updated": 0})));
+        };
+        if columns.is_empty() {
+            return Ok(Response::new(json!({"updated": 0})));
+        }
+
+        // check object permissions for all objects before anything is updated
+        for object in &objects {
+            self.permissions
+                .has_object_permission(&mut parts, _state, object)
+                .await?;
+        }
+
+        // convert patch values by model deserialization, scoped columns cannot be changed
+        let patched = match quickapi_model::patch_model::<E>(first.clone(), &patch) {
+            Ok(patched) => patched,
+            Err(e) => {
+                return Ok(Response::new(json!({
+                    "error": "Bad Request",
+                    "message": e.to_string(),
+                }))
+                .with_status(axum::http::StatusCode::BAD_REQUEST));
+            }
+        };
+        let patched = self.scopes.scope_model(&mut parts, _state, patched).await?;
+
+        // update rows in batches of primary keys
+        let mut updated = 0;
+        for batch in objects.chunks(super::BATCH_SIZE) {
+            let mut update = E::update_many();
+            for column in columns.iter() {
+                update = update.col_expr(*column, column.save_as(Expr::val(patched.get(*column))));
+            }
+            updated += update
+                .filter(quickapi_model::primary_keys_condition::<E>(batch))
+                .exec(conn)
+                .await?
+                .rows_affected;
+        }
+
+        Ok(Response::new(json!({"updated": updated})))
+    }
+}
+
+/// Implement the ViewTrait for BulkUpdateView
+#[async_trait::async_trait]
+impl<E, S> ViewTrait<S> for BulkUpdateView<E, S>
+where
+    E: EntityTrait,
+    S: Clone + Send + Sync + 'static,
+    <E as EntityTrait>::Model: serde::Serialize + DeserializeOwned + Sync,
+{
+    async fn handle_view(
+        &self,
+        _parts: &mut Parts,
+        _state: &S,
+        _body: &bytes::Bytes,
+    ) -> Result<Response, Error> {
+        if !self.transaction {
+            return self.handle(&self.db, _parts, _state, _body).await;
+        }
+
+        // run the whole pipeline in one transaction, rolled back on any error
+        let transaction = crate::view::transaction::begin(&self.db, _parts).await?;
+        let response = match self.handle(&*transaction, _parts, _state, _body).await {
+            Ok(response) => response,
+            Err(err) => {
+                crate::view::transaction::discard(transaction, _parts);
+                return Err(err);
+            }
+        };
+        crate::view::transaction::finish(transaction, _parts, response).await
+    }
+
+    /// get_when_views returns a list of when views for the BulkUpdateView.
+    async fn get_when_views<'a>(
+        &'a self,
+        _parts: &'a mut Parts,
+        _state: &'a S,
+    ) -> Result<Vec<&'a (dyn ViewTrait<S> + Send + Sync)>, Error> {
+        self.when
+            .get_views(_parts, _state)
+            .await
+            .map_err(Into::into)
+    }
+
+    fn has_fallback(&self) -> bool {
+        self.fallback
+    }
+
+    /// max_body_size returns the maximum size of request body for the BulkUpdateView.
+    fn max_body_size(&self) -> Option<usize> {
+        Some(self.max_body_size)
+    }
+}
+
+/// Implement the RouterExt trait for BulkUpdateView
+impl<E, S> quickapi_view::RouterExt<S> for BulkUpdateView<E, S>
+where
+    E: EntityTrait,
+    S: Clone + Send + Sync + 'static,
+    <E as EntityTrait>::Model: serde::Serialize + DeserializeOwned + Sync,
+{
+    fn register_router_with_prefix(
+        &self,
+        router: Router<S>,
+        prefix: &str,
+    ) -> Result<Router<S>, Error> {
+        let mf = as_method_filter(&self.method)?;
+        let path = format!("{}{}", prefix, self.path);
+
+        debug!(
+            method = self.method.to_string(),
+            path = &path,
+            "API bulk update",
+        );
+
+        self.registration.register(&self.method, &path)?;
+        let handler = Handler::new(self.clone()).with_db(&self.db);
+        Ok(router.route(&path, on(mf, handler)))
+    }
+
+    /// apply_scopes adds scopes registered for the entity (also to when views)
+    fn apply_scopes(&mut self, scopes: &quickapi_filter::ScopeRegistry<S>) {
+        self.scopes.extend(&scopes.get::<E>());
+        self.when.apply_scopes(scopes);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::StatusCode;
+    use axum::http::request::Parts;
+    use axum_test::TestServer;
+    use quickapi_model::ReadOnlyMode;
+    use quickapi_view::RouterExt;
+    use sea_orm::entity::prelude::*;
+    use sea_orm::{Database, QueryOrder, Schema};
+    use serde_json::json;
+
+    #[derive(
+        Clone, Debug, PartialEq, Eq, DeriveEntityModel, serde::Deserialize, serde::Serialize,
+    )]
+    #[sea_orm(table_name = "user")]
+    pub struct Model {
+        #[sea_orm(primary_key)]
+        pub id: i32,
+        pub tenant_id: i32,
+        pub username: String,
+        pub active: bool,
+        pub deleted: bool,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+
+    #[derive(Clone)]
+    struct Tenant(i32);
+
+    // NoRoot denies updating user named root
+    #[derive(Clone)]
+    struct NoRoot;
+
+    #[async_trait::async_trait]
+    impl quickapi_permission::Permission<Entity, ()> for NoRoot {
+        async fn has_permission(
+            &self,
+            _parts: &mut Parts,
+            _state: &(),
+        ) -> Result<(), quickapi_permission::Error> {
+            Ok(())
+        }
+
+        async fn has_object_permission(
+            &self,
+            _parts: &mut Parts,
+            _state: &(),
+            model: &Model,
+        ) -> Result<(), quickapi_permission::Error> {
+            match model.username.as_str() {
+                "root" => Err(quickapi_permission::Error::Denied(
+                    "root is reserved".to_owned(),
+                )),
+                _ => Ok(()),
+            }
+        }
+    }
+
+    async fn database() -> DatabaseConnection {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        let backend = db.get_database_backend();
+        db.execute(backend.build(&Schema::new(backend).create_table_from_entity(Entity)))
+            .await
+            .unwrap();
+        db.execute_unprepared(
+            r#"INSERT INTO "user" ("tenant_id", "username", "active", "deleted") VALUES
+                   (1, 'root', true, false),
+                   (1, 'joe', true, false),
+                   (1, 'anna', true, true),
+                   (2, 'bob', true, false)"#,
+        )
+        .await
+        .unwrap();
+        db
+    }
+
+    // active returns active flags of all users ordered by id
+    async fn active(db: &DatabaseConnection) -> Vec<bool> {
+        Entity::find()
+            .order_by_asc(Column::Id)
+            .all(db)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|user| user.active)
+            .collect()
+    }
+
+    fn server(view: super::BulkUpdateView<Entity, ()>) -> TestServer {
+        let router = view
+            .register_router(axum::Router::new())
+            .unwrap()
+            .layer(axum::Extension(Tenant(1)));
+        TestServer::new(router).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_bulk_update() {
+        let db = database().await;
+        let view = crate::new::<()>(db.clone())
+            .bulk_update::<Entity>("/user")
+            .unwrap()
+            .with_soft_delete("deleted")
+            .with_scope(quickapi_filter::ExtensionScope::<Entity, Tenant>::new(
+                Column::TenantId,
+                |tenant: &Tenant| tenant.0,
+            ));
+
+        // object permission denied for one row, nothing is updated
+        server(view.clone().with_permission(NoRoot))
+            .patch("/user")
+            .json(&json!({"active": false}))
+            .await
+            .assert_status(StatusCode::FORBIDDEN);
+        assert_eq!(active(&db).await, [true, true, true, true]);
+
+        // rows out of scope and soft deleted rows are not updated
+        let response = server(view.clone())
+            .patch("/user")
+            .json(&json!({"active": false}))
+            .await;
+        response.assert_status_ok();
+        response.assert_json(&json!({"updated": 2}));
+        assert_eq!(active(&db).await, [false, false, true, true]);
+    }
+
+    #[tokio::test]
+    async fn test_bulk_update_read_only() {
+        let db = database().await;
+        let view = crate::new::<()>(db.clone())
+            .bulk_update::<Entity>("/user")
+            .unwrap()
+            .with_read_only([Column::Username]);
+
+        // read only field is ignored
+        server(view.clone())
+            .patch("/user")
+            .json(&json!({"username": "x", "active": false}))
+            .await
+            .assert_json(&json!({"updated": 4}));
+        let user = Entity::find_by_id(1).one(&db).await.unwrap().unwrap();
+        assert_eq!((user.username.as_str(), user.active), ("root", false));
+
+        // or rejected
+        server(view.with_read_only_mode(ReadOnlyMode::Reject))
+            .patch("/user")
+            .json(&json!({"username": "x"}))
+            .await
+            .assert_status(StatusCode::BAD_REQUEST);
+    }
+}
@@ -0,0 +1,339 @@
+/*
+ *  The MIT License (MIT)
+ *
+ *  Copyright (c) 2024-2025, Peter Vrba
+ *
+ *  Permission is hereby granted, free of charge, to any person obtaining a copy
+ *  of this software and associated documentation files (the "Software"), to deal
+ *  in the Software without restriction, including without limitation the rights
+ *  to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ *  copies of the Software, and to permit persons to whom the Software is
+ *  furnished to do so, subject to the following conditions:
+ *
+ *  The above copyright notice and this permission notice shall be included in
+ *  all copies or substantial portions of the Software.
+ *
+ *  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ *  IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ *  FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ *  AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ *  LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ *  OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+ *  THE SOFTWARE.
+ *
+ */
+
+use crate::view::handler::Handler;
+use crate::view::registry::{Registration, Routes};
+use axum::Router;
+use axum::http::Method;
+use axum::http::request::Parts;
+use axum::routing::on;
+use quickapi_filter::{Scope, SelectFilter, SelectFilterErased};
+use quickapi_http::response::Response;
+use quickapi_http::response::key::Key;
+use quickapi_permission::Permission;
+use quickapi_view::{Error, RouterExt, ViewTrait};
+use sea_orm::{DatabaseConnection, EntityTrait, PaginatorTrait, QuerySelect};
+use serde_json::json;
+use std::marker::PhantomData;
+use tracing::debug;
+
+const DEFAULT_COUNT_JSON_KEY: &str = "count";
+const DEFAULT_EXISTS_JSON_KEY: &str = "exists";
+
+/// CountView is a view that returns number of entities matching the filters (`{"count": n}`),
+/// or whether any entity matches them (`{"exists": true}`).
+/// It uses the same filters, scopes and when conditions as ListView.
+#[derive(Clone)]
+pub struct CountView<E, S>
+where
+    E: EntityTrait,
+    S: Clone + Send + Sync + 'static,
+{
+    db: DatabaseConnection,
+    path: String,
+    method: Method,
+    registration: Registration,
+    filters: quickapi_filter::SelectFilters<E, S>,
+    scopes: quickapi_filter::Scopes<E, S>,
+    soft_delete: Option<quickapi_filter::SoftDelete>,
+    permissions: quickapi_permission::Permissions<E, S>,
+    when: quickapi_when::WhenViews<S>,
+    exists: bool,
+    fallback: bool,
+    wrap_json_key: Option<Key>,
+    _phantom_data: PhantomData<(E, S)>,
+}
+
+impl<E, S> CountView<E, S>
+where
+    E: EntityTrait,
+    S: Clone + Send + Sync + 'static,
+{
+    /// new creates a new CountView instance, exists view only checks whether any entity matches.
+    pub(crate) fn new(
+        db: DatabaseConnection,
+        path: impl Into<String>,
+        method: Method,
+        exists: bool,
+    ) -> Self {
+        Self {
+            db,
+            path: path.into(),
+            method,
+            registration: Registration::new::<E>(if exists { "exists" } else { "count" }),
+            filters: quickapi_filter::SelectFilters::new(),
+            scopes: quickapi_filter::Scopes::new(),
+            soft_delete: None,
+            permissions: Default::default(),
+            when: Default::default(),
+            exists,
+            fallback: false,
+            wrap_json_key: Some(match exists {
+                true => DEFAULT_EXISTS_JSON_KEY.into(),
+                false => DEFAULT_COUNT_JSON_KEY.into(),
+            }),
+            _phantom_data: PhantomData,
+        }
+    }
+
+    /// with_name sets name of the route in QuickApi routes (`{table}-{kind}` by default, e.g. `user-count`).
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.registration = self.registration.with_name(name);
+        self
+    }
+
+    /// with_routes sets registry of routes the view is recorded into when registered.
+    pub(crate) fn with_routes(mut self, routes: Routes) -> Self {
+        self.registration = self.registration.with_routes(routes);
+        self
+    }
+
+    /// with_filter adds a filter to the CountView (same filters as in ListView).
+    pub fn with_filter<F, T>(mut self, f: F) -> Self
+    where
+        F: SelectFilter<E, S, T> + Clone + Send + Sync + 'static,
+        T: Sync + Send + 'static,
+    {
+        self.filters.push(f);
+        self
+    }
+
+    /// with_scope adds a row-level scope that is applied before filters
+    pub fn with_scope(mut self, scope: impl Scope<E, S> + 'static) -> Self {
+        self.scopes.push(scope);
+        self
+    }
+
+    /// with_soft_delete marks entity as soft deletable by column, soft deleted rows are not counted.
+    pub fn with_soft_delete(mut self, column: &'static str) -> Self {
+        self.soft_delete = Some(quickapi_filter::SoftDelete::new(column));
+        self
+    }
+
+    /// with_deleted counts also soft deleted rows (usually in when condition for admins).
+    pub fn with_deleted(mut self) -> Self {
+        self.soft_delete = None;
+        self
+    }
+
+    /// with_permission adds a permission that is checked before the query runs
+    pub fn with_permission(mut self, permission: impl Permission<E, S> + 'static) -> Self {
+        self.permissions.push(permission);
+        self
+    }
+
+    /// with_fallback sets a fallback CountView that will be used if when conditions are not met.
+    pub fn with_fallback(mut self, fallback: bool) -> Self {
+        self.fallback = fallback;
+        self
+    }
+
+    /// when adds a condition to the CountView.
+    pub fn when<F, T>(
+        mut self,
+        _when: impl quickapi_when::When<S, T> + Clone + Sync + 'static,
+        _f: F,
+    ) -> Result<Self, crate::Error>
+    where
+        F: Fn(CountView<E, S>) -> Result<CountView<E, S>, crate::Error>,
+        T: Sync + Send + 'static,
+        <E as EntityTrait>::Model: Sync,
+    {
+        let mut clone = self.clone();
+        clone.when = Default::default();
+        let _result = _f(clone)?;
+        self.when.add_when(_when, _result);
+        Ok(self)
+    }
+}
+
+/// Implementing RouterExt for CountView to register the router
+impl<E, S> RouterExt<S> for CountView<E, S>
+where
+    E: EntityTrait,
+    S: Clone + Send + Sync + 'static,
+    <E as EntityTrait>::Model: Sync,
+{
+    fn register_router_with_prefix(
+        &self,
+        router: Router<S>,
+        prefix: &str,
+    ) -> Result<Router<S>, Error> {
+        let mf = quickapi_view::as_method_filter(&self.method)?;
+        let path = format!("{}{}", prefix, self.path);
+
+        debug!(method = self.method.to_string(), path = &path, "API count",);
+
+        self.registration.register(&self.method, &path)?;
+        let handler = Handler::new(self.clone()).with_db(&self.db);
+        Ok(router.route(&path, on(mf, handler)))
+    }
+
+    /// apply_scopes adds scopes registered for the entity (also to when views)
+    fn apply_scopes(&mut self, scopes: &quickapi_filter::ScopeRegistry<S>) {
+        self.scopes.extend(&scopes.get::<E>());
+        self.when.apply_scopes(scopes);
+    }
+}
+
+/// Implementing ViewTrait for CountView to handle view logic
+#[async_trait::async_trait]
+impl<E, S> ViewTrait<S> for CountView<E, S>
+where
+    E: EntityTrait,
+    S: Clone + Send + Sync + 'static,
+    <E as EntityTrait>::Model: Sync,
+{
+    async fn handle_view(
+        &self,
+        _parts: &mut Parts,
+        _state: &S,
+        _body: &bytes::Bytes,
+    ) -> Result<Response, Error> {
+        // check permissions before the query runs
+        self.permissions.has_permission(_parts, _state).await?;
+
+        // restrict rows by scopes before any user filters
+        let mut query = self.scopes.scope_select(_parts, _state, E::find()).await?;
+
+        // soft deleted rows are not counted (unless deleted are included)
+        if let Some(soft_delete) = &self.soft_delete {
+            query = soft_delete.exclude_deleted(query)?;
+        }
+
+        let query = self
+            .filters
+            .filter_select_boxed(_parts, _state, query)
+            .await
+            .map_err(|e| Error::InternalError(Box::new(e)))?;
+
+        let value = match self.exists {
+            true => json!(query.limit(1).one(&self.db).await?.is_some()),
+            false => json!(query.count(&self.db).await?),
+        };
+
+        let value = match self.wrap_json_key.clone() {
+            Some(key) => json!({ Into::<String>::into(key): value }),
+            None => value,
+        };
+
+        Ok(Response::new(value))
+    }
+
+    /// get_when_views returns a vector of views that should be executed when the CountView is called.
+    async fn get_when_views<'a>(
+        &'a self,
+        _parts: &'a mut Parts,
+        _state: &'a S,
+    ) -> Result<Vec<&'a (dyn ViewTrait<S> + Send + Sync)>, Error> {
+        self.when
+            .get_views(_parts, _state)
+            .await
+            .map_err(Into::into)
+    }
+
+    /// has_fallback returns true if the CountView has a fallback defined (if when does not matches).
+    fn has_fallback(&self) -> bool {
+        self.fallback
+    }
+}
+
+/// Implementing ViewWrapResultTrait for CountView to handle JSON response wrapping
+impl<E, S> quickapi_view::ViewWrapResultTrait<S> for CountView<E, S>
+where
+    E: EntityTrait,
+    S: Clone + Send + Sync + 'static,
+    <E as EntityTrait>::Model: Sync,
+{
+    /// wrap_result_key method to set a custom key for the JSON response
+    fn wrap_result_key(mut self, key: impl Into<Key>) -> Self {
+        self.wrap_json_key = Some(key.into());
+        self
+    }
+
+    /// no_wrap_result_key method to disable wrapping the JSON response in a key
+    fn no_wrap_result_key(mut self) -> Self {
+        self.wrap_json_key = None;
+        self
+    }
+
+    /// get_wrap_result_key method to retrieve the key used for wrapping the JSON response
+    fn get_wrap_result_key(&self) -> Option<Key> {
+        self.wrap_json_key.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quickapi_filter::common::search::Search;
+    use sea_orm::entity::prelude::*;
+    use sea_orm::{ActiveValue, ConnectionTrait, Database, Schema};
+
+    #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+    #[sea_orm(table_name = "user")]
+    pub struct Model {
+        #[sea_orm(primary_key)]
+        pub id: i32,
+        pub username: String,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+
+    #[tokio::test]
+    async fn test_count_sqlite() {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        let backend = db.get_database_backend();
+        db.execute(backend.build(&Schema::new(backend).create_table_from_entity(Entity)))
+            .await
+            .unwrap();
+        Entity::insert_many(["Joe", "joey", "Anna"].map(|username| ActiveModel {
+            username: ActiveValue::Set(username.to_owned()),
+            ..Default::default()
+        }))
+        .exec(&db)
+        .await
+        .unwrap();
+
+        let view = CountView::<Entity, ()>::new(db, "/user/count", Method::GET, false)
+            .with_filter(Search::new([Column::Username]));
+
+        let (mut parts, _) = axum::http::Request::builder()
+            .uri("/user/count?search=JO")
+            .body(())
+            .unwrap()
+            .into_parts();
+        parts.extensions.insert(backend);
+        let response = view
+            .handle_view(&mut parts, &(), &bytes::Bytes::new())
+            .await
+            .unwrap();
+
+        assert_eq!(response.data(), &json!({"count": 2}));
+    }
+}
@@ -23,20 +23,33 @@
  *
  */
 
-use crate::view::handler::Handler;
+use crate::view::handler::{Handler, check_when_max_body_size};
+use crate::view::jsonapi::{JSON_API_CONTENT_TYPE, JsonApi};
+use crate::view::links::{Link, Links};
+use crate::view::registry::{Registration, Routes};
 use axum::Router;
 use axum::http::Method;
 use axum::http::request::Parts;
 use axum::routing::on;
+use quickapi_filter::Scope;
 use quickapi_http::Response;
-use quickapi_http::serializer::ModelDeserializerJson;
+use quickapi_http::idempotency::Idempotency;
+use quickapi_http::response::Key;
+use quickapi_http::serializer::{ModelDeserializerJson, OutputSerializerJson};
+use quickapi_permission::Permission;
 use quickapi_view::{Error, ViewTrait, as_method_filter};
-use sea_orm::{DatabaseConnection, EntityTrait};
+use sea_orm::{
+    ConnectionTrait, DatabaseConnection, EntityTrait, IntoActiveModel, TransactionTrait,
+};
 use serde::de::DeserializeOwned;
+use serde_json::json;
 use std::marker::PhantomData;
 use tracing::debug;
 
-use quickapi_model::ModelCallbackErased;
+use quickapi_model::{Fields, ModelCallbackErased, ReadOnlyMode};
+
+const DEFAULT_JSON_KEY: &str = "object";
+const DEFAULT_BULK_JSON_KEY: &str = "objects";
 
 /// CreateView is a struct that represents a view for creating new records in the database.
 #[derive(Clone)]
@@ -54,10 +67,23 @@ where
     db: DatabaseConnection,
     path: String,
     method: Method,
+    registration: Registration,
     when: quickapi_when::WhenViews<S>,
     before_save: quickapi_model::ModelCallbacks<E, S>,
+    after_save: quickapi_model::ModelCallbacks<E, S>,
+    scopes: quickapi_filter::Scopes<E, S>,
+    permissions: quickapi_permission::Permissions<E, S>,
     fallback: bool,
+    transaction: bool,
+    idempotency: Option<Idempotency>,
     serializer: ModelDeserializerJson<Ser>,
+    output: OutputSerializerJson<E::Model>,
+    fields: Fields<E>,
+    max_body_size: usize,
+    bulk: bool,
+    json_api: Option<JsonApi>,
+    links: Links<E>,
+    wrap_json_key: Option<Key>,
     _phantom_data: PhantomData<(E, S, Ser)>,
 }
 
@@ -66,12 +92,10 @@ impl<E, S, Ser> quickapi_view::RouterExt<S> for CreateView<E, S, Ser>
 where
     E: EntityTrait,
     S: Clone + Send + Sync + 'static,
-    Ser: Clone
-        + DeserializeOwned
-        + Sync
-        + Send
-        + 'static,
-    <E as EntityTrait>::Model: From<Ser>,
+    Ser: Clone + DeserializeOwned + Sync + Send + 'static,
+    <E as EntityTrait>::Model:
+        From<Ser> + IntoActiveModel<E::ActiveModel> + serde::Serialize + Sync,
+    <E as EntityTrait>::ActiveModel: Send,
 {
     fn register_router_with_prefix(
         &self,
@@ -88,7 +112,15 @@ where
         );
 
         // Register the ListView with the axum router
-        Ok(router.route(&path, on(mf, Handler::new(self.clone()))))
+        self.registration.register(&self.method, &path)?;
+        let handler = Handler::new(self.clone()).with_db(&self.db);
+        Ok(router.route(&path, on(mf, handler)))
+    }
+
+    /// apply_scopes adds scopes registered for the entity (also to when views)
+    fn apply_scopes(&mut self, scopes: &quickapi_filter::ScopeRegistry<S>) {
+        self.scopes.extend(&scopes.get::<E>());
+        self.when.apply_scopes(scopes);
     }
 }
 
@@ -105,19 +137,47 @@ where
         db: DatabaseConnection,
         path: impl Into<String>,
         method: Method,
-    ) -> Result<Self, crate::Error> {
+    ) -> Result<Self, crate::Error>
+    where
+        <E as EntityTrait>::Model: serde::Serialize,
+    {
         Ok(CreateView {
             db,
             path: path.into(),
             method,
+            registration: Registration::new::<E>("create"),
             when: Default::default(),
             before_save: Default::default(),
+            after_save: Default::default(),
+            scopes: quickapi_filter::Scopes::new(),
+            permissions: Default::default(),
             fallback: false,
+            transaction: false,
+            idempotency: None,
             serializer: ModelDeserializerJson::<Ser>::new(),
+            output: Default::default(),
+            fields: Default::default(),
+            max_body_size: crate::quickapi::DEFAULT_MAX_BODY_SIZE,
+            bulk: false,
+            json_api: None,
+            links: Default::default(),
+            wrap_json_key: Some(DEFAULT_JSON_KEY.into()),
             _phantom_data: PhantomData,
         })
     }
 
+    /// with_name sets name of the route in QuickApi routes (`{table}-{kind}` by default, e.g. `user-create`).
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.registration = self.registration.with_name(name);
+        self
+    }
+
+    /// with_routes sets registry of routes the view is recorded into when registered.
+    pub(crate) fn with_routes(mut self, routes: Routes) -> Self {
+        self.registration = self.registration.with_routes(routes);
+        self
+    }
+
     /// with_serializer sets custom serializer for the CreateView.
     pub fn with_serializer<Serializer>(self) -> CreateView<E, S, Serializer>
     where
@@ -128,14 +188,55 @@ where
             db: self.db,
             path: self.path,
             method: self.method,
+            registration: self.registration,
             when: self.when,
             before_save: self.before_save,
+            after_save: self.after_save,
+            scopes: self.scopes,
+            permissions: self.permissions,
             serializer: ModelDeserializerJson::<Serializer>::new(),
+            output: self.output,
+            fields: self.fields,
             fallback: false,
+            transaction: self.transaction,
+            idempotency: self.idempotency,
+            max_body_size: self.max_body_size,
+            bulk: self.bulk,
+            json_api: self.json_api,
+            links: self.links,
+            wrap_json_key: self.wrap_json_key,
             _phantom_data: PhantomData,
         }
     }
 
+    /// with_output_serializer sets serializer of created objects in response (input serializer is kept).
+    pub fn with_output_serializer<O>(mut self) -> Self
+    where
+        O: From<E::Model> + serde::Serialize,
+    {
+        self.output = OutputSerializerJson::new::<O>();
+        self
+    }
+
+    /// with_read_only marks columns as read only, client cannot set them (database default is used).
+    pub fn with_read_only(mut self, columns: impl IntoIterator<Item = E::Column>) -> Self {
+        self.fields = self.fields.with_read_only(columns);
+        self
+    }
+
+    /// with_write_only marks columns as write only, they are never returned in response.
+    pub fn with_write_only(mut self, columns: impl IntoIterator<Item = E::Column>) -> Self {
+        self.fields = self.fields.with_write_only(columns);
+        self
+    }
+
+    /// with_read_only_mode sets whether read only fields sent by client are ignored (default)
+    /// or rejected with 400.
+    pub fn with_read_only_mode(mut self, mode: ReadOnlyMode) -> Self {
+        self.fields = self.fields.with_read_only_mode(mode);
+        self
+    }
+
     /// with_before_save sets a before save handler for the CreateView.
     pub fn with_before_save<T>(
         mut self,
@@ -154,49 +255,445 @@ where
         self
     }
 
+    /// with_after_save sets an after save handler for the CreateView.
+    /// It's called with the inserted object, returned object is used in response.
+    pub fn with_after_save<T>(
+        mut self,
+        after_save: impl quickapi_model::ModelCallback<E, S, T> + Clone + Send + Sync + 'static,
+    ) -> Self
+    where
+        T: Send + Sync + 'static,
+    {
+        self.after_save.push(after_save);
+        self
+    }
+
+    /// remove all after save handlers from the CreateView.
+    pub fn clear_after_save(mut self) -> Self {
+        self.after_save.clear();
+        self
+    }
+
+    /// with_scope adds a row-level scope to the CreateView.
+    /// Scoped columns are force-set on the instance after before save callbacks.
+    pub fn with_scope(mut self, scope: impl Scope<E, S> + 'static) -> Self {
+        self.scopes.push(scope);
+        self
+    }
+
+    /// with_permission adds a permission to the CreateView.
+    /// Object permission is checked on the instance after before save callbacks, before it's inserted.
+    pub fn with_permission(mut self, permission: impl Permission<E, S> + 'static) -> Self {
+        self.permissions.push(permission);
+        self
+    }
+
+    /// with_max_body_size sets the maximum size of request body (0 means no limit).
+    pub fn with_max_body_size(mut self, max_body_size: usize) -> Self {
+        self.max_body_size = max_body_size;
+        self
+    }
+
+    /// with_bulk enables creating multiple objects at once, when request body is JSON array.
+    /// All objects are inserted in one transaction, or none when any of them is invalid or denied.
+    /// Created objects are wrapped in `objects` key (or key set by wrap_result_key), bulk create needs
+    /// database with RETURNING support (postgres, sqlite with sea-orm `sqlite-use-returning-for-3_35`).
+    pub fn with_bulk(mut self, bulk: bool) -> Self {
+        self.bulk = bulk;
+        self
+    }
+
+    /// with_link adds named link (e.g. self, related resource) to serialized object as HAL `_links`,
+    /// path parameters of the link are resolved from model columns (e.g. `/api/user/{id}`).
+    pub fn with_link(
+        mut self,
+        name: impl Into<String>,
+        link: impl Into<Link>,
+    ) -> Result<Self, crate::Error> {
+        self.links.push(name, link)?;
+        Ok(self)
+    }
+
+    /// with_json_api parses JSON:API request document and renders created object(s) as JSON:API document.
+    pub fn with_json_api(mut self, json_api: JsonApi) -> Self {
+        self.json_api = Some(json_api);
+        self
+    }
+
+    /// with_transaction runs the whole CreateView (callbacks and queries) in one database transaction.
+    /// Transaction is committed on success response, rolled back otherwise.
+    pub fn with_transaction(mut self, transaction: bool) -> Self {
+        self.transaction = transaction;
+        self
+    }
+
+    /// with_idempotency makes the CreateView honour Idempotency-Key header, first response
+    /// for the key is stored in the store and returned for retried requests.
+    pub fn with_idempotency(mut self, idempotency: impl Into<Idempotency>) -> Self {
+        self.idempotency = Some(idempotency.into());
+        self
+    }
+
     /// with_fallback sets a fallback CreateView that will be used if when conditions are not met.
-    pub fn with_fallback<Serializer>(mut self, fallback: bool) -> Self {
+    pub fn with_fallback(mut self, fallback: bool) -> Self {
         self.fallback = fallback;
         self
     }
 
-    // TODO: add when
+    /// when adds a condition to the CreateView.
+    /// Closure receives clone of the view (without when conditions) and can change
+    /// serializer, before save callbacks and other settings.
+    pub fn when<F, T, Serializer>(
+        mut self,
+        _when: impl quickapi_when::When<S, T> + Clone + Sync + 'static,
+        _f: F,
+    ) -> Result<Self, crate::Error>
+    where
+        Serializer: Clone + DeserializeOwned + Sync + Send + 'static,
+        <E as EntityTrait>::Model:
+            From<Serializer> + IntoActiveModel<E::ActiveModel> + serde::Serialize + Sync,
+        <E as EntityTrait>::ActiveModel: Send,
+        F: Fn(CreateView<E, S, Ser>) -> Result<CreateView<E, S, Serializer>, crate::Error>,
+        T: Sync + Send + 'static,
+    {
+        let mut clone = self.clone();
+        clone.when = Default::default();
+        let _result = _f(clone)?;
+        check_when_max_body_size(self.max_body_size, _result.max_body_size)?;
+        self.when.add_when(_when, _result);
+        Ok(self)
+    }
 }
 
-/// Implement the ViewTrait for CreateView, which defines how the view handles requests.
-#[async_trait::async_trait]
-impl<E, S, Ser> ViewTrait<S> for CreateView<E, S, Ser>
+/// CreateView implementation of bulk create.
+impl<E, S, Ser> CreateView<E, S, Ser>
 where
     E: EntityTrait,
     S: Clone + Send + Sync + 'static,
     Ser: Clone + DeserializeOwned + Sync + Send + 'static,
-    <E as EntityTrait>::Model: From<Ser>,
+    <E as EntityTrait>::Model:
+        From<Ser> + IntoActiveModel<E::ActiveModel> + serde::Serialize + Sync,
+    <E as EntityTrait>::ActiveModel: Send,
 {
-    async fn handle_view(
+    /// handle_bulk creates objects from JSON array with insert_many in one transaction.
+    /// Invalid, out of scope and denied items are reported by their index and nothing is inserted.
+    async fn handle_bulk<C>(
+        &self,
+        conn: &C,
+        parts: &mut Parts,
+        state: &S,
+        body: &bytes::Bytes,
+    ) -> Result<Response, Error>
+    where
+        C: ConnectionTrait + TransactionTrait,
+    {
+        // created objects are read back by RETURNING (not supported by MySQL)
+        if !conn.support_returning() {
+            return Err(Error::ImproperlyConfigured(
+                "bulk create requires database with RETURNING support".to_owned(),
+            ));
+        }
+
+        let items: Vec<serde_json::Value> = match serde_json::from_slice(body) {
+            Ok(items) => items,
+            Err(e) => {
+                return Ok(Response::new(json!({
+                    "error": "Bad Request",
+                    "message": e.to_string(),
+                }))
+                .with_status(axum::http::StatusCode::BAD_REQUEST));
+            }
+        };
+
+        let mut instances = Vec::with_capacity(items.len());
+        let mut errors = Vec::new();
+        let mut forbidden = false;
+
+        for (index, item) in items.into_iter().enumerate() {
+            // deserialize the item into the model (via the serializer)
+            let received: E::Model = match serde_json::from_value::<Ser>(item) {
+                Ok(instance) => instance.into(),
+                Err(e) => {
+                    errors.push(json!({"index": index, "message": e.to_string()}));
+                    continue;
+                }
+            };
+
+            // apply before_save handlers
+            let instance = match self.before_save.call(parts, state, received.clone()).await {
+                Ok(instance) => instance,
+                Err(e) => {
+                    errors.push(json!({"index": index, "message": e.to_string()}));
+                    continue;
+                }
+            };
+
+            // force scoped columns
+            let instance = match self.scopes.scope_model(parts, state, instance).await {
+                Ok(instance) => instance,
+                Err(
+                    quickapi_filter::Error::Forbidden(message)
+                    | quickapi_filter::Error::NotFound(message),
+                ) => {
+                    forbidden = true;
+                    errors.push(json!({"index": index, "message": message}));
+                    continue;
+                }
+                Err(e) => return Err(e.into()),
+            };
+
+            // check object permissions
+            match self
+                .permissions
+                .has_object_permission(parts, state, &instance)
+                .await
+            {
+                Ok(()) => {}
+                Err(quickapi_permission::Error::Denied(message)) => {
+                    forbidden = true;
+                    errors.push(json!({"index": index, "message": message}));
+                    continue;
+                }
+                Err(e) => return Err(e.into()),
+            }
+
+            // read only columns are left to the database
+            let mut active = quickapi_model::insert_active_model::<E>(instance.clone());
+            self.fields.unset_read_only(&received, &instance, &mut active);
+
+            instances.push(active);
+        }
+
+        // any denied item forbids the whole batch
+        if !errors.is_empty() {
+            let status = match forbidden {
+                true => axum::http::StatusCode::FORBIDDEN,
+                false => axum::http::StatusCode::BAD_REQUEST,
+            };
+            return Ok(Response::new(json!({
+                "error": status.canonical_reason(),
+                "errors": errors,
+            }))
+            .with_status(status));
+        }
+
+        // insert all instances in one transaction
+        let created = if instances.is_empty() {
+            Vec::new()
+        } else {
+            let txn = conn.begin().await?;
+            let created = E::insert_many(instances)
+                .exec_with_returning_many(&txn)
+                .await?;
+
+            // apply after_save handlers
+            let mut saved = Vec::with_capacity(created.len());
+            for object in created {
+                saved.push(
+                    self.after_save
+                        .call(parts, state, object)
+                        .await
+                        .map_err(|e| Error::InternalError(Box::new(e)))?,
+                );
+            }
+
+            txn.commit().await?;
+            saved
+        };
+
+        let mut serialized = created
+            .into_iter()
+            .map(|o| {
+                let links = self.links.render(&o);
+                let mut object = self
+                    .output
+                    .serialize_json(o)
+                    .map_err(|e| Error::InternalError(Box::new(e)))?;
+                Links::<E>::attach(&mut object, links);
+                Ok(object)
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+        serialized
+            .iter_mut()
+            .for_each(|object| self.fields.strip_output(object));
+
+        if let Some(json_api) = &self.json_api {
+            return Ok(
+                Response::new(json_api.document(serde_json::Value::Array(serialized)))
+                    .with_header(axum::http::header::CONTENT_TYPE, JSON_API_CONTENT_TYPE)
+                    .with_status(axum::http::StatusCode::CREATED),
+            );
+        }
+
+        // default key of single object is pluralized, custom key is used as is
+        let objects = match self.wrap_json_key.clone().map(String::from) {
+            Some(key) if key == DEFAULT_JSON_KEY => json!({ DEFAULT_BULK_JSON_KEY: serialized }),
+            Some(key) => json!({ key: serialized }),
+            None => serde_json::Value::Array(serialized),
+        };
+
+        Ok(Response::new(objects).with_status(axum::http::StatusCode::CREATED))
+    }
+}
+
+/// CreateView implementation of the request pipeline.
+impl<E, S, Ser> CreateView<E, S, Ser>
+where
+    E: EntityTrait,
+    S: Clone + Send + Sync + 'static,
+    Ser: Clone + DeserializeOwned + Sync + Send + 'static,
+    <E as EntityTrait>::Model:
+        From<Ser> + IntoActiveModel<E::ActiveModel> + serde::Serialize + Sync,
+    <E as EntityTrait>::ActiveModel: Send,
+{
+    /// handle runs the view pipeline on given connection (database or transaction).
+    async fn handle<C>(
         &self,
+        conn: &C,
         _parts: &mut Parts,
         _state: &S,
         _body: &bytes::Bytes,
-    ) -> Result<Response, Error> {
+    ) -> Result<Response, Error>
+    where
+        C: ConnectionTrait + TransactionTrait,
+    {
         let mut _parts = _parts.clone();
 
+        // check permissions before anything else
+        self.permissions.has_permission(&mut _parts, _state).await?;
+
+        // JSON:API request document is parsed into plain object(s)
+        let _body = &match &self.json_api {
+            Some(json_api) => match json_api.parse(_body) {
+                Ok(body) => body,
+                Err(e) => {
+                    return Ok(Response::new(json!({
+                        "error": "Bad Request",
+                        "message": e.to_string(),
+                    }))
+                    .with_status(axum::http::StatusCode::BAD_REQUEST));
+                }
+            },
+            None => _body.clone(),
+        };
+
+        // reject read only fields sent by client (when configured)
+        if let Err(e) = self.fields.check_input(_body) {
+            return Ok(Response::new(json!({
+                "error": "Bad Request",
+                "message": e.to_string(),
+            }))
+            .with_status(axum::http::StatusCode::BAD_REQUEST));
+        }
+
+        // bulk create from JSON array
+        if self.bulk && _body.trim_ascii_start().first() == Some(&b'[') {
+            return self.handle_bulk(conn, &mut _parts, _state, _body).await;
+        }
+
         // deserialize the body into the model (via the serializer)
-        let _instance: E::Model = self
-            .serializer
-            .deserialize_json::<E>(_body)
-            .map_err(|e| Error::InternalError(Box::new(e)))?;
+        let _instance: E::Model = match self.serializer.deserialize_json::<E>(_body) {
+            Ok(instance) => instance,
+            Err(e) => {
+                return Ok(Response::new(json!({
+                    "error": "Bad Request",
+                    "message": e.to_string(),
+                }))
+                .with_status(axum::http::StatusCode::BAD_REQUEST));
+            }
+        };
 
         // now we need to call before_save handlers
         // TODO: add validations
 
         // apply before_save handlers
-        let _instance = &self
+        let received = _instance.clone();
+        let _instance = self
             .before_save
             .call(&mut _parts, _state, _instance)
             .await
             .map_err(|e| Error::InternalError(Box::new(e)))?;
 
-        todo!()
+        // force scoped columns, so client cannot create objects outside of its scope
+        let _instance = self
+            .scopes
+            .scope_model(&mut _parts, _state, _instance)
+            .await?;
+
+        // check object permissions on the instance before it's inserted
+        self.permissions
+            .has_object_permission(&mut _parts, _state, &_instance)
+            .await?;
+
+        // insert the instance into the database, read only columns are left to the database
+        let mut active = quickapi_model::insert_active_model::<E>(_instance.clone());
+        self.fields.unset_read_only(&received, &_instance, &mut active);
+        let created = E::insert(active).exec_with_returning(conn).await?;
+
+        // apply after_save handlers
+        let created = self
+            .after_save
+            .call(&mut _parts, _state, created)
+            .await
+            .map_err(|e| Error::InternalError(Box::new(e)))?;
+
+        let links = self.links.render(&created);
+        let mut serialized = self
+            .output
+            .serialize_json(created)
+            .map_err(|e| Error::InternalError(Box::new(e)))?;
+        Links::<E>::attach(&mut serialized, links);
+        self.fields.strip_output(&mut serialized);
+
+        if let Some(json_api) = &self.json_api {
+            return Ok(Response::new(json_api.document(serialized))
+                .with_header(axum::http::header::CONTENT_TYPE, JSON_API_CONTENT_TYPE)
+                .with_status(axum::http::StatusCode::CREATED));
+        }
+
+        let object = match &self.wrap_json_key {
+            Some(key) => serde_json::Value::Object(serde_json::Map::from_iter(vec![(
+                Into::<String>::into(key.clone()),
+                serialized,
+            )])),
+            None => serialized,
+        };
+
+        Ok(Response::new(object).with_status(axum::http::StatusCode::CREATED))
+    }
+}
+
+/// Implement the ViewTrait for CreateView, which defines how the view handles requests.
+#[async_trait::async_trait]
+impl<E, S, Ser> ViewTrait<S> for CreateView<E, S, Ser>
+where
+    E: EntityTrait,
+    S: Clone + Send + Sync + 'static,
+    Ser: Clone + DeserializeOwned + Sync + Send + 'static,
+    <E as EntityTrait>::Model:
+        From<Ser> + IntoActiveModel<E::ActiveModel> + serde::Serialize + Sync,
+    <E as EntityTrait>::ActiveModel: Send,
+{
+    async fn handle_view(
+        &self,
+        _parts: &mut Parts,
+        _state: &S,
+        _body: &bytes::Bytes,
+    ) -> Result<Response, Error> {
+        if !self.transaction {
+            return self.handle(&self.db, _parts, _state, _body).await;
+        }
+
+        // run the whole pipeline in one transaction, rolled back on any error
+        let transaction = crate::view::transaction::begin(&self.db, _parts).await?;
+        let response = match self.handle(&*transaction, _parts, _state, _body).await {
+            Ok(response) => response,
+            Err(err) => {
+                crate::view::transaction::discard(transaction, _parts);
+                return Err(err);
+            }
+        };
+        crate::view::transaction::finish(transaction, _parts, response).await
     }
 
     /// get_when_views returns a vector of views that should be executed when the CreateView is called.
@@ -209,11 +706,381 @@ where
         self.when
             .get_views(_parts, _state)
             .await
-            .map_err(|e| Error::InternalError(Box::new(e)))
+            .map_err(Into::into)
     }
 
     /// has_fallback returns true if the CreateView has a fallback defined (if when does not matches).
     fn has_fallback(&self) -> bool {
         self.fallback
     }
+
+    /// max_body_size returns the maximum size of request body for the CreateView.
+    fn max_body_size(&self) -> Option<usize> {
+        Some(self.max_body_size)
+    }
+
+    /// idempotency returns Idempotency-Key handling of the CreateView.
+    fn idempotency(&self) -> Option<Idempotency> {
+        self.idempotency.clone()
+    }
+
+    /// has_permission checks CreateView permissions before stored response is replayed.
+    async fn has_permission(&self, _parts: &mut Parts, _state: &S) -> Result<(), Error> {
+        Ok(self.permissions.has_permission(_parts, _state).await?)
+    }
+}
+
+/// Implementing ViewWrapResultTrait for CreateView to handle JSON response wrapping
+impl<E, S, Ser> quickapi_view::ViewWrapResultTrait<S> for CreateView<E, S, Ser>
+where
+    E: EntityTrait,
+    S: Clone + Send + Sync + 'static,
+    Ser: Clone + DeserializeOwned + Sync + Send + 'static,
+    <E as EntityTrait>::Model:
+        From<Ser> + IntoActiveModel<E::ActiveModel> + serde::Serialize + Sync,
+    <E as EntityTrait>::ActiveModel: Send,
+{
+    /// wrap_result_key method to set a custom key for the JSON response
+    fn wrap_result_key(mut self, key: impl Into<Key>) -> Self {
+        self.wrap_json_key = Some(key.into());
+        self
+    }
+
+    /// no_wrap_result_key method to disable wrapping the JSON response in a key
+    fn no_wrap_result_key(mut self) -> Self {
+        self.wrap_json_key = None;
+        self
+    }
+
+    /// get_wrap_result_key method to retrieve the key used for wrapping the JSON response
+    fn get_wrap_result_key(&self) -> Option<Key> {
+        self.wrap_json_key.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quickapi_view::ViewWrapResultTrait;
+    use sea_orm::entity::prelude::*;
+    use sea_orm::{Database, Schema};
+
+    mod user {
+        use sea_orm::entity::prelude::*;
+
+        #[derive(
+            Clone, Debug, PartialEq, Eq, DeriveEntityModel, serde::Serialize, serde::Deserialize,
+        )]
+        #[sea_orm(table_name = "user")]
+        pub struct Model {
+            #[sea_orm(primary_key)]
+            pub id: i32,
+            pub username: String,
+        }
+
+        #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+        pub enum Relation {}
+
+        impl ActiveModelBehavior for ActiveModel {}
+    }
+
+    fn view() -> CreateView<user::Entity, (), user::Model> {
+        CreateView::new(DatabaseConnection::Disconnected, "/user", Method::POST).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_bad_request() {
+        let (mut parts, _) = axum::http::Request::builder()
+            .method(Method::POST)
+            .uri("/user")
+            .body(())
+            .unwrap()
+            .into_parts();
+
+        // body is rejected before anything touches the database
+        let response = view()
+            .handle_view(&mut parts, &(), &bytes::Bytes::from(r#"{"id": 1}"#))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), axum::http::StatusCode::BAD_REQUEST);
+    }
+
+    #[test]
+    fn test_wrap_result_key() {
+        let key = |view: CreateView<user::Entity, (), user::Model>| {
+            view.get_wrap_result_key().map(String::from)
+        };
+        assert_eq!(key(view()), Some(DEFAULT_JSON_KEY.to_owned()));
+        assert_eq!(key(view().wrap_result_key("user")), Some("user".to_owned()));
+        assert_eq!(key(view().no_wrap_result_key()), None);
+    }
+
+    #[test]
+    fn test_when_max_body_size() {
+        // body limit is per route, when branch can't change it
+        assert!(
+            view()
+                .when(async move || Ok(()), |v| Ok(v.with_max_body_size(1)))
+                .is_err()
+        );
+        assert!(
+            view()
+                .with_max_body_size(1)
+                .when(async move || Ok(()), Ok)
+                .is_ok()
+        );
+    }
+
+    #[derive(
+        Clone, Debug, PartialEq, Eq, DeriveEntityModel, serde::Deserialize, serde::Serialize,
+    )]
+    #[sea_orm(table_name = "user")]
+    pub struct Model {
+        #[sea_orm(primary_key)]
+        pub id: i32,
+        pub username: String,
+        pub password: String,
+        #[sea_orm(default_value = 0)]
+        pub karma: i32,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+
+    #[derive(serde::Serialize)]
+    struct Username {
+        username: String,
+    }
+
+    impl From<Model> for Username {
+        fn from(model: Model) -> Self {
+            Username {
+                username: model.username,
+            }
+        }
+    }
+
+    // CreateUser is input serializer that accepts also read only karma (it's up to the view)
+    #[derive(Clone, serde::Deserialize, serde::Serialize)]
+    struct CreateUser {
+        username: String,
+        password: String,
+        #[serde(default)]
+        karma: i32,
+    }
+
+    impl From<CreateUser> for Model {
+        fn from(input: CreateUser) -> Self {
+            Model {
+                id: 0,
+                username: input.username,
+                password: input.password,
+                karma: input.karma,
+            }
+        }
+    }
+
+    async fn create(
+        view: &CreateView<Entity, (), CreateUser>,
+        body: serde_json::Value,
+    ) -> Response {
+        let (mut parts, _) = axum::http::Request::builder()
+            .method(Method::POST)
+            .uri("/user")
+            .body(())
+            .unwrap()
+            .into_parts();
+        view.handle_view(&mut parts, &(), &bytes::Bytes::from(body.to_string()))
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_read_only_write_only() {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        let backend = db.get_database_backend();
+        db.execute(backend.build(&Schema::new(backend).create_table_from_entity(Entity)))
+            .await
+            .unwrap();
+
+        let view = CreateView::<Entity, (), Model>::new(db, "/user", Method::POST)
+            .unwrap()
+            .with_serializer::<CreateUser>()
+            .with_read_only([Column::Karma])
+            .with_write_only([Column::Password]);
+        let body = json!({"username": "joe", "password": "secret", "karma": 100});
+
+        // read only field is ignored, write only field is not returned
+        let response = create(&view, body.clone()).await;
+        assert_eq!(
+            response.data(),
+            &json!({"object": {"id": 1, "username": "joe", "karma": 0}})
+        );
+
+        // read only field is rejected
+        let view = view.with_read_only_mode(ReadOnlyMode::Reject);
+        let response = create(&view, body).await;
+        assert_eq!(
+            response.data(),
+            &json!({"error": "Bad Request", "message": "Field `karma` is read only"})
+        );
+
+        // output serializer differs from input serializer
+        let view = view.with_output_serializer::<Username>();
+        let response = create(&view, json!({"username": "anna", "password": "secret"})).await;
+        assert_eq!(response.data(), &json!({"object": {"username": "anna"}}));
+    }
+
+    #[tokio::test]
+    async fn test_json_api() {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        let backend = db.get_database_backend();
+        db.execute(backend.build(&Schema::new(backend).create_table_from_entity(Entity)))
+            .await
+            .unwrap();
+
+        let view = CreateView::<Entity, (), Model>::new(db, "/user", Method::POST)
+            .unwrap()
+            .with_serializer::<CreateUser>()
+            .with_write_only([Column::Password])
+            .with_json_api(JsonApi::new::<Entity>().unwrap());
+
+        let response = create(
+            &view,
+            json!({"data": {"type": "user", "attributes": {"username": "joe", "password": "secret"}}}),
+        )
+        .await;
+        assert_eq!(response.status(), axum::http::StatusCode::CREATED);
+        assert_eq!(
+            response.headers()[axum::http::header::CONTENT_TYPE],
+            JSON_API_CONTENT_TYPE
+        );
+        assert_eq!(
+            response.data(),
+            &json!({"data": {"type": "user", "id": "1", "attributes": {"username": "joe", "karma": 0}}})
+        );
+
+        // resource of other type is rejected
+        let response = create(&view, json!({"data": {"type": "order", "attributes": {}}})).await;
+        assert_eq!(response.status(), axum::http::StatusCode::BAD_REQUEST);
+    }
+
+    // NoRoot denies creating user named root
+    #[derive(Clone)]
+    struct NoRoot;
+
+    #[async_trait::async_trait]
+    impl quickapi_permission::Permission<Entity, ()> for NoRoot {
+        async fn has_permission(
+            &self,
+            _parts: &mut Parts,
+            _state: &(),
+        ) -> Result<(), quickapi_permission::Error> {
+            Ok(())
+        }
+
+        async fn has_object_permission(
+            &self,
+            _parts: &mut Parts,
+            _state: &(),
+            model: &Model,
+        ) -> Result<(), quickapi_permission::Error> {
+            match model.username.as_str() {
+                "root" => Err(quickapi_permission::Error::Denied(
+                    "root is reserved".to_owned(),
+                )),
+                _ => Ok(()),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_bulk() {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        let backend = db.get_database_backend();
+        db.execute(backend.build(&Schema::new(backend).create_table_from_entity(Entity)))
+            .await
+            .unwrap();
+
+        let view = CreateView::<Entity, (), Model>::new(db, "/user", Method::POST)
+            .unwrap()
+            .with_serializer::<CreateUser>()
+            .with_output_serializer::<Username>()
+            .with_permission(NoRoot)
+            .with_bulk(true);
+        let user = |username: &str| json!({"username": username, "password": "secret"});
+
+        // denied item is reported by index, nothing is created
+        let response = create(&view, json!([user("joe"), user("root"), {}])).await;
+        assert_eq!(response.status(), axum::http::StatusCode::FORBIDDEN);
+        let errors = &response.data()["errors"];
+        assert_eq!(
+            errors.as_array().map(|errors| errors.len()),
+            Some(2),
+            "{errors}"
+        );
+        assert_eq!(
+            errors[0],
+            json!({"index": 1, "message": "root is reserved"})
+        );
+        assert_eq!(errors[1]["index"], json!(2));
+
+        // created objects are wrapped in plural of default key, or in custom key
+        let response = create(&view, json!([user("joe"), user("anna")])).await;
+        assert_eq!(response.status(), axum::http::StatusCode::CREATED);
+        assert_eq!(
+            response.data(),
+            &json!({"objects": [{"username": "joe"}, {"username": "anna"}]})
+        );
+        let view = view.wrap_result_key("users");
+        let response = create(&view, json!([user("bob")])).await;
+        assert_eq!(response.data(), &json!({"users": [{"username": "bob"}]}));
+    }
+    #[tokio::test]
+    async fn test_transaction() {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        let backend = db.get_database_backend();
+        db.execute(backend.build(&Schema::new(backend).create_table_from_entity(Entity)))
+            .await
+            .unwrap();
+
+        let view = CreateView::<Entity, (), Model>::new(db.clone(), "/user", Method::POST)
+            .unwrap()
+            .with_serializer::<CreateUser>()
+            .with_transaction(true)
+            .with_after_save(async |model: Model| {
+                if model.username == "root" {
+                    return Err(quickapi_model::Error::InternalError("audit failed".into()));
+                }
+                Ok(model)
+            });
+        let (mut parts, _) = axum::http::Request::builder()
+            .method(Method::POST)
+            .uri("/user")
+            .body(())
+            .unwrap()
+            .into_parts();
+
+        // after save error rolls back the insert
+        let body = json!({"username": "root", "password": "secret"});
+        let result = view
+            .handle_view(&mut parts, &(), &bytes::Bytes::from(body.to_string()))
+            .await;
+        assert!(result.is_err());
+        assert_eq!(Entity::find().count(&db).await.unwrap(), 0);
+
+        // successful response commits
+        let response = create(&view, json!({"username": "joe", "password": "secret"})).await;
+        assert_eq!(response.status(), axum::http::StatusCode::CREATED);
+        let users = Entity::find().all(&db).await.unwrap();
+        assert_eq!(
+            users
+                .iter()
+                .map(|u| u.username.as_str())
+                .collect::<Vec<_>>(),
+            ["joe"]
+        );
+    }
 }
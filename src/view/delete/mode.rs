@@ -24,9 +24,11 @@
  */
 
 /// Mode enum defines the mode of the delete operation. if single, only one instance is deleted, if multiple, all instances matching the criteria are deleted.
+/// Soft mode does not delete the instance, it marks it as deleted by setting column (boolean or timestamp).
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 pub enum DeleteMode {
     #[default]
     Single,
     Multiple,
+    Soft { column: &'static str },
 }
@@ -24,15 +24,27 @@
  */
 
 use crate::view::handler::Handler;
+use crate::view::links::{Link, Links};
+use crate::view::registry::{Registration, Routes};
 use axum::Router;
 use axum::http::Method;
 use axum::http::request::Parts;
 use axum::routing::on;
+use quickapi_filter::Scope;
+use quickapi_http::etag::ETag;
+use quickapi_http::serializer::OutputSerializerJson;
 use quickapi_lookup::Lookup;
+use quickapi_model::Fields;
+use quickapi_permission::Permission;
 use quickapi_view::as_method_filter;
 use quickapi_view::{Error, ViewTrait};
 use quickapi_when::WhenViews;
-use sea_orm::DatabaseConnection;
+use sea_orm::sea_query::Expr;
+use sea_orm::{
+    ConnectionTrait, DatabaseConnection, EntityTrait, IntoActiveModel, QueryFilter,
+    TransactionTrait,
+};
+use serde_json::json;
 use std::marker::PhantomData;
 use std::sync::Arc;
 use tracing::debug;
@@ -48,10 +60,19 @@ where
     db: DatabaseConnection,
     path: String,
     method: Method,
+    registration: Registration,
     mode: super::DeleteMode,
+    soft_delete: Option<quickapi_filter::SoftDelete>,
+    etag: ETag,
+    output: OutputSerializerJson<E::Model>,
+    fields: Fields<E>,
+    links: Links<E>,
     when: WhenViews<S>,
     lookup: Arc<dyn Lookup<E, S>>,
+    scopes: quickapi_filter::Scopes<E, S>,
+    permissions: quickapi_permission::Permissions<E, S>,
     fallback: bool,
+    transaction: bool,
     _phantom_data: PhantomData<(E, S)>,
 }
 
@@ -66,19 +87,50 @@ where
         path: impl Into<String>,
         method: Method,
         lookup: impl Lookup<E, S> + 'static,
-    ) -> Self {
+    ) -> Self
+    where
+        <E as EntityTrait>::Model: serde::Serialize,
+    {
         Self {
             db,
             path: path.into(),
             method,
+            registration: Registration::new::<E>("delete"),
             mode: Default::default(),
+            soft_delete: None,
+            etag: Default::default(),
+            output: Default::default(),
+            fields: Default::default(),
+            links: Default::default(),
             when: Default::default(),
             lookup: Arc::new(lookup),
+            scopes: quickapi_filter::Scopes::new(),
+            permissions: Default::default(),
             fallback: false,
+            transaction: false,
             _phantom_data: Default::default(),
         }
     }
 
+    /// with_name sets name of the route in QuickApi routes (`{table}-{kind}` by default, e.g. `user-delete`).
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.registration = self.registration.with_name(name);
+        self
+    }
+
+    /// with_routes sets registry of routes the view is recorded into when registered.
+    pub(crate) fn with_routes(mut self, routes: Routes) -> Self {
+        self.registration = self.registration.with_routes(routes);
+        self
+    }
+
+    /// with_transaction runs the whole DeleteView (callbacks and queries) in one database transaction.
+    /// Transaction is committed on success response, rolled back otherwise.
+    pub fn with_transaction(mut self, transaction: bool) -> Self {
+        self.transaction = transaction;
+        self
+    }
+
     /// with_fallback sets the fallback for the DeleteView.
     pub fn with_fallback(mut self, fallback: bool) -> Self {
         self.fallback = fallback;
@@ -91,11 +143,216 @@ where
         self
     }
 
+    /// with_scope adds a row-level scope to the DeleteView.
+    /// Scopes are applied before lookup, so objects out of scope are never deleted.
+    pub fn with_scope(mut self, scope: impl Scope<E, S> + 'static) -> Self {
+        self.scopes.push(scope);
+        self
+    }
+
+    /// with_permission adds a permission to the DeleteView.
+    /// Permission is checked before the query runs, object permission for every object before it's deleted.
+    pub fn with_permission(mut self, permission: impl Permission<E, S> + 'static) -> Self {
+        self.permissions.push(permission);
+        self
+    }
+
     /// with_mode sets the mode for the DeleteView.
     pub fn with_mode(mut self, mode: super::DeleteMode) -> Self {
         self.mode = mode;
         self
     }
+
+    /// with_soft_delete marks entity as soft deletable by column, soft deleted rows are not found in any mode
+    /// (so they are not deleted by Single or Multiple mode either).
+    pub fn with_soft_delete(mut self, column: &'static str) -> Self {
+        self.soft_delete = Some(quickapi_filter::SoftDelete::new(column));
+        self
+    }
+
+    /// with_etag_column sets version column used for ETag in If-Match precondition
+    /// (by default ETag is hash of serialized object), row modified meanwhile is not deleted (412).
+    pub fn with_etag_column(mut self, column: &'static str) -> Self {
+        self.etag = ETag::Column(column);
+        self
+    }
+
+    /// with_output_serializer sets serializer of the object ETag in If-Match precondition is computed from,
+    /// set it (with write only fields and links) same as in DetailView, so ETag returned by DetailView matches.
+    pub fn with_output_serializer<O>(mut self) -> Self
+    where
+        O: From<E::Model> + serde::Serialize,
+    {
+        self.output = OutputSerializerJson::new::<O>();
+        self
+    }
+
+    /// with_write_only marks columns as write only, they are not part of serialized object (see with_output_serializer).
+    pub fn with_write_only(mut self, columns: impl IntoIterator<Item = E::Column>) -> Self {
+        self.fields = self.fields.with_write_only(columns);
+        self
+    }
+
+    /// with_link adds named link to serialized object as HAL `_links` (see with_output_serializer).
+    pub fn with_link(
+        mut self,
+        name: impl Into<String>,
+        link: impl Into<Link>,
+    ) -> Result<Self, crate::Error> {
+        self.links.push(name, link)?;
+        Ok(self)
+    }
+
+    /// when adds a condition to the DeleteView.
+    /// Closure receives clone of the view (without when conditions) and can change
+    /// delete mode, lookup and other settings.
+    pub fn when<F, T>(
+        mut self,
+        _when: impl quickapi_when::When<S, T> + Clone + Sync + 'static,
+        _f: F,
+    ) -> Result<Self, crate::Error>
+    where
+        F: Fn(DeleteView<E, S>) -> Result<DeleteView<E, S>, crate::Error>,
+        T: Sync + Send + 'static,
+        <E as EntityTrait>::Model: IntoActiveModel<E::ActiveModel> + serde::Serialize + Sync,
+        <E as EntityTrait>::ActiveModel: Send,
+    {
+        let mut clone = self.clone();
+        clone.when = Default::default();
+        let _result = _f(clone)?;
+        self.when.add_when(_when, _result);
+        Ok(self)
+    }
+}
+
+/// DeleteView implementation of the request pipeline.
+impl<E, S> DeleteView<E, S>
+where
+    E: sea_orm::EntityTrait,
+    S: Clone + Send + Sync + 'static,
+    <E as EntityTrait>::Model: IntoActiveModel<E::ActiveModel> + serde::Serialize + Sync,
+    <E as EntityTrait>::ActiveModel: Send,
+{
+    /// handle runs the view pipeline on given connection (database or transaction).
+    async fn handle<C>(
+        &self,
+        conn: &C,
+        _parts: &mut Parts,
+        _state: &S,
+        _body: &bytes::Bytes,
+    ) -> Result<quickapi_http::response::Response, quickapi_view::Error>
+    where
+        C: ConnectionTrait + TransactionTrait,
+    {
+        let mut parts = _parts.clone();
+
+        // check permissions before the query runs
+        self.permissions.has_permission(&mut parts, _state).await?;
+
+        // restrict rows by scopes before lookup
+        let query = self
+            .scopes
+            .scope_select(&mut parts, _state, E::find())
+            .await?;
+
+        // prepare lookup
+        let mut query = self
+            .lookup
+            .lookup(&mut parts, _state, query)
+            .await
+            .map_err(|e| quickapi_view::Error::InternalError(Box::new(e)))?;
+
+        // already soft deleted objects are not found (in any mode)
+        let soft_delete = match self.mode {
+            super::DeleteMode::Soft { column } => Some(quickapi_filter::SoftDelete::new(column)),
+            _ => self.soft_delete,
+        };
+        if let Some(soft_delete) = soft_delete {
+            query = soft_delete.exclude_deleted(query)?;
+        }
+
+        // find objects to be deleted
+        let objects = match self.mode {
+            super::DeleteMode::Single | super::DeleteMode::Soft { .. } => {
+                query.one(conn).await?.into_iter().collect()
+            }
+            super::DeleteMode::Multiple => query.all(conn).await?,
+        };
+
+        if objects.is_empty() {
+            return Ok(quickapi_http::response::Response::new(json!({
+                "error": "Not Found",
+                "message": "The requested resource was not found."
+            }))
+            .with_status(axum::http::StatusCode::NOT_FOUND));
+        }
+
+        // check object permissions for all objects before anything is deleted
+        for object in &objects {
+            self.permissions
+                .has_object_permission(&mut parts, _state, object)
+                .await?;
+        }
+
+        // object must not be modified since client has read it (same representation as DetailView)
+        if parts.headers.contains_key(axum::http::header::IF_MATCH) {
+            for object in &objects {
+                let links = self.links.render(object);
+                let mut serialized = self
+                    .output
+                    .serialize_json(object.clone())
+                    .map_err(|e| quickapi_view::Error::InternalError(Box::new(e)))?;
+                Links::<E>::attach(&mut serialized, links);
+                self.fields.strip_output(&mut serialized);
+                let etag = self
+                    .etag
+                    .compute::<E>(object, &serialized)
+                    .map_err(|e| quickapi_view::Error::InternalError(Box::new(e)))?;
+                if !quickapi_http::etag::if_match(&parts.headers, &etag) {
+                    return Ok(quickapi_http::etag::precondition_failed());
+                }
+            }
+        }
+
+        // versioned rows are deleted only when they were not modified since they were read
+        let version = self
+            .etag
+            .version_column::<E>()
+            .map_err(|e| quickapi_view::Error::InternalError(Box::new(e)))?;
+
+        for object in objects {
+            let condition = match version {
+                Some(version) => quickapi_model::version_condition::<E>(&object, version),
+                None => quickapi_model::primary_key_condition::<E>(&object),
+            };
+            let rows_affected = match self.mode {
+                super::DeleteMode::Soft { column } => {
+                    let soft_delete = quickapi_filter::SoftDelete::new(column);
+                    let mut update = E::update_many().col_expr(
+                        soft_delete.resolve_column::<E>()?,
+                        soft_delete.deleted_value::<E>()?,
+                    );
+                    if let Some(version) = version {
+                        update = update.col_expr(version, Expr::col(version).add(1));
+                    }
+                    update.filter(condition).exec(conn).await?.rows_affected
+                }
+                _ => {
+                    E::delete_many()
+                        .filter(condition)
+                        .exec(conn)
+                        .await?
+                        .rows_affected
+                }
+            };
+            if version.is_some() && rows_affected == 0 {
+                return Ok(quickapi_http::etag::precondition_failed());
+            }
+        }
+
+        Ok(quickapi_http::response::Response::default()
+            .with_status(axum::http::StatusCode::NO_CONTENT))
+    }
 }
 
 /// Implement the ViewTrait for DeleteView
@@ -104,6 +361,8 @@ impl<E, S> ViewTrait<S> for DeleteView<E, S>
 where
     E: sea_orm::EntityTrait,
     S: Clone + Send + Sync + 'static,
+    <E as EntityTrait>::Model: IntoActiveModel<E::ActiveModel> + serde::Serialize + Sync,
+    <E as EntityTrait>::ActiveModel: Send,
 {
     async fn handle_view(
         &self,
@@ -111,10 +370,22 @@ where
         _state: &S,
         _body: &bytes::Bytes,
     ) -> Result<quickapi_http::response::Response, quickapi_view::Error> {
-        Err(quickapi_view::Error::ImproperlyConfigured(
-            "nope".to_owned(),
-        ))
+        if !self.transaction {
+            return self.handle(&self.db, _parts, _state, _body).await;
+        }
+
+        // run the whole pipeline in one transaction, rolled back on any error
+        let transaction = crate::view::transaction::begin(&self.db, _parts).await?;
+        let response = match self.handle(&*transaction, _parts, _state, _body).await {
+            Ok(response) => response,
+            Err(err) => {
+                crate::view::transaction::discard(transaction, _parts);
+                return Err(err);
+            }
+        };
+        crate::view::transaction::finish(transaction, _parts, response).await
     }
+
     /// get_when_views returns a list of when views for the DeleteView.
     async fn get_when_views<'a>(
         &'a self,
@@ -124,7 +395,7 @@ where
         self.when
             .get_views(_parts, _state)
             .await
-            .map_err(|e| Error::InternalError(Box::new(e)))
+            .map_err(Into::into)
     }
 
     fn has_fallback(&self) -> bool {
@@ -137,6 +408,8 @@ impl<E, S> quickapi_view::RouterExt<S> for DeleteView<E, S>
 where
     E: sea_orm::EntityTrait,
     S: Clone + Send + Sync + 'static,
+    <E as EntityTrait>::Model: IntoActiveModel<E::ActiveModel> + serde::Serialize + Sync,
+    <E as EntityTrait>::ActiveModel: Send,
 {
     fn register_router_with_prefix(
         &self,
@@ -149,6 +422,160 @@ where
         debug!(method = self.method.to_string(), path = &path, "API delete",);
 
         // Register the ListView with the axum router
-        Ok(router.route(&path, on(mf, Handler::new(self.clone()))))
+        self.registration.register(&self.method, &path)?;
+        let handler = Handler::new(self.clone()).with_db(&self.db);
+        Ok(router.route(&path, on(mf, handler)))
+    }
+
+    /// apply_scopes adds scopes registered for the entity (also to when views)
+    fn apply_scopes(&mut self, scopes: &quickapi_filter::ScopeRegistry<S>) {
+        self.scopes.extend(&scopes.get::<E>());
+        self.when.apply_scopes(scopes);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::view::delete::DeleteMode;
+    use axum::http::StatusCode;
+    use axum::http::request::Parts;
+    use axum_test::TestServer;
+    use quickapi_view::RouterExt;
+    use sea_orm::entity::prelude::*;
+    use sea_orm::{Database, Schema};
+
+    #[derive(
+        Clone, Debug, PartialEq, Eq, DeriveEntityModel, serde::Deserialize, serde::Serialize,
+    )]
+    #[sea_orm(table_name = "user")]
+    pub struct Model {
+        #[sea_orm(primary_key)]
+        pub id: i32,
+        pub username: String,
+        pub deleted: bool,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+
+    // NoRoot denies deleting user named root
+    #[derive(Clone)]
+    struct NoRoot;
+
+    #[async_trait::async_trait]
+    impl quickapi_permission::Permission<Entity, ()> for NoRoot {
+        async fn has_permission(
+            &self,
+            _parts: &mut Parts,
+            _state: &(),
+        ) -> Result<(), quickapi_permission::Error> {
+            Ok(())
+        }
+
+        async fn has_object_permission(
+            &self,
+            _parts: &mut Parts,
+            _state: &(),
+            model: &Model,
+        ) -> Result<(), quickapi_permission::Error> {
+            match model.username.as_str() {
+                "root" => Err(quickapi_permission::Error::Denied(
+                    "root is reserved".to_owned(),
+                )),
+                _ => Ok(()),
+            }
+        }
+    }
+
+    async fn database() -> DatabaseConnection {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        let backend = db.get_database_backend();
+        db.execute(backend.build(&Schema::new(backend).create_table_from_entity(Entity)))
+            .await
+            .unwrap();
+        db.execute_unprepared(
+            r#"INSERT INTO "user" ("username", "deleted") VALUES ('root', false), ('joe', false)"#,
+        )
+        .await
+        .unwrap();
+        db
+    }
+
+    #[tokio::test]
+    async fn test_delete() {
+        let db = database().await;
+        let router = crate::new::<()>(db.clone())
+            .delete::<Entity>("/user/{id}")
+            .unwrap()
+            .with_permission(NoRoot)
+            .register_router(axum::Router::new())
+            .unwrap();
+        let server = TestServer::new(router).unwrap();
+
+        // object permission denied, nothing is deleted
+        server
+            .delete("/user/1")
+            .await
+            .assert_status(StatusCode::FORBIDDEN);
+        assert!(Entity::find_by_id(1).one(&db).await.unwrap().is_some());
+
+        server
+            .delete("/user/2")
+            .await
+            .assert_status(StatusCode::NO_CONTENT);
+        assert!(Entity::find_by_id(2).one(&db).await.unwrap().is_none());
+
+        // already deleted object is not found
+        server
+            .delete("/user/2")
+            .await
+            .assert_status(StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_soft_delete() {
+        let db = database().await;
+        let router = crate::new::<()>(db.clone())
+            .delete::<Entity>("/user/{id}")
+            .unwrap()
+            .with_mode(DeleteMode::Soft { column: "deleted" })
+            .register_router(axum::Router::new())
+            .unwrap();
+        let server = TestServer::new(router).unwrap();
+
+        server
+            .delete("/user/2")
+            .await
+            .assert_status(StatusCode::NO_CONTENT);
+        let object = Entity::find_by_id(2).one(&db).await.unwrap().unwrap();
+        assert!(object.deleted);
+
+        // soft deleted object is not found
+        server
+            .delete("/user/2")
+            .await
+            .assert_status(StatusCode::NOT_FOUND);
+
+        // soft deleted object is not found in hard delete mode either
+        let router = crate::new::<()>(db.clone())
+            .with_soft_delete::<Entity>("deleted")
+            .delete::<Entity>("/user/{id}")
+            .unwrap()
+            .with_mode(DeleteMode::Single)
+            .register_router(axum::Router::new())
+            .unwrap();
+        let server = TestServer::new(router).unwrap();
+        server
+            .delete("/user/2")
+            .await
+            .assert_status(StatusCode::NOT_FOUND);
+        assert!(Entity::find_by_id(2).one(&db).await.unwrap().is_some());
+        server
+            .delete("/user/1")
+            .await
+            .assert_status(StatusCode::NO_CONTENT);
+        assert!(Entity::find_by_id(1).one(&db).await.unwrap().is_none());
     }
 }
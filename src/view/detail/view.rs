@@ -26,14 +26,20 @@
 use crate::Error;
 use crate::view::detail::DetailViewTrait;
 use crate::view::handler::Handler;
+use crate::view::jsonapi::{JSON_API_CONTENT_TYPE, JsonApi};
+use crate::view::links::{Link, Links};
+use crate::view::registry::{Registration, Routes};
 use axum::Router;
 use axum::http::Method;
 use axum::http::request::Parts;
 use axum::routing::on;
-use quickapi_filter::SelectFilterErased;
+use quickapi_filter::{Annotation, Annotations, Scope, SelectFilterErased};
 use quickapi_http::ModelSerializerJson;
+use quickapi_http::etag::ETag;
 use quickapi_http::response::{Key, Response};
 use quickapi_lookup::Lookup;
+use quickapi_model::Fields;
+use quickapi_permission::Permission;
 use quickapi_view::{ViewTrait, as_method_filter};
 use sea_orm::{DatabaseConnection, EntityTrait};
 use serde_json::json;
@@ -53,10 +59,19 @@ where
     db: DatabaseConnection,
     path: String,
     method: Method,
+    registration: Registration,
     when: quickapi_when::WhenViews<S>,
     lookup: Arc<dyn Lookup<E, S>>,
     filters: quickapi_filter::SelectFilters<E, S>,
+    scopes: quickapi_filter::Scopes<E, S>,
+    soft_delete: Option<quickapi_filter::SoftDelete>,
+    etag: ETag,
+    permissions: quickapi_permission::Permissions<E, S>,
     ser: ModelSerializerJson<O>,
+    fields: Fields<E>,
+    annotations: Annotations,
+    json_api: Option<JsonApi>,
+    links: Links<E>,
     wrap_json_key: Option<Key>,
     fallback: bool,
     _phantom: PhantomData<(E, S, O)>,
@@ -80,25 +95,47 @@ where
             db,
             path: path.as_ref().to_string(),
             method,
+            registration: Registration::new::<E>("detail"),
             when: Default::default(),
             lookup: Arc::new(lookup),
             filters: quickapi_filter::SelectFilters::new(),
+            scopes: quickapi_filter::Scopes::new(),
+            soft_delete: None,
+            etag: Default::default(),
+            permissions: Default::default(),
             ser: ModelSerializerJson::<O>::new(),
+            fields: Default::default(),
+            annotations: Default::default(),
+            json_api: None,
+            links: Default::default(),
             wrap_json_key: Some(DEFAULT_JSON_KEY.into()),
             fallback: false,
             _phantom: PhantomData,
         }
     }
 
+    /// with_name sets name of the route in QuickApi routes (`{table}-{kind}` by default, e.g. `user-detail`).
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.registration = self.registration.with_name(name);
+        self
+    }
+
+    /// with_routes sets registry of routes the view is recorded into when registered.
+    pub(crate) fn with_routes(mut self, routes: Routes) -> Self {
+        self.registration = self.registration.with_routes(routes);
+        self
+    }
+
     /// when adds a condition to the DetailView.
     pub fn when<F, T, Ser>(
         mut self,
-        _when: impl quickapi_when::When<S, T> + Clone + Send + Sync + 'static,
+        _when: impl quickapi_when::When<S, T> + Clone + Sync + 'static,
         _f: F,
     ) -> Result<Self, Error>
     where
         Ser: Clone + serde::Serialize + Send + Sync + 'static + From<<E as EntityTrait>::Model>,
         F: Fn(DetailView<E, S, O>) -> Result<DetailView<E, S, Ser>, Error>,
+        <E as EntityTrait>::Model: Sync,
         T: Sync + Send + 'static,
     {
         let mut clone = self.clone();
@@ -127,6 +164,38 @@ where
         self
     }
 
+    /// with_scope adds a row-level scope to the DetailView.
+    /// Scopes are applied before filters and lookup, so objects out of scope are not found.
+    pub fn with_scope(mut self, scope: impl Scope<E, S> + 'static) -> Self {
+        self.scopes.push(scope);
+        self
+    }
+
+    /// with_soft_delete marks entity as soft deletable by column, soft deleted rows are hidden.
+    pub fn with_soft_delete(mut self, column: &'static str) -> Self {
+        self.soft_delete = Some(quickapi_filter::SoftDelete::new(column));
+        self
+    }
+
+    /// with_deleted includes soft deleted rows in the DetailView (usually in when condition for admins).
+    pub fn with_deleted(mut self) -> Self {
+        self.soft_delete = None;
+        self
+    }
+
+    /// with_etag_column sets version column used for ETag (by default ETag is hash of serialized object).
+    pub fn with_etag_column(mut self, column: &'static str) -> Self {
+        self.etag = ETag::Column(column);
+        self
+    }
+
+    /// with_permission adds a permission to the DetailView.
+    /// Permission is checked before the query runs, object permission after the lookup.
+    pub fn with_permission(mut self, permission: impl Permission<E, S> + 'static) -> Self {
+        self.permissions.push(permission);
+        self
+    }
+
     /// with_serializer creates a new DetailView with a specified serializer.
     pub fn with_serializer<Ser>(self) -> DetailView<E, S, Ser>
     where
@@ -136,16 +205,60 @@ where
             db: self.db,
             path: self.path,
             method: self.method,
+            registration: self.registration,
             _phantom: PhantomData,
             when: self.when,
             lookup: self.lookup,
             filters: self.filters,
+            scopes: self.scopes,
+            soft_delete: self.soft_delete,
+            etag: self.etag,
+            permissions: self.permissions,
             ser: ModelSerializerJson::<Ser>::new(),
+            fields: self.fields,
+            annotations: self.annotations,
+            json_api: self.json_api,
+            links: self.links,
             wrap_json_key: self.wrap_json_key,
             fallback: self.fallback,
         }
     }
 
+    /// with_write_only marks columns as write only, they are never returned in response.
+    pub fn with_write_only(mut self, columns: impl IntoIterator<Item = E::Column>) -> Self {
+        self.fields = self.fields.with_write_only(columns);
+        self
+    }
+
+    /// with_annotation adds named SQL expression (or subquery) to the query.
+    /// Its value is returned alongside serialized object, filters can use it by name.
+    pub fn with_annotation(mut self, annotation: Annotation) -> Self {
+        self.annotations.push(annotation);
+        self
+    }
+
+    /// with_link adds named link (e.g. self, related resource) to serialized object as HAL `_links`,
+    /// path parameters of the link are resolved from model columns (e.g. `/api/user/{id}`).
+    pub fn with_link(
+        mut self,
+        name: impl Into<String>,
+        link: impl Into<Link>,
+    ) -> Result<Self, Error> {
+        self.links.push(name, link)?;
+        Ok(self)
+    }
+
+    /// link returns link to this view (path template).
+    pub fn link(&self) -> Link {
+        Link::new(self.path.clone())
+    }
+
+    /// with_json_api renders object as JSON:API document (wrap result key is not used).
+    pub fn with_json_api(mut self, json_api: JsonApi) -> Self {
+        self.json_api = Some(json_api);
+        self
+    }
+
     /// with_fallback sets the fallback behavior for the DetailView.
     pub fn with_fallback(mut self, fallback: bool) -> Self {
         self.fallback = fallback;
@@ -159,6 +272,7 @@ where
     E: EntityTrait,
     S: Clone + Send + Sync + 'static,
     O: serde::Serialize + From<<E as EntityTrait>::Model> + Clone + Send + Sync + 'static,
+    <E as EntityTrait>::Model: Sync,
 {
 }
 
@@ -173,11 +287,20 @@ where
             db: self.db.clone(),
             path: self.path.clone(),
             method: self.method.clone(),
+            registration: self.registration.clone(),
             _phantom: PhantomData,
             when: self.when.clone(),
             lookup: self.lookup.clone(),
             filters: self.filters.clone(), // TODO: Verify if this is correct
+            scopes: self.scopes.clone(),
+            soft_delete: self.soft_delete,
+            etag: self.etag,
+            permissions: self.permissions.clone(),
             ser: self.ser.clone(),
+            fields: self.fields.clone(),
+            annotations: self.annotations.clone(),
+            json_api: self.json_api.clone(),
+            links: self.links.clone(),
             wrap_json_key: self.wrap_json_key.clone(),
             fallback: self.fallback,
         }
@@ -190,6 +313,7 @@ where
     E: EntityTrait,
     S: Clone + Send + Sync + 'static,
     O: serde::Serialize + From<<E as EntityTrait>::Model> + Clone + Send + Sync + 'static,
+    <E as EntityTrait>::Model: Sync,
 {
     /// register_router_with_prefix method to register the DetailView with an axum router.
     fn register_router_with_prefix(
@@ -203,7 +327,15 @@ where
         debug!(method = self.method.to_string(), path = &path, "API detail",);
 
         // Register the ListView with the axum router
-        Ok(router.route(&path, on(mf, Handler::new(self.clone()))))
+        self.registration.register(&self.method, &path)?;
+        let handler = Handler::new(self.clone()).with_db(&self.db);
+        Ok(router.route(&path, on(mf, handler)))
+    }
+
+    /// apply_scopes adds scopes registered for the entity (also to when views)
+    fn apply_scopes(&mut self, scopes: &quickapi_filter::ScopeRegistry<S>) {
+        self.scopes.extend(&scopes.get::<E>());
+        self.when.apply_scopes(scopes);
     }
 }
 
@@ -214,6 +346,7 @@ where
     E: EntityTrait,
     S: Clone + Send + Sync + 'static,
     O: serde::Serialize + From<<E as EntityTrait>::Model> + Clone + Send + Sync + 'static,
+    <E as EntityTrait>::Model: Sync,
 {
     async fn handle_view(
         &self,
@@ -221,42 +354,89 @@ where
         _state: &S,
         _body: &bytes::Bytes,
     ) -> Result<Response, quickapi_view::Error> {
+        // annotations are available to filters by name
+        _parts.extensions.insert(self.annotations.clone());
         let mut parts = _parts.clone();
 
+        // check permissions before the query runs
+        self.permissions.has_permission(&mut parts, _state).await?;
+
+        // restrict rows by scopes before any user filters
+        let mut query = self.scopes.scope_select(_parts, _state, E::find()).await?;
+
+        // hide soft deleted rows (unless deleted are included)
+        if let Some(soft_delete) = &self.soft_delete {
+            query = soft_delete.exclude_deleted(query)?;
+        }
+
         let query = self
             .filters
-            .filter_select_boxed(_parts, &_state, E::find())
+            .filter_select_boxed(_parts, _state, query)
             .await
             .map_err(|e| quickapi_view::Error::InternalError(Box::new(e)))?;
 
         // prepare lookup
         let lookup = self.lookup.clone();
         let query = lookup
-            .lookup(&mut parts, &_state, query.clone())
+            .lookup(&mut parts, _state, query.clone())
             .await
             .map_err(|e| quickapi_view::Error::InternalError(Box::new(e)))?;
 
-        // now perform the query
-        let result = query
-            .clone()
-            .one(&self.db)
-            .await
-            .map_err(|e| quickapi_view::Error::InternalError(Box::new(e)));
+        // now perform the query (with annotation values)
+        let result = if self.annotations.is_empty() {
+            query
+                .clone()
+                .one(&self.db)
+                .await
+                .map(|object| object.map(|object| (object, Default::default())))
+        } else {
+            self.annotations.one(&self.db, query.clone()).await
+        }
+        .map_err(|e| quickapi_view::Error::InternalError(Box::new(e)));
 
         let _object = result?;
 
         // check for result
-        let Some(object) = _object else {
+        let Some((object, values)) = _object else {
             return Ok(Response::new(json!({
                 "error": "Not Found",
                 "message": "The requested resource was not found."
-            })));
+            }))
+            .with_status(axum::http::StatusCode::NOT_FOUND));
         };
 
-        let serialized = self
+        // check object permissions on the looked up object
+        self.permissions
+            .has_object_permission(&mut parts, _state, &object)
+            .await?;
+
+        let mut serialized = self
             .ser
             .serialize_json(object.clone())
             .map_err(|e| quickapi_view::Error::InternalError(Box::new(e)))?;
+        Annotations::merge(&mut serialized, values);
+        Links::<E>::attach(&mut serialized, self.links.render(&object));
+        self.fields.strip_output(&mut serialized);
+
+        // entity tag from serialized object (or version column)
+        let etag = self
+            .etag
+            .compute::<E>(&object, &serialized)
+            .map_err(|e| quickapi_view::Error::InternalError(Box::new(e)))?;
+
+        // client already has current representation
+        if quickapi_http::etag::if_none_match(&_parts.headers, &etag) {
+            return Ok(quickapi_http::etag::not_modified(&etag));
+        }
+
+        // JSON:API document with link to itself
+        if let Some(json_api) = &self.json_api {
+            let mut document = json_api.document(serialized);
+            document["links"] = json!({"self": _parts.uri.to_string()});
+            return Ok(Response::new(document)
+                .with_header(axum::http::header::CONTENT_TYPE, JSON_API_CONTENT_TYPE)
+                .with_header(axum::http::header::ETAG, &etag));
+        }
 
         let object = match &self.wrap_json_key {
             Some(key) => serde_json::Value::Object(serde_json::Map::from_iter(vec![(
@@ -266,7 +446,7 @@ where
             None => serialized,
         };
 
-        Ok(Response::new(object))
+        Ok(Response::new(object).with_header(axum::http::header::ETAG, &etag))
     }
 
     /// get_when_views returns a vector of when views for the DetailView.
@@ -278,7 +458,7 @@ where
         self.when
             .get_views(_parts, _state)
             .await
-            .map_err(|e| quickapi_view::Error::InternalError(Box::new(e)))
+            .map_err(Into::into)
     }
 
     fn has_fallback(&self) -> bool {
@@ -292,6 +472,7 @@ where
     E: EntityTrait,
     S: Clone + Send + Sync + 'static,
     O: serde::Serialize + From<<E as EntityTrait>::Model> + Clone + Send + Sync + 'static,
+    <E as EntityTrait>::Model: Sync,
 {
     /// wrap_result_key method to set a custom key for the JSON response
     fn wrap_result_key(mut self, key: impl Into<Key>) -> Self {
@@ -310,3 +491,78 @@ where
         self.wrap_json_key.clone()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use axum::http::StatusCode;
+    use axum::http::header::{ETAG, IF_NONE_MATCH};
+    use axum_test::TestServer;
+    use quickapi_view::RouterExt;
+    use sea_orm::entity::prelude::*;
+    use sea_orm::{Database, Schema};
+    use serde_json::json;
+
+    #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, serde::Serialize)]
+    #[sea_orm(table_name = "user")]
+    pub struct Model {
+        #[sea_orm(primary_key)]
+        pub id: i32,
+        pub username: String,
+        pub deleted: bool,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+
+    #[tokio::test]
+    async fn test_detail() {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        let backend = db.get_database_backend();
+        db.execute(backend.build(&Schema::new(backend).create_table_from_entity(Entity)))
+            .await
+            .unwrap();
+        db.execute_unprepared(
+            r#"INSERT INTO "user" ("username", "deleted") VALUES ('joe', false), ('anna', true)"#,
+        )
+        .await
+        .unwrap();
+        let router = crate::new::<()>(db.clone())
+            .with_soft_delete::<Entity>("deleted")
+            .detail::<Entity>("/user/{id}", "id")
+            .unwrap()
+            .register_router(axum::Router::new())
+            .unwrap();
+        let server = TestServer::new(router).unwrap();
+
+        let response = server.get("/user/1").await;
+        response.assert_status_ok();
+        response.assert_json(&json!({"object": {"id": 1, "username": "joe", "deleted": false}}));
+        let etag = response.header(ETAG);
+
+        // client already has current representation
+        server
+            .get("/user/1")
+            .add_header(IF_NONE_MATCH, etag.clone())
+            .await
+            .assert_status(StatusCode::NOT_MODIFIED);
+
+        // representation has changed
+        db.execute_unprepared(r#"UPDATE "user" SET "username" = 'joey' WHERE "id" = 1"#)
+            .await
+            .unwrap();
+        let response = server
+            .get("/user/1")
+            .add_header(IF_NONE_MATCH, etag.clone())
+            .await;
+        response.assert_status_ok();
+        assert_ne!(response.header(ETAG), etag);
+
+        // soft deleted object is hidden
+        server
+            .get("/user/2")
+            .await
+            .assert_status(StatusCode::NOT_FOUND);
+    }
+}
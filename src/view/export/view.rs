@@ -0,0 +1,524 @@
+/*
+ *  The MIT License (MIT)
+ *
+ *  Copyright (c) 2024-2025, Peter Vrba
+ *
+ *  Permission is hereby granted, free of charge, to any person obtaining a copy
+ *  of this software and associated documentation files (the "Software"), to deal
+ *  in the Software without restriction, including without limitation the rights
+ *  to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ *  copies of the Software, and to permit persons to whom the Software is
+ *  furnished to do so, subject to the following conditions:
+ *
+ *  The above copyright notice and this permission notice shall be included in
+ *  all copies or substantial portions of the Software.
+ *
+ *  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ *  IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ *  FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ *  AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ *  LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ *  OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+ *  THE SOFTWARE.
+ *
+ */
+
+use crate::view::handler::Handler;
+use crate::view::registry::{Registration, Routes};
+use axum::Router;
+use axum::http::Method;
+use axum::http::request::Parts;
+use axum::routing::on;
+use futures_util::{Stream, StreamExt};
+use quickapi_filter::{Scope, SelectFilter, SelectFilterErased};
+use quickapi_http::ModelSerializerJson;
+use quickapi_http::response::Response;
+use quickapi_permission::Permission;
+use quickapi_view::{Error, RouterExt, ViewTrait};
+use sea_orm::{DatabaseConnection, EntityTrait, Iden, Iterable, Select};
+use std::marker::PhantomData;
+use tracing::debug;
+
+const CSV_CONTENT_TYPE: &str = "text/csv; charset=utf-8";
+
+/// ExportView is a view that streams entities matching the filters as CSV.
+/// It uses the same filters, scopes and when conditions as ListView. Columns are taken from
+/// serialized objects (by serializer), by default all entity columns in their order. With custom serializer
+/// columns are fields of the first serialized object (sorted by name), unless set by `with_columns`.
+#[derive(Clone)]
+pub struct ExportView<E, S, O = <E as EntityTrait>::Model>
+where
+    E: EntityTrait,
+    S: Clone + Send + Sync + 'static,
+    O: serde::Serialize + Clone + Send + Sync + 'static,
+{
+    db: DatabaseConnection,
+    path: String,
+    method: Method,
+    registration: Registration,
+    columns: Vec<&'static str>,
+    custom_serializer: bool,
+    headers: Vec<(&'static str, String)>,
+    filename: Option<String>,
+    filters: quickapi_filter::SelectFilters<E, S>,
+    scopes: quickapi_filter::Scopes<E, S>,
+    soft_delete: Option<quickapi_filter::SoftDelete>,
+    permissions: quickapi_permission::Permissions<E, S>,
+    when: quickapi_when::WhenViews<S>,
+    fallback: bool,
+    ser: ModelSerializerJson<O>,
+    _phantom_data: PhantomData<(E, S)>,
+}
+
+impl<E, S, O> ExportView<E, S, O>
+where
+    E: EntityTrait,
+    S: Clone + Send + Sync + 'static,
+    O: serde::Serialize + Clone + Send + Sync + 'static,
+{
+    /// new creates a new ExportView instance.
+    pub(crate) fn new(db: DatabaseConnection, path: impl Into<String>, method: Method) -> Self {
+        Self {
+            db,
+            path: path.into(),
+            method,
+            registration: Registration::new::<E>("export"),
+            columns: Vec::new(),
+            custom_serializer: false,
+            headers: Vec::new(),
+            filename: None,
+            filters: quickapi_filter::SelectFilters::new(),
+            scopes: quickapi_filter::Scopes::new(),
+            soft_delete: None,
+            permissions: Default::default(),
+            when: Default::default(),
+            fallback: false,
+            ser: ModelSerializerJson::<O>::new(),
+            _phantom_data: PhantomData,
+        }
+    }
+
+    /// with_name sets name of the route in QuickApi routes (`{table}-{kind}` by default, e.g. `user-export`).
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.registration = self.registration.with_name(name);
+        self
+    }
+
+    /// with_routes sets registry of routes the view is recorded into when registered.
+    pub(crate) fn with_routes(mut self, routes: Routes) -> Self {
+        self.registration = self.registration.with_routes(routes);
+        self
+    }
+
+    /// with_serializer sets custom serializer, its fields are exported.
+    pub fn with_serializer<Ser>(self) -> ExportView<E, S, Ser>
+    where
+        Ser: serde::Serialize + Clone + Send + Sync + 'static,
+        <E as EntityTrait>::Model: Into<Ser>,
+    {
+        ExportView {
+            db: self.db,
+            path: self.path,
+            method: self.method,
+            registration: self.registration,
+            columns: self.columns,
+            custom_serializer: true,
+            headers: self.headers,
+            filename: self.filename,
+            filters: self.filters,
+            scopes: self.scopes,
+            soft_delete: self.soft_delete,
+            permissions: self.permissions,
+            when: self.when,
+            fallback: self.fallback,
+            ser: ModelSerializerJson::<Ser>::new(),
+            _phantom_data: PhantomData,
+        }
+    }
+
+    /// with_columns sets exported fields of serialized objects and their order.
+    pub fn with_columns(mut self, columns: impl IntoIterator<Item = &'static str>) -> Self {
+        self.columns = columns.into_iter().collect();
+        self
+    }
+
+    /// with_header sets header of the column in CSV (by default it's the column name).
+    pub fn with_header(mut self, column: &'static str, header: impl Into<String>) -> Self {
+        self.headers.retain(|(c, _)| *c != column);
+        self.headers.push((column, header.into()));
+        self
+    }
+
+    /// with_filename sets name of the downloaded file (by default it's table name with csv extension).
+    pub fn with_filename(mut self, filename: impl Into<String>) -> Self {
+        self.filename = Some(filename.into());
+        self
+    }
+
+    /// with_filter adds a filter to the ExportView (same filters as in ListView).
+    pub fn with_filter<F, T>(mut self, f: F) -> Self
+    where
+        F: SelectFilter<E, S, T> + Clone + Send + Sync + 'static,
+        T: Sync + Send + 'static,
+    {
+        self.filters.push(f);
+        self
+    }
+
+    /// with_scope adds a row-level scope that is applied before filters
+    pub fn with_scope(mut self, scope: impl Scope<E, S> + 'static) -> Self {
+        self.scopes.push(scope);
+        self
+    }
+
+    /// with_soft_delete marks entity as soft deletable by column, soft deleted rows are not exported.
+    pub fn with_soft_delete(mut self, column: &'static str) -> Self {
+        self.soft_delete = Some(quickapi_filter::SoftDelete::new(column));
+        self
+    }
+
+    /// with_deleted exports also soft deleted rows (usually in when condition for admins).
+    pub fn with_deleted(mut self) -> Self {
+        self.soft_delete = None;
+        self
+    }
+
+    /// with_permission adds a permission that is checked before the query runs
+    pub fn with_permission(mut self, permission: impl Permission<E, S> + 'static) -> Self {
+        self.permissions.push(permission);
+        self
+    }
+
+    /// with_fallback sets a fallback ExportView that will be used if when conditions are not met.
+    pub fn with_fallback(mut self, fallback: bool) -> Self {
+        self.fallback = fallback;
+        self
+    }
+
+    /// when adds a condition to the ExportView.
+    pub fn when<F, T, Ser>(
+        mut self,
+        _when: impl quickapi_when::When<S, T> + Clone + Sync + 'static,
+        _f: F,
+    ) -> Result<Self, crate::Error>
+    where
+        Ser: serde::Serialize + From<<E as EntityTrait>::Model> + Clone + Send + Sync + 'static,
+        F: Fn(ExportView<E, S, O>) -> Result<ExportView<E, S, Ser>, crate::Error>,
+        T: Sync + Send + 'static,
+    {
+        let mut clone = self.clone();
+        clone.when = Default::default();
+        let _result = _f(clone)?;
+        self.when.add_when(_when, _result);
+        Ok(self)
+    }
+
+    /// columns returns exported columns, None means columns are taken from the first serialized object.
+    fn columns(&self) -> Option<Vec<String>> {
+        match (self.columns.is_empty(), self.custom_serializer) {
+            (false, _) => Some(self.columns.iter().map(|c| (*c).to_owned()).collect()),
+            (true, false) => Some(E::Column::iter().map(|c| c.to_string()).collect()),
+            (true, true) => None,
+        }
+    }
+}
+
+/// Headers maps exported columns to their headers in CSV (by default it's the column name).
+#[derive(Clone)]
+struct Headers(Vec<(&'static str, String)>);
+
+impl Headers {
+    /// header returns header of the column.
+    fn header<'a>(&'a self, column: &'a str) -> &'a str {
+        self.0
+            .iter()
+            .find(|(c, _)| *c == column)
+            .map(|(_, h)| h.as_str())
+            .unwrap_or(column)
+    }
+}
+
+/// csv_row writes one CSV record.
+fn csv_row<I, T>(fields: I) -> Result<bytes::Bytes, axum::BoxError>
+where
+    I: IntoIterator<Item = T>,
+    T: AsRef<[u8]>,
+{
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record(fields)?;
+    Ok(writer.into_inner().map_err(|e| e.into_error())?.into())
+}
+
+/// csv_cell returns CSV representation of JSON value (nested values are written as JSON).
+/// Text starting with `=`, `+`, `-` or `@` is prefixed with `'`, so spreadsheets don't evaluate it as formula.
+fn csv_cell(value: Option<&serde_json::Value>) -> String {
+    match value {
+        None | Some(serde_json::Value::Null) => String::new(),
+        Some(serde_json::Value::String(value)) if value.starts_with(['=', '+', '-', '@']) => {
+            format!("'{value}")
+        }
+        Some(serde_json::Value::String(value)) => value.clone(),
+        Some(value) => value.to_string(),
+    }
+}
+
+/// csv_stream streams header and rows of the query as CSV.
+/// When columns are not given, they are taken from the first serialized object (no rows, no header).
+fn csv_stream<E, O>(
+    db: DatabaseConnection,
+    query: Select<E>,
+    ser: ModelSerializerJson<O>,
+    columns: Option<Vec<String>>,
+    headers: Headers,
+) -> impl Stream<Item = Result<bytes::Bytes, axum::BoxError>> + Send + 'static
+where
+    E: EntityTrait,
+    O: serde::Serialize + From<<E as EntityTrait>::Model> + Clone + Send + Sync + 'static,
+{
+    async_stream::try_stream! {
+        let mut columns = columns;
+        if let Some(columns) = &columns {
+            yield csv_row(columns.iter().map(|column| headers.header(column)))?;
+        }
+
+        let mut rows = query.stream(&db).await?;
+        while let Some(row) = rows.next().await {
+            let object = ser.serialize_json(row?)?;
+            let columns = match &mut columns {
+                Some(columns) => columns,
+                None => {
+                    let fields = object
+                        .as_object()
+                        .map(|fields| fields.keys().cloned().collect())
+                        .unwrap_or_default();
+                    let columns = columns.insert(fields);
+                    yield csv_row(columns.iter().map(|column| headers.header(column)))?;
+                    columns
+                }
+            };
+            yield csv_row(columns.iter().map(|column| csv_cell(object.get(column))))?;
+        }
+    }
+}
+
+/// Implementing RouterExt for ExportView to register the router
+impl<E, S, O> RouterExt<S> for ExportView<E, S, O>
+where
+    E: EntityTrait,
+    S: Clone + Send + Sync + 'static,
+    O: serde::Serialize + From<<E as EntityTrait>::Model> + Clone + Send + Sync + 'static,
+{
+    fn register_router_with_prefix(
+        &self,
+        router: Router<S>,
+        prefix: &str,
+    ) -> Result<Router<S>, Error> {
+        let mf = quickapi_view::as_method_filter(&self.method)?;
+        let path = format!("{}{}", prefix, self.path);
+
+        debug!(method = self.method.to_string(), path = &path, "API export",);
+
+        self.registration.register(&self.method, &path)?;
+        let handler = Handler::new(self.clone()).with_db(&self.db);
+        Ok(router.route(&path, on(mf, handler)))
+    }
+
+    /// apply_scopes adds scopes registered for the entity (also to when views)
+    fn apply_scopes(&mut self, scopes: &quickapi_filter::ScopeRegistry<S>) {
+        self.scopes.extend(&scopes.get::<E>());
+        self.when.apply_scopes(scopes);
+    }
+}
+
+/// Implementing ViewTrait for ExportView to handle view logic
+#[async_trait::async_trait]
+impl<E, S, O> ViewTrait<S> for ExportView<E, S, O>
+where
+    E: EntityTrait,
+    S: Clone + Send + Sync + 'static,
+    O: serde::Serialize + From<<E as EntityTrait>::Model> + Clone + Send + Sync + 'static,
+{
+    async fn handle_view(
+        &self,
+        _parts: &mut Parts,
+        _state: &S,
+        _body: &bytes::Bytes,
+    ) -> Result<Response, Error> {
+        // check permissions before the query runs
+        self.permissions.has_permission(_parts, _state).await?;
+
+        // restrict rows by scopes before any user filters
+        let mut query = self.scopes.scope_select(_parts, _state, E::find()).await?;
+
+        // soft deleted rows are not exported (unless deleted are included)
+        if let Some(soft_delete) = &self.soft_delete {
+            query = soft_delete.exclude_deleted(query)?;
+        }
+
+        let query = self
+            .filters
+            .filter_select_boxed(_parts, _state, query)
+            .await
+            .map_err(|e| Error::InternalError(Box::new(e)))?;
+
+        let filename = match &self.filename {
+            Some(filename) => filename.clone(),
+            None => format!("{}.csv", E::default().table_name()),
+        };
+
+        // rows are streamed, so errors in the middle of export only end the stream
+        let stream = csv_stream(
+            self.db.clone(),
+            query,
+            self.ser.clone(),
+            self.columns(),
+            Headers(self.headers.clone()),
+        );
+
+        Ok(
+            Response::with_body(CSV_CONTENT_TYPE, axum::body::Body::from_stream(stream))
+                .with_header(
+                    axum::http::header::CONTENT_DISPOSITION,
+                    &format!("attachment; filename=\"{}\"", filename.replace('"', "")),
+                ),
+        )
+    }
+
+    /// get_when_views returns a vector of views that should be executed when the ExportView is called.
+    async fn get_when_views<'a>(
+        &'a self,
+        _parts: &'a mut Parts,
+        _state: &'a S,
+    ) -> Result<Vec<&'a (dyn ViewTrait<S> + Send + Sync)>, Error> {
+        self.when
+            .get_views(_parts, _state)
+            .await
+            .map_err(Into::into)
+    }
+
+    /// has_fallback returns true if the ExportView has a fallback defined (if when does not matches).
+    fn has_fallback(&self) -> bool {
+        self.fallback
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum_test::TestServer;
+    use sea_orm::entity::prelude::*;
+    use sea_orm::{Database, Schema};
+
+    #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, serde::Serialize)]
+    #[sea_orm(table_name = "user")]
+    pub struct Model {
+        #[sea_orm(primary_key)]
+        pub id: i32,
+        pub tenant_id: i32,
+        pub username: String,
+        pub note: String,
+        pub deleted: bool,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+
+    #[derive(Clone)]
+    struct Tenant(i32);
+
+    #[derive(Clone, serde::Serialize)]
+    struct UserNote {
+        username: String,
+        note: String,
+    }
+
+    impl From<Model> for UserNote {
+        fn from(model: Model) -> Self {
+            UserNote {
+                username: model.username,
+                note: model.note,
+            }
+        }
+    }
+
+    async fn export<O>(view: ExportView<Entity, (), O>) -> String
+    where
+        O: serde::Serialize + From<Model> + Clone + Send + Sync + 'static,
+    {
+        let router = view
+            .register_router(Router::new())
+            .unwrap()
+            .layer(axum::Extension(Tenant(1)));
+        let response = TestServer::new(router).unwrap().get("/user").await;
+        response.assert_status_ok();
+        response.text()
+    }
+
+    #[tokio::test]
+    async fn test_export() {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        let backend = db.get_database_backend();
+        db.execute(backend.build(&Schema::new(backend).create_table_from_entity(Entity)))
+            .await
+            .unwrap();
+        let view = crate::new::<()>(db.clone())
+            .export::<Entity>("/user")
+            .unwrap()
+            .with_soft_delete("deleted")
+            .with_scope(quickapi_filter::ExtensionScope::<Entity, Tenant>::new(
+                Column::TenantId,
+                |tenant: &Tenant| tenant.0,
+            ));
+
+        // no rows, only header
+        assert_eq!(
+            export(view.clone()).await,
+            "id,tenant_id,username,note,deleted\n"
+        );
+
+        db.execute_unprepared(
+            r#"INSERT INTO "user" ("tenant_id", "username", "note", "deleted") VALUES
+                   (1, 'joe', '=HYPERLINK("x")', false),
+                   (1, 'anna', 'hi, all', true),
+                   (2, 'bob', '', false)"#,
+        )
+        .await
+        .unwrap();
+
+        // rows out of scope and soft deleted rows are not exported, formulas are escaped
+        assert_eq!(
+            export(view.clone()).await,
+            "id,tenant_id,username,note,deleted\n1,1,joe,\"'=HYPERLINK(\"\"x\"\")\",false\n"
+        );
+
+        // columns of custom serializer are taken from serialized object
+        let view = view
+            .with_serializer::<UserNote>()
+            .with_header("username", "User");
+        assert_eq!(
+            export(view.clone()).await,
+            "note,User\n\"'=HYPERLINK(\"\"x\"\")\",joe\n"
+        );
+        assert_eq!(export(view.with_columns(["username"])).await, "User\njoe\n");
+    }
+
+    #[test]
+    fn test_csv_row() {
+        let row = csv_row(["id", "name, surname", "say \"hi\"", ""]).unwrap();
+        assert_eq!(&row[..], b"id,\"name, surname\",\"say \"\"hi\"\"\",\n");
+    }
+
+    #[test]
+    fn test_csv_cell() {
+        assert_eq!(csv_cell(None), "");
+        assert_eq!(csv_cell(Some(&serde_json::json!(null))), "");
+        assert_eq!(csv_cell(Some(&serde_json::json!("text"))), "text");
+        assert_eq!(csv_cell(Some(&serde_json::json!(1.5))), "1.5");
+        assert_eq!(csv_cell(Some(&serde_json::json!([1, 2]))), "[1,2]");
+        assert_eq!(csv_cell(Some(&serde_json::json!("=1+2"))), "'=1+2");
+        assert_eq!(csv_cell(Some(&serde_json::json!("@SUM(A1)"))), "'@SUM(A1)");
+        assert_eq!(csv_cell(Some(&serde_json::json!(-1))), "-1");
+    }
+}
@@ -24,17 +24,17 @@
  */
 
 use axum::Json;
+use axum::http::StatusCode;
 use axum::response::{IntoResponse, Response};
+use quickapi_http::idempotency::Replay;
 use quickapi_view::ViewTrait;
+use sea_orm::{ConnectionTrait, DatabaseConnection, DbBackend};
 use serde_json::json;
 use std::marker::PhantomData;
 use std::pin::Pin;
 
-/// TODO: make this configurable
-const MAX_BODY_SIZE: usize = 1_048_576; // 0 means no limit
-
 #[derive(Clone)]
-pub(crate) struct Handler<S, V>(V, PhantomData<S>)
+pub(crate) struct Handler<S, V>(V, Option<DbBackend>, PhantomData<S>)
 where
     V: ViewTrait<S> + Send + Sync + 'static,
     S: Clone + Send + Sync + 'static;
@@ -46,7 +46,17 @@ where
 {
     /// Creates a new Handler instance with the given view and state.
     pub fn new(view: V) -> Self {
-        Self(view, PhantomData)
+        Self(view, None, PhantomData)
+    }
+
+    /// with_db sets database of the view, its backend is inserted into request extensions,
+    /// so filters build sql for the database the view actually queries.
+    pub fn with_db(mut self, db: &DatabaseConnection) -> Self {
+        self.1 = match db {
+            DatabaseConnection::Disconnected => None,
+            db => Some(db.get_database_backend()),
+        };
+        self
     }
 }
 
@@ -64,30 +74,469 @@ where
         let state = _state.clone();
 
         Box::pin(async move {
-            // read body into bytes
-            let body = axum::body::to_bytes(body, MAX_BODY_SIZE)
-                .await
-                .unwrap_or_default();
+            // read body into bytes (only when view reads it)
+            let body = match self.0.max_body_size() {
+                Some(max_body_size) => match read_body(body, max_body_size).await {
+                    Ok(body) => body,
+                    Err(response) => return response,
+                },
+                None => bytes::Bytes::new(),
+            };
+
+            // database backend of the view (filters build backend specific sql)
+            if let Some(backend) = self.1 {
+                parts.extensions.insert(backend);
+            }
 
             // prepare json response partials (keys)
             parts
                 .extensions
                 .insert(quickapi_http::response::partials::Partials::<S>::default());
 
+            // replay stored response for already seen Idempotency-Key (only to permitted callers)
+            let idempotency = self.0.idempotency();
+            let replay = match &idempotency {
+                Some(idempotency) if idempotency.key(&parts).is_some() => {
+                    match self.0.has_permission(&mut parts.clone(), &state).await {
+                        Ok(()) => {}
+                        Err(quickapi_view::Error::Response(response)) => {
+                            return response.merge_partials::<S>(&parts).into_response();
+                        }
+                        Err(err) => return internal_error(err.to_string()),
+                    }
+                    match idempotency.replay(&parts, &body).await {
+                        Ok(Some(Replay::Respond(response))) => return response,
+                        Ok(Some(Replay::Store { key, request_hash })) => Some((key, request_hash)),
+                        Ok(None) => None,
+                        Err(err) => return internal_error(err.to_string()),
+                    }
+                }
+                _ => None,
+            };
+
             // now run the view with the parts and state
-            match self.0.run(&mut parts, &state, &body).await {
+            let response = match self.0.run(&mut parts, &state, &body).await {
                 Ok(response) => {
-                    // Otherwise, we convert the response to a generic response.
-                    response.into_response()
+                    // merge partials (meta, links, ...) and envelope, then convert to generic response.
+                    response.merge_partials::<S>(&parts).into_response()
                 }
-                Err(err) => (
-                    axum::http::StatusCode::INTERNAL_SERVER_ERROR,
-                    Json(json!({
-                        "error": err.to_string(),
-                    })),
-                )
-                    .into_response(),
+                Err(err) => internal_error(err.to_string()),
+            };
+
+            // store first response for Idempotency-Key
+            match (idempotency, replay) {
+                (Some(idempotency), Some((key, request_hash))) => idempotency
+                    .store(&key, request_hash, response)
+                    .await
+                    .unwrap_or_else(|err| internal_error(err.to_string())),
+                _ => response,
             }
         })
     }
 }
+
+/// internal_error returns 500 response with error message.
+fn internal_error(message: String) -> Response {
+    (
+        axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+        Json(json!({
+            "error": message,
+        })),
+    )
+        .into_response()
+}
+
+/// check_when_max_body_size rejects `when` branch that changes maximum size of request body.
+/// Body is read before `when` conditions are evaluated, so the limit is per route (set on the view itself).
+pub(crate) fn check_when_max_body_size(view: usize, branch: usize) -> Result<(), crate::Error> {
+    if view != branch {
+        return Err(crate::Error::ImproperlyConfigured(
+            "max body size is per route, set it on the view instead of when branch".to_owned(),
+        ));
+    }
+    Ok(())
+}
+
+/// read_body reads the request body up to max_body_size (0 means no limit).
+/// Returns 413 when the body is too large and 400 when it cannot be read.
+async fn read_body(body: axum::body::Body, max_body_size: usize) -> Result<bytes::Bytes, Response> {
+    let limit = match max_body_size {
+        0 => usize::MAX,
+        limit => limit,
+    };
+
+    axum::body::to_bytes(body, limit).await.map_err(|err| {
+        let inner = err.into_inner();
+        if inner.is::<http_body_util::LengthLimitError>() {
+            (
+                StatusCode::PAYLOAD_TOO_LARGE,
+                Json(json!({
+                    "error": "Payload Too Large",
+                    "message": format!("request body exceeds {} bytes", max_body_size),
+                })),
+            )
+                .into_response()
+        } else {
+            (
+                StatusCode::BAD_REQUEST,
+                Json(json!({
+                    "error": "Bad Request",
+                    "message": format!("failed to read request body: {}", inner),
+                })),
+            )
+                .into_response()
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::Router;
+    use axum::http::request::Parts;
+    use axum::routing::post;
+    use axum_test::TestServer;
+    use quickapi_http::Response;
+    use quickapi_http::response::{Envelope, Partials};
+
+    // EchoView responds with the size of the body it received
+    #[derive(Clone)]
+    struct EchoView(Option<usize>);
+
+    impl quickapi_view::RouterExt<()> for EchoView {
+        fn register_router_with_prefix(
+            &self,
+            router: Router<()>,
+            _prefix: &str,
+        ) -> Result<Router<()>, quickapi_view::Error> {
+            Ok(router.route("/", post(Handler::new(self.clone()))))
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl ViewTrait<()> for EchoView {
+        async fn handle_view(
+            &self,
+            _parts: &mut Parts,
+            _state: &(),
+            body: &bytes::Bytes,
+        ) -> Result<Response, quickapi_view::Error> {
+            Ok(Response::new(json!({"size": body.len()})))
+        }
+
+        async fn get_when_views<'a>(
+            &'a self,
+            _parts: &'a mut Parts,
+            _state: &'a (),
+        ) -> Result<Vec<&'a (dyn ViewTrait<()> + Send + Sync)>, quickapi_view::Error> {
+            Ok(vec![])
+        }
+
+        fn has_fallback(&self) -> bool {
+            true
+        }
+
+        fn max_body_size(&self) -> Option<usize> {
+            self.0
+        }
+    }
+
+    // server prepares test server for given view
+    fn server(view: EchoView) -> TestServer {
+        use quickapi_view::RouterExt;
+        TestServer::new(view.register_router(Router::new()).unwrap()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_body_within_limit() {
+        let response = server(EchoView(Some(10))).post("/").bytes("hello".into()).await;
+        response.assert_status_ok();
+        response.assert_json(&json!({"size": 5}));
+    }
+
+    #[tokio::test]
+    async fn test_body_too_large() {
+        let response = server(EchoView(Some(3))).post("/").bytes("hello".into()).await;
+        response.assert_status(StatusCode::PAYLOAD_TOO_LARGE);
+    }
+
+    #[tokio::test]
+    async fn test_body_no_limit() {
+        let response = server(EchoView(Some(0))).post("/").bytes("hello".into()).await;
+        response.assert_json(&json!({"size": 5}));
+    }
+
+    #[tokio::test]
+    async fn test_body_not_read() {
+        let response = server(EchoView(None)).post("/").bytes("hello".into()).await;
+        response.assert_json(&json!({"size": 0}));
+    }
+
+    // BackendView responds with database backend found in request extensions
+    #[derive(Clone)]
+    struct BackendView;
+
+    impl quickapi_view::RouterExt<()> for BackendView {
+        fn register_router_with_prefix(
+            &self,
+            router: Router<()>,
+            _prefix: &str,
+        ) -> Result<Router<()>, quickapi_view::Error> {
+            let handler = Handler(self.clone(), Some(DbBackend::MySql), PhantomData);
+            Ok(router.route("/", post(handler)))
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl ViewTrait<()> for BackendView {
+        async fn handle_view(
+            &self,
+            parts: &mut Parts,
+            _state: &(),
+            _body: &bytes::Bytes,
+        ) -> Result<Response, quickapi_view::Error> {
+            let backend = parts.extensions.get::<DbBackend>().map(|b| format!("{:?}", b));
+            Ok(Response::new(json!({"backend": backend})))
+        }
+
+        async fn get_when_views<'a>(
+            &'a self,
+            _parts: &'a mut Parts,
+            _state: &'a (),
+        ) -> Result<Vec<&'a (dyn ViewTrait<()> + Send + Sync)>, quickapi_view::Error> {
+            Ok(vec![])
+        }
+
+        fn has_fallback(&self) -> bool {
+            true
+        }
+    }
+
+    #[tokio::test]
+    async fn test_backend_extension() {
+        use quickapi_view::RouterExt;
+        let server = TestServer::new(BackendView.register_router(Router::new()).unwrap()).unwrap();
+        let response = server.post("/").await;
+        response.assert_json(&json!({"backend": "MySql"}));
+    }
+
+    // CounterView responds with number of times it was called, honours Idempotency-Key
+    #[derive(Clone)]
+    struct CounterView(
+        std::sync::Arc<std::sync::atomic::AtomicUsize>,
+        quickapi_http::idempotency::Idempotency,
+    );
+
+    impl quickapi_view::RouterExt<()> for CounterView {
+        fn register_router_with_prefix(
+            &self,
+            router: Router<()>,
+            _prefix: &str,
+        ) -> Result<Router<()>, quickapi_view::Error> {
+            Ok(router.route("/", post(Handler::new(self.clone()))))
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl ViewTrait<()> for CounterView {
+        async fn handle_view(
+            &self,
+            _parts: &mut Parts,
+            _state: &(),
+            _body: &bytes::Bytes,
+        ) -> Result<Response, quickapi_view::Error> {
+            let count = self.0.fetch_add(1, std::sync::atomic::Ordering::SeqCst) + 1;
+            // slow enough for concurrent requests to overlap
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+            Ok(Response::new(json!({"count": count})).with_status(StatusCode::CREATED))
+        }
+
+        async fn get_when_views<'a>(
+            &'a self,
+            _parts: &'a mut Parts,
+            _state: &'a (),
+        ) -> Result<Vec<&'a (dyn ViewTrait<()> + Send + Sync)>, quickapi_view::Error> {
+            Ok(vec![])
+        }
+
+        fn has_fallback(&self) -> bool {
+            true
+        }
+
+        fn max_body_size(&self) -> Option<usize> {
+            Some(0)
+        }
+
+        fn idempotency(&self) -> Option<quickapi_http::idempotency::Idempotency> {
+            Some(self.1.clone())
+        }
+
+        // requests with `x-deny` header are not permitted
+        async fn has_permission(
+            &self,
+            parts: &mut Parts,
+            _state: &(),
+        ) -> Result<(), quickapi_view::Error> {
+            match parts.headers.contains_key("x-deny") {
+                true => Err(quickapi_view::Error::Response(Box::new(
+                    Response::default().with_status(StatusCode::FORBIDDEN),
+                ))),
+                false => Ok(()),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_idempotency_key() {
+        use quickapi_view::RouterExt;
+        let view = CounterView(
+            Default::default(),
+            quickapi_http::idempotency::MemoryIdempotencyStore::new().into(),
+        );
+        let server = TestServer::new(view.register_router(Router::new()).unwrap()).unwrap();
+
+        // same key and body is replayed
+        for _ in 0..2 {
+            let response = server
+                .post("/")
+                .add_header("Idempotency-Key", "abc")
+                .bytes("hello".into())
+                .await;
+            response.assert_status(StatusCode::CREATED);
+            response.assert_json(&json!({"count": 1}));
+        }
+
+        // same key with different body is conflict
+        let response = server
+            .post("/")
+            .add_header("Idempotency-Key", "abc")
+            .bytes("other".into())
+            .await;
+        response.assert_status(StatusCode::CONFLICT);
+
+        // stored response is not replayed to caller without permission
+        let response = server
+            .post("/")
+            .add_header("Idempotency-Key", "abc")
+            .add_header("x-deny", "1")
+            .bytes("hello".into())
+            .await;
+        response.assert_status(StatusCode::FORBIDDEN);
+
+        // keys are scoped by Authorization header, other caller's request is not replayed
+        let response = server
+            .post("/")
+            .add_header("Idempotency-Key", "abc")
+            .add_header("Authorization", "Bearer other")
+            .bytes("hello".into())
+            .await;
+        response.assert_json(&json!({"count": 2}));
+
+        // requests without key are not replayed
+        let response = server.post("/").bytes("hello".into()).await;
+        response.assert_json(&json!({"count": 3}));
+    }
+
+    #[tokio::test]
+    async fn test_idempotency_key_concurrent() {
+        use quickapi_view::RouterExt;
+        let view = CounterView(
+            Default::default(),
+            quickapi_http::idempotency::MemoryIdempotencyStore::new().into(),
+        );
+        let server = TestServer::new(view.register_router(Router::new()).unwrap()).unwrap();
+        let request = || {
+            server
+                .post("/")
+                .add_header("Idempotency-Key", "abc")
+                .bytes("hello".into())
+        };
+
+        // view runs once, retry sent while the first request is in progress is conflict
+        let (first, second) = tokio::join!(request(), request());
+        let mut statuses = [first.status_code(), second.status_code()];
+        statuses.sort();
+        assert_eq!(statuses, [StatusCode::CREATED, StatusCode::CONFLICT]);
+        assert_eq!(view.0.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+        // once finished, the response is replayed
+        let response = request().await;
+        response.assert_status(StatusCode::CREATED);
+        response.assert_json(&json!({"count": 1}));
+    }
+
+    // MetaView adds meta through Partials extractor and responds with list of objects
+    #[derive(Clone)]
+    struct MetaView;
+
+    impl quickapi_view::RouterExt<()> for MetaView {
+        fn register_router_with_prefix(
+            &self,
+            router: Router<()>,
+            _prefix: &str,
+        ) -> Result<Router<()>, quickapi_view::Error> {
+            Ok(router.route("/", axum::routing::get(Handler::new(self.clone()))))
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl ViewTrait<()> for MetaView {
+        async fn handle_view(
+            &self,
+            parts: &mut Parts,
+            state: &(),
+            _body: &bytes::Bytes,
+        ) -> Result<Response, quickapi_view::Error> {
+            use axum::extract::FromRequestParts;
+            let partials = Partials::<()>::from_request_parts(parts, state).await.unwrap();
+            partials.insert("meta", json!({"total": 1}));
+            partials.push("warnings", json!("deprecated"));
+            Ok(Response::new(json!([{"id": 1}])))
+        }
+
+        async fn get_when_views<'a>(
+            &'a self,
+            _parts: &'a mut Parts,
+            _state: &'a (),
+        ) -> Result<Vec<&'a (dyn ViewTrait<()> + Send + Sync)>, quickapi_view::Error> {
+            Ok(vec![])
+        }
+
+        fn has_fallback(&self) -> bool {
+            true
+        }
+    }
+
+    #[tokio::test]
+    async fn test_partials() {
+        use quickapi_view::RouterExt;
+        let router = MetaView.register_router(Router::new()).unwrap();
+
+        let response = TestServer::new(router).unwrap().get("/").await;
+        response.assert_json(&json!({
+            "data": [{"id": 1}],
+            "meta": {"total": 1},
+            "warnings": ["deprecated"],
+        }));
+    }
+
+    #[tokio::test]
+    async fn test_envelope() {
+        use quickapi_view::RouterExt;
+        let envelope = Envelope::new("data")
+            .with_key("meta", json!({}))
+            .with_key("errors", json!([]));
+        let router = MetaView
+            .register_router(Router::new())
+            .unwrap()
+            .layer(axum::Extension(envelope));
+
+        let response = TestServer::new(router).unwrap().get("/").await;
+        response.assert_json(&json!({
+            "data": [{"id": 1}],
+            "meta": {"total": 1},
+            "warnings": ["deprecated"],
+            "errors": [],
+        }));
+    }
+}
@@ -0,0 +1,649 @@
+/*
+ *  The MIT License (MIT)
+ *
+ *  Copyright (c) 2024-2025, Peter Vrba
+ *
+ *  Permission is hereby granted, free of charge, to any person obtaining a copy
+ *  of this software and associated documentation files (the "Software"), to deal
+ *  in the Software without restriction, including without limitation the rights
+ *  to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ *  copies of the Software, and to permit persons to whom the Software is
+ *  furnished to do so, subject to the following conditions:
+ *
+ *  The above copyright notice and this permission notice shall be included in
+ *  all copies or substantial portions of the Software.
+ *
+ *  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ *  IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ *  FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ *  AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ *  LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ *  OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+ *  THE SOFTWARE.
+ *
+ */
+
+use crate::view::handler::{Handler, check_when_max_body_size};
+use crate::view::registry::{Registration, Routes};
+use axum::Router;
+use axum::http::request::Parts;
+use axum::http::{Method, StatusCode};
+use axum::routing::on;
+use quickapi_filter::Scope;
+use quickapi_http::Response;
+use quickapi_permission::Permission;
+use quickapi_view::{Error, ViewTrait, as_method_filter};
+use sea_orm::{
+    ConnectionTrait, DatabaseConnection, EntityTrait, IntoActiveModel, TransactionTrait,
+};
+use serde::de::DeserializeOwned;
+use serde_json::json;
+use std::marker::PhantomData;
+use tracing::debug;
+
+use quickapi_model::ModelCallbackErased;
+
+/// DEFAULT_BATCH_SIZE is the default number of rows inserted by one query.
+const DEFAULT_BATCH_SIZE: usize = 500;
+
+/// Format is format of imported file, detected by Content-Type header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Csv,
+    Ndjson,
+}
+
+impl Format {
+    /// from_parts returns format by Content-Type header of the request.
+    fn from_parts(parts: &Parts) -> Option<Self> {
+        let content_type = parts
+            .headers
+            .get(axum::http::header::CONTENT_TYPE)?
+            .to_str()
+            .ok()?;
+        match content_type.split(';').next()?.trim() {
+            "text/csv" => Some(Format::Csv),
+            "application/x-ndjson" | "application/ndjson" | "application/jsonl" => {
+                Some(Format::Ndjson)
+            }
+            _ => None,
+        }
+    }
+
+    /// parse parses rows of the file into serializer, rows are numbered from 1 (CSV header is not a row).
+    fn parse<Ser>(&self, body: &[u8]) -> Vec<(usize, Result<Ser, String>)>
+    where
+        Ser: DeserializeOwned,
+    {
+        match self {
+            Format::Csv => csv::Reader::from_reader(body)
+                .into_deserialize::<Ser>()
+                .enumerate()
+                .map(|(index, row)| (index + 1, row.map_err(|e| e.to_string())))
+                .collect(),
+            Format::Ndjson => body
+                .split(|b| *b == b'\n')
+                .enumerate()
+                .filter(|(_, line)| !line.trim_ascii().is_empty())
+                .map(|(index, line)| {
+                    (
+                        index + 1,
+                        serde_json::from_slice(line).map_err(|e| e.to_string()),
+                    )
+                })
+                .collect(),
+        }
+    }
+}
+
+/// ImportView is a view for bulk import of entities from uploaded CSV (`text/csv`, with header row)
+/// or NDJSON (`application/x-ndjson`). Rows are deserialized into the create serializer (same as in CreateView),
+/// valid rows are inserted in batches within one transaction. Responds with report of inserted and failed rows.
+#[derive(Clone)]
+pub struct ImportView<E, S, Ser>
+where
+    E: EntityTrait,
+    S: Clone + Send + Sync + 'static,
+    Ser: Clone + DeserializeOwned + Sync + Send + 'static,
+    <E as EntityTrait>::Model: From<Ser>,
+{
+    db: DatabaseConnection,
+    path: String,
+    method: Method,
+    registration: Registration,
+    when: quickapi_when::WhenViews<S>,
+    before_save: quickapi_model::ModelCallbacks<E, S>,
+    scopes: quickapi_filter::Scopes<E, S>,
+    permissions: quickapi_permission::Permissions<E, S>,
+    batch_size: usize,
+    fallback: bool,
+    transaction: bool,
+    max_body_size: usize,
+    _phantom_data: PhantomData<(E, S, Ser)>,
+}
+
+impl<E, S, Ser> ImportView<E, S, Ser>
+where
+    E: EntityTrait,
+    S: Clone + Send + Sync + 'static,
+    Ser: Clone + DeserializeOwned + Sync + Send + 'static,
+    <E as EntityTrait>::Model: From<Ser>,
+{
+    /// new creates a new ImportView instance.
+    pub(crate) fn new(db: DatabaseConnection, path: impl Into<String>, method: Method) -> Self {
+        Self {
+            db,
+            path: path.into(),
+            method,
+            registration: Registration::new::<E>("import"),
+            when: Default::default(),
+            before_save: Default::default(),
+            scopes: quickapi_filter::Scopes::new(),
+            permissions: Default::default(),
+            batch_size: DEFAULT_BATCH_SIZE,
+            fallback: false,
+            transaction: false,
+            max_body_size: crate::quickapi::DEFAULT_MAX_BODY_SIZE,
+            _phantom_data: PhantomData,
+        }
+    }
+
+    /// with_name sets name of the route in QuickApi routes (`{table}-{kind}` by default, e.g. `user-import`).
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.registration = self.registration.with_name(name);
+        self
+    }
+
+    /// with_routes sets registry of routes the view is recorded into when registered.
+    pub(crate) fn with_routes(mut self, routes: Routes) -> Self {
+        self.registration = self.registration.with_routes(routes);
+        self
+    }
+
+    /// with_serializer sets custom serializer rows are deserialized into.
+    pub fn with_serializer<Serializer>(self) -> ImportView<E, S, Serializer>
+    where
+        Serializer: Clone + DeserializeOwned + Sync + Send + 'static,
+        <E as EntityTrait>::Model: From<Serializer>,
+    {
+        ImportView {
+            db: self.db,
+            path: self.path,
+            method: self.method,
+            registration: self.registration,
+            when: self.when,
+            before_save: self.before_save,
+            scopes: self.scopes,
+            permissions: self.permissions,
+            batch_size: self.batch_size,
+            fallback: false,
+            transaction: self.transaction,
+            max_body_size: self.max_body_size,
+            _phantom_data: PhantomData,
+        }
+    }
+
+    /// with_before_save sets a before save handler called for every row, its error fails the row.
+    pub fn with_before_save<T>(
+        mut self,
+        before_save: impl quickapi_model::ModelCallback<E, S, T> + Clone + Send + Sync + 'static,
+    ) -> Self
+    where
+        T: Send + Sync + 'static,
+    {
+        self.before_save.push(before_save);
+        self
+    }
+
+    /// remove all before save handlers from the ImportView.
+    pub fn clear_before_save(mut self) -> Self {
+        self.before_save.clear();
+        self
+    }
+
+    /// with_scope adds a row-level scope to the ImportView, scoped columns are force-set on every row.
+    pub fn with_scope(mut self, scope: impl Scope<E, S> + 'static) -> Self {
+        self.scopes.push(scope);
+        self
+    }
+
+    /// with_permission adds a permission to the ImportView.
+    /// Object permission is checked on every row before it's inserted.
+    pub fn with_permission(mut self, permission: impl Permission<E, S> + 'static) -> Self {
+        self.permissions.push(permission);
+        self
+    }
+
+    /// with_batch_size sets number of rows inserted by one query.
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// with_max_body_size sets the maximum size of uploaded file (0 means no limit).
+    pub fn with_max_body_size(mut self, max_body_size: usize) -> Self {
+        self.max_body_size = max_body_size;
+        self
+    }
+
+    /// with_transaction runs the whole ImportView (callbacks and queries) in one database transaction.
+    /// Transaction is committed on success response, rolled back otherwise.
+    pub fn with_transaction(mut self, transaction: bool) -> Self {
+        self.transaction = transaction;
+        self
+    }
+
+    /// with_fallback sets a fallback ImportView that will be used if when conditions are not met.
+    pub fn with_fallback(mut self, fallback: bool) -> Self {
+        self.fallback = fallback;
+        self
+    }
+
+    /// when adds a condition to the ImportView.
+    pub fn when<F, T, Serializer>(
+        mut self,
+        _when: impl quickapi_when::When<S, T> + Clone + Sync + 'static,
+        _f: F,
+    ) -> Result<Self, crate::Error>
+    where
+        Serializer: Clone + DeserializeOwned + Sync + Send + 'static,
+        <E as EntityTrait>::Model: From<Serializer> + IntoActiveModel<E::ActiveModel> + Sync,
+        <E as EntityTrait>::ActiveModel: Send,
+        F: Fn(ImportView<E, S, Ser>) -> Result<ImportView<E, S, Serializer>, crate::Error>,
+        T: Sync + Send + 'static,
+    {
+        let mut clone = self.clone();
+        clone.when = Default::default();
+        let _result = _f(clone)?;
+        check_when_max_body_size(self.max_body_size, _result.max_body_size)?;
+        self.when.add_when(_when, _result);
+        Ok(self)
+    }
+}
+
+/// ImportView implementation of the request pipeline.
+impl<E, S, Ser> ImportView<E, S, Ser>
+where
+    E: EntityTrait,
+    S: Clone + Send + Sync + 'static,
+    Ser: Clone + DeserializeOwned + Sync + Send + 'static,
+    <E as EntityTrait>::Model: From<Ser> + IntoActiveModel<E::ActiveModel> + Sync,
+    <E as EntityTrait>::ActiveModel: Send,
+{
+    /// insert inserts instances in batches within one transaction, returns number of inserted rows.
+    async fn insert<C>(&self, conn: &C, instances: Vec<E::Model>) -> Result<u64, Error>
+    where
+        C: ConnectionTrait + TransactionTrait,
+    {
+        let txn = conn.begin().await?;
+        let mut inserted = 0;
+
+        let mut instances = instances.into_iter().peekable();
+        while instances.peek().is_some() {
+            let batch = instances
+                .by_ref()
+                .take(self.batch_size)
+                .map(quickapi_model::insert_active_model::<E>);
+            inserted += E::insert_many(batch).exec_without_returning(&txn).await?;
+        }
+
+        txn.commit().await?;
+        Ok(inserted)
+    }
+
+    /// handle runs the view pipeline on given connection (database or transaction).
+    async fn handle<C>(
+        &self,
+        conn: &C,
+        _parts: &mut Parts,
+        _state: &S,
+        _body: &bytes::Bytes,
+    ) -> Result<Response, Error>
+    where
+        C: ConnectionTrait + TransactionTrait,
+    {
+        let mut parts = _parts.clone();
+
+        // check permissions before anything else
+        self.permissions.has_permission(&mut parts, _state).await?;
+
+        let Some(format) = Format::from_parts(&parts) else {
+            return Ok(Response::new(json!({
+                "error": "Unsupported Media Type",
+                "message": "expected text/csv or application/x-ndjson",
+            }))
+            .with_status(StatusCode::UNSUPPORTED_MEDIA_TYPE));
+        };
+
+        let rows = format.parse::<Ser>(_body);
+        let total = rows.len();
+        let mut instances = Vec::with_capacity(total);
+        let mut failed = Vec::new();
+
+        for (row, result) in rows {
+            let instance: E::Model = match result {
+                Ok(instance) => instance.into(),
+                Err(message) => {
+                    failed.push(json!({"row": row, "message": message}));
+                    continue;
+                }
+            };
+
+            // apply before_save handlers
+            let instance = match self.before_save.call(&mut parts, _state, instance).await {
+                Ok(instance) => instance,
+                Err(e) => {
+                    failed.push(json!({"row": row, "message": e.to_string()}));
+                    continue;
+                }
+            };
+
+            // force scoped columns, rows out of scope are reported as failed
+            let instance = match self.scopes.scope_model(&mut parts, _state, instance).await {
+                Ok(instance) => instance,
+                Err(quickapi_filter::Error::Forbidden(message))
+                | Err(quickapi_filter::Error::NotFound(message)) => {
+                    failed.push(json!({"row": row, "message": message}));
+                    continue;
+                }
+                Err(e) => return Err(e.into()),
+            };
+
+            // check object permissions, denied rows are reported as failed
+            match self
+                .permissions
+                .has_object_permission(&mut parts, _state, &instance)
+                .await
+            {
+                Ok(()) => instances.push(instance),
+                Err(quickapi_permission::Error::Denied(message)) => {
+                    failed.push(json!({"row": row, "message": message}));
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        let inserted = self.insert(conn, instances).await?;
+
+        Ok(Response::new(json!({
+            "total": total,
+            "inserted": inserted,
+            "failed": failed,
+        })))
+    }
+}
+
+/// Implement the ViewTrait for ImportView, which defines how the view handles requests.
+#[async_trait::async_trait]
+impl<E, S, Ser> ViewTrait<S> for ImportView<E, S, Ser>
+where
+    E: EntityTrait,
+    S: Clone + Send + Sync + 'static,
+    Ser: Clone + DeserializeOwned + Sync + Send + 'static,
+    <E as EntityTrait>::Model: From<Ser> + IntoActiveModel<E::ActiveModel> + Sync,
+    <E as EntityTrait>::ActiveModel: Send,
+{
+    async fn handle_view(
+        &self,
+        _parts: &mut Parts,
+        _state: &S,
+        _body: &bytes::Bytes,
+    ) -> Result<Response, Error> {
+        if !self.transaction {
+            return self.handle(&self.db, _parts, _state, _body).await;
+        }
+
+        // run the whole pipeline in one transaction, rolled back on any error
+        let transaction = crate::view::transaction::begin(&self.db, _parts).await?;
+        let response = match self.handle(&*transaction, _parts, _state, _body).await {
+            Ok(response) => response,
+            Err(err) => {
+                crate::view::transaction::discard(transaction, _parts);
+                return Err(err);
+            }
+        };
+        crate::view::transaction::finish(transaction, _parts, response).await
+    }
+
+    /// get_when_views returns a vector of views that should be executed when the ImportView is called.
+    async fn get_when_views<'a>(
+        &'a self,
+        _parts: &'a mut Parts,
+        _state: &'a S,
+    ) -> Result<Vec<&'a (dyn ViewTrait<S> + Send + Sync)>, Error> {
+        self.when
+            .get_views(_parts, _state)
+            .await
+            .map_err(Into::into)
+    }
+
+    /// has_fallback returns true if the ImportView has a fallback defined (if when does not matches).
+    fn has_fallback(&self) -> bool {
+        self.fallback
+    }
+
+    /// max_body_size returns the maximum size of uploaded file for the ImportView.
+    fn max_body_size(&self) -> Option<usize> {
+        Some(self.max_body_size)
+    }
+}
+
+/// ImportView implementation for registering the view with an axum router.
+impl<E, S, Ser> quickapi_view::RouterExt<S> for ImportView<E, S, Ser>
+where
+    E: EntityTrait,
+    S: Clone + Send + Sync + 'static,
+    Ser: Clone + DeserializeOwned + Sync + Send + 'static,
+    <E as EntityTrait>::Model: From<Ser> + IntoActiveModel<E::ActiveModel> + Sync,
+    <E as EntityTrait>::ActiveModel: Send,
+{
+    fn register_router_with_prefix(
+        &self,
+        router: Router<S>,
+        prefix: &str,
+    ) -> Result<Router<S>, Error> {
+        let mf = as_method_filter(&self.method)?;
+        let path = format!("{}{}", prefix, self.path);
+
+        debug!(method = self.method.to_string(), path = &path, "API import",);
+
+        self.registration.register(&self.method, &path)?;
+        let handler = Handler::new(self.clone()).with_db(&self.db);
+        Ok(router.route(&path, on(mf, handler)))
+    }
+
+    /// apply_scopes adds scopes registered for the entity (also to when views)
+    fn apply_scopes(&mut self, scopes: &quickapi_filter::ScopeRegistry<S>) {
+        self.scopes.extend(&scopes.get::<E>());
+        self.when.apply_scopes(scopes);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum_test::TestServer;
+    use quickapi_view::RouterExt;
+    use sea_orm::entity::prelude::*;
+    use sea_orm::{Database, QueryOrder, Schema};
+
+    #[derive(
+        Clone, Debug, PartialEq, Eq, DeriveEntityModel, serde::Deserialize, serde::Serialize,
+    )]
+    #[sea_orm(table_name = "user")]
+    pub struct Model {
+        #[sea_orm(primary_key)]
+        #[serde(default)]
+        pub id: i32,
+        #[serde(default)]
+        pub tenant_id: i32,
+        pub username: String,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+
+    #[derive(Clone)]
+    struct Tenant(i32);
+
+    // NoRoot denies importing user named root
+    #[derive(Clone)]
+    struct NoRoot;
+
+    #[async_trait::async_trait]
+    impl Permission<Entity, ()> for NoRoot {
+        async fn has_permission(
+            &self,
+            _parts: &mut Parts,
+            _state: &(),
+        ) -> Result<(), quickapi_permission::Error> {
+            Ok(())
+        }
+
+        async fn has_object_permission(
+            &self,
+            _parts: &mut Parts,
+            _state: &(),
+            model: &Model,
+        ) -> Result<(), quickapi_permission::Error> {
+            match model.username.as_str() {
+                "root" => Err(quickapi_permission::Error::Denied(
+                    "root is reserved".to_owned(),
+                )),
+                _ => Ok(()),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_import() {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        let backend = db.get_database_backend();
+        db.execute(backend.build(&Schema::new(backend).create_table_from_entity(Entity)))
+            .await
+            .unwrap();
+        let view = crate::new::<()>(db.clone())
+            .import::<Entity>("/user")
+            .unwrap()
+            .with_permission(NoRoot)
+            .with_scope(quickapi_filter::ExtensionScope::<Entity, Tenant>::new(
+                Column::TenantId,
+                |tenant: &Tenant| tenant.0,
+            ));
+        let router = view
+            .register_router(Router::new())
+            .unwrap()
+            .layer(axum::Extension(Tenant(1)));
+        let server = TestServer::new(router).unwrap();
+
+        // unsupported format
+        server
+            .post("/user")
+            .json(&json!({"username": "joe"}))
+            .await
+            .assert_status(StatusCode::UNSUPPORTED_MEDIA_TYPE);
+
+        // invalid and denied rows are reported, valid rows are inserted in scope
+        let response = server
+            .post("/user")
+            .bytes("username,tenant_id\njoe,2\nroot,1\nanna,x\nbob,1\n".into())
+            .content_type("text/csv")
+            .await;
+        response.assert_status_ok();
+        let report = response.json::<serde_json::Value>();
+        assert_eq!(
+            (&report["total"], &report["inserted"]),
+            (&json!(4), &json!(2))
+        );
+        assert_eq!(
+            report["failed"][0],
+            json!({"row": 2, "message": "root is reserved"})
+        );
+        assert_eq!(report["failed"][1]["row"], json!(3));
+
+        let response = server
+            .post("/user")
+            .bytes("{\"username\": \"eve\"}\n{\"username\": \"root\"}\n".into())
+            .content_type("application/x-ndjson")
+            .await;
+        response.assert_json(&json!({
+            "total": 2,
+            "inserted": 1,
+            "failed": [{"row": 2, "message": "root is reserved"}],
+        }));
+
+        let users = Entity::find()
+            .order_by_asc(Column::Id)
+            .all(&db)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|user| (user.tenant_id, user.username))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            users,
+            [
+                (1, "joe".to_owned()),
+                (1, "bob".to_owned()),
+                (1, "eve".to_owned())
+            ]
+        );
+    }
+
+    #[derive(Debug, Clone, PartialEq, serde::Deserialize)]
+    struct Row {
+        name: String,
+        amount: i32,
+    }
+
+    #[test]
+    fn test_parse_csv() {
+        let rows = Format::Csv.parse::<Row>(b"name,amount\nfirst,1\nsecond,x\n");
+        assert_eq!(rows.len(), 2);
+        assert_eq!(
+            rows[0],
+            (
+                1,
+                Ok(Row {
+                    name: "first".to_owned(),
+                    amount: 1
+                })
+            )
+        );
+        assert_eq!(rows[1].0, 2);
+        assert!(rows[1].1.is_err());
+    }
+
+    #[test]
+    fn test_parse_ndjson() {
+        let rows =
+            Format::Ndjson.parse::<Row>(b"{\"name\":\"first\",\"amount\":1}\n\n{\"name\":\"x\"}\n");
+        assert_eq!(rows.len(), 2);
+        assert!(rows[0].1.is_ok());
+        assert_eq!(rows[1].0, 3);
+        assert!(rows[1].1.is_err());
+    }
+
+    #[test]
+    fn test_format() {
+        let parts = |content_type: &str| {
+            axum::http::Request::post("/")
+                .header(axum::http::header::CONTENT_TYPE, content_type)
+                .body(())
+                .unwrap()
+                .into_parts()
+                .0
+        };
+        assert_eq!(
+            Format::from_parts(&parts("text/csv; charset=utf-8")),
+            Some(Format::Csv)
+        );
+        assert_eq!(
+            Format::from_parts(&parts("application/x-ndjson")),
+            Some(Format::Ndjson)
+        );
+        assert_eq!(Format::from_parts(&parts("application/json")), None);
+    }
+}
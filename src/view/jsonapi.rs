@@ -0,0 +1,426 @@
+/*
+ *  The MIT License (MIT)
+ *
+ *  Copyright (c) 2024-2025, Peter Vrba
+ *
+ *  Permission is hereby granted, free of charge, to any person obtaining a copy
+ *  of this software and associated documentation files (the "Software"), to deal
+ *  in the Software without restriction, including without limitation the rights
+ *  to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ *  copies of the Software, and to permit persons to whom the Software is
+ *  furnished to do so, subject to the following conditions:
+ *
+ *  The above copyright notice and this permission notice shall be included in
+ *  all copies or substantial portions of the Software.
+ *
+ *  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ *  IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ *  FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ *  AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ *  LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ *  OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+ *  THE SOFTWARE.
+ *
+ */
+
+use crate::view::links::LINKS_KEY;
+use axum::http::request::Parts;
+use quickapi_filter::common::paginator::Pagination;
+use sea_orm::{ColumnTrait, ColumnType, EntityTrait, Iterable, PrimaryKeyToColumn};
+use serde_json::{Map, Value, json};
+
+/// JSON_API_CONTENT_TYPE is the media type of JSON:API documents.
+pub const JSON_API_CONTENT_TYPE: &str = "application/vnd.api+json";
+
+/// JsonApi renders objects as JSON:API resources `{type, id, attributes, relationships}` and parses
+/// JSON:API request documents back into plain objects (used by views in JSON:API mode).
+/// Resource type is the table name of the entity and id is its primary key.
+#[derive(Clone, Debug)]
+pub struct JsonApi {
+    resource: Resource,
+    relationships: Vec<Relationship>,
+}
+
+/// Resource is the type of resource and its id attribute.
+#[derive(Clone, Debug)]
+struct Resource {
+    resource_type: String,
+    id: String,
+    numeric_id: bool,
+}
+
+/// Relationship is an attribute rendered in relationships (foreign key or expanded related objects).
+#[derive(Clone, Debug)]
+struct Relationship {
+    name: String,
+    field: String,
+    resource: Resource,
+}
+
+impl Resource {
+    /// new returns resource of entity E.
+    fn new<E>() -> Result<Self, crate::Error>
+    where
+        E: EntityTrait,
+    {
+        let id = quickapi_model::primary_key::<E>()
+            .map_err(|e| crate::Error::ImproperlyConfigured(e.to_string()))?;
+
+        // ids are strings in JSON:API, numeric primary keys are converted back when parsing
+        let numeric_id = E::PrimaryKey::iter().next().is_some_and(|pk| {
+            matches!(
+                pk.into_column().def().get_column_type(),
+                ColumnType::TinyInteger
+                    | ColumnType::SmallInteger
+                    | ColumnType::Integer
+                    | ColumnType::BigInteger
+                    | ColumnType::TinyUnsigned
+                    | ColumnType::SmallUnsigned
+                    | ColumnType::Unsigned
+                    | ColumnType::BigUnsigned
+            )
+        });
+
+        Ok(Self {
+            resource_type: E::default().table_name().to_owned(),
+            id,
+            numeric_id,
+        })
+    }
+
+    /// identifier returns resource identifier `{type, id}` of given id value.
+    fn identifier(&self, id: &Value) -> Value {
+        let id = match id {
+            Value::String(id) => id.clone(),
+            id => id.to_string(),
+        };
+        json!({"type": self.resource_type, "id": id})
+    }
+
+    /// parse_id returns id value of resource identifier (number for numeric primary keys).
+    fn parse_id(&self, identifier: &Value) -> Result<Value, crate::Error> {
+        let id = match identifier.get("id") {
+            Some(Value::String(id)) => id,
+            Some(id) if id.is_number() => return Ok(id.clone()),
+            _ => return Err(invalid("resource identifier without id")),
+        };
+        if let Some(resource_type) = identifier.get("type").and_then(Value::as_str)
+            && resource_type != self.resource_type
+        {
+            return Err(invalid(&format!("unexpected type `{resource_type}`")));
+        }
+        match self.numeric_id {
+            true => id
+                .parse::<i64>()
+                .map(Value::from)
+                .map_err(|_| invalid(&format!("invalid id `{id}`"))),
+            false => Ok(Value::String(id.clone())),
+        }
+    }
+
+    /// render returns resource object of given object, expanded relations are added to included.
+    fn render(
+        &self,
+        object: Value,
+        relationships: &[Relationship],
+        included: &mut Vec<Value>,
+    ) -> Value {
+        let mut attributes = match object {
+            Value::Object(attributes) => attributes,
+            other => return other,
+        };
+        let mut resource = self.identifier(&attributes.remove(&self.id).unwrap_or(Value::Null));
+
+        // HAL links of object are links of resource
+        if let Some(links) = attributes.remove(LINKS_KEY) {
+            resource["links"] = links;
+        }
+
+        let mut rendered = Map::new();
+        for relationship in relationships {
+            if let Some(value) = attributes.remove(&relationship.field) {
+                let data = relationship.render(value, included);
+                rendered.insert(relationship.name.clone(), json!({"data": data}));
+            }
+        }
+
+        resource["attributes"] = Value::Object(attributes);
+        if !rendered.is_empty() {
+            resource["relationships"] = Value::Object(rendered);
+        }
+        resource
+    }
+}
+
+impl Relationship {
+    /// render returns resource linkage of related value (id, object or array of them).
+    fn render(&self, value: Value, included: &mut Vec<Value>) -> Value {
+        match value {
+            Value::Null => Value::Null,
+            Value::Array(values) => Value::Array(
+                values
+                    .into_iter()
+                    .map(|value| self.render(value, included))
+                    .collect(),
+            ),
+            // expanded related object goes to included
+            Value::Object(object) => {
+                let identifier = self
+                    .resource
+                    .identifier(object.get(&self.resource.id).unwrap_or(&Value::Null));
+                let resource = self.resource.render(Value::Object(object), &[], included);
+                if !included.contains(&resource) {
+                    included.push(resource);
+                }
+                identifier
+            }
+            id => self.resource.identifier(&id),
+        }
+    }
+
+    /// parse returns related id (or array of ids) of relationship object `{data: ...}`.
+    fn parse(&self, value: &Value) -> Result<Value, crate::Error> {
+        match value.get("data") {
+            Some(Value::Null) => Ok(Value::Null),
+            Some(Value::Array(identifiers)) => identifiers
+                .iter()
+                .map(|identifier| self.resource.parse_id(identifier))
+                .collect::<Result<Vec<_>, _>>()
+                .map(Value::Array),
+            Some(identifier) => self.resource.parse_id(identifier),
+            None => Err(invalid(&format!("relationship `{}` without data", self.name))),
+        }
+    }
+}
+
+impl JsonApi {
+    /// new creates JsonApi for entity E, type is the table name, id is the primary key.
+    pub fn new<E>() -> Result<Self, crate::Error>
+    where
+        E: EntityTrait,
+    {
+        Ok(Self {
+            resource: Resource::new::<E>()?,
+            relationships: Vec::new(),
+        })
+    }
+
+    /// with_type sets resource type (table name by default).
+    pub fn with_type(mut self, resource_type: impl Into<String>) -> Self {
+        self.resource.resource_type = resource_type.into();
+        self
+    }
+
+    /// with_relationship renders attribute (field) as relationship `name` to entity R.
+    /// Field is either foreign key (or array of them) or expanded related object(s), which are added to included.
+    pub fn with_relationship<R>(
+        mut self,
+        name: impl Into<String>,
+        field: impl Into<String>,
+    ) -> Result<Self, crate::Error>
+    where
+        R: EntityTrait,
+    {
+        self.relationships.push(Relationship {
+            name: name.into(),
+            field: field.into(),
+            resource: Resource::new::<R>()?,
+        });
+        Ok(self)
+    }
+
+    /// document returns JSON:API document of serialized object (or array of objects).
+    pub fn document(&self, data: Value) -> Value {
+        let mut included = Vec::new();
+        let data = match data {
+            Value::Array(objects) => Value::Array(
+                objects
+                    .into_iter()
+                    .map(|object| self.resource.render(object, &self.relationships, &mut included))
+                    .collect(),
+            ),
+            object => self.resource.render(object, &self.relationships, &mut included),
+        };
+
+        let mut document = json!({"data": data});
+        if !included.is_empty() {
+            document["included"] = Value::Array(included);
+        }
+        document
+    }
+
+    /// list_document returns JSON:API document of list of objects with links and meta of pagination.
+    pub fn list_document(&self, objects: Value, parts: &Parts) -> Value {
+        let count = objects.as_array().map(Vec::len).unwrap_or_default();
+        let mut document = self.document(objects);
+
+        match parts.extensions.get::<Pagination>() {
+            Some(pagination) => {
+                document["links"] = Value::Object(pagination.links(&parts.uri, count));
+                document["meta"] = Value::Object(pagination.meta());
+            }
+            None => document["links"] = json!({"self": parts.uri.to_string()}),
+        }
+        document
+    }
+
+    /// parse parses JSON:API request document (single resource or array of them) into plain object(s)
+    /// of attributes, id and relationship fields.
+    pub fn parse(&self, body: &[u8]) -> Result<bytes::Bytes, crate::Error> {
+        let document: Value = serde_json::from_slice(body)?;
+        let data = match document.get("data") {
+            Some(Value::Array(resources)) => Value::Array(
+                resources
+                    .iter()
+                    .map(|resource| self.parse_resource(resource))
+                    .collect::<Result<Vec<_>, _>>()?,
+            ),
+            Some(resource) => self.parse_resource(resource)?,
+            None => return Err(invalid("document without data")),
+        };
+        Ok(serde_json::to_vec(&data)?.into())
+    }
+
+    /// parse_resource returns plain object of resource object.
+    fn parse_resource(&self, resource: &Value) -> Result<Value, crate::Error> {
+        if let Some(resource_type) = resource.get("type").and_then(Value::as_str)
+            && resource_type != self.resource.resource_type
+        {
+            return Err(invalid(&format!("unexpected type `{resource_type}`")));
+        }
+
+        let mut object = match resource.get("attributes") {
+            Some(Value::Object(attributes)) => attributes.clone(),
+            Some(_) => return Err(invalid("attributes must be an object")),
+            None => Map::new(),
+        };
+
+        // client generated id
+        if resource.get("id").is_some() {
+            object.insert(self.resource.id.clone(), self.resource.parse_id(resource)?);
+        }
+
+        if let Some(relationships) = resource.get("relationships").and_then(Value::as_object) {
+            for (name, value) in relationships {
+                let relationship = self
+                    .relationships
+                    .iter()
+                    .find(|relationship| &relationship.name == name)
+                    .ok_or_else(|| invalid(&format!("unknown relationship `{name}`")))?;
+                object.insert(relationship.field.clone(), relationship.parse(value)?);
+            }
+        }
+
+        Ok(Value::Object(object))
+    }
+}
+
+/// invalid returns error of invalid JSON:API document.
+fn invalid(message: &str) -> crate::Error {
+    crate::Error::InvalidDocument(message.to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sea_orm::entity::prelude::*;
+
+    mod user {
+        use sea_orm::entity::prelude::*;
+
+        #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+        #[sea_orm(table_name = "user")]
+        pub struct Model {
+            #[sea_orm(primary_key)]
+            pub id: i32,
+        }
+
+        #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+        pub enum Relation {}
+
+        impl ActiveModelBehavior for ActiveModel {}
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+    #[sea_orm(table_name = "order")]
+    pub struct Model {
+        #[sea_orm(primary_key)]
+        pub id: i32,
+        pub user_id: i32,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+
+    fn json_api() -> JsonApi {
+        JsonApi::new::<Entity>()
+            .unwrap()
+            .with_relationship::<user::Entity>("user", "user_id")
+            .unwrap()
+    }
+
+    #[test]
+    fn test_document() {
+        assert_eq!(
+            json_api().document(json!({
+                "id": 1,
+                "user_id": 2,
+                "note": "x",
+                "_links": {"self": {"href": "/api/order/1"}},
+            })),
+            json!({"data": {
+                "type": "order",
+                "id": "1",
+                "attributes": {"note": "x"},
+                "links": {"self": {"href": "/api/order/1"}},
+                "relationships": {"user": {"data": {"type": "user", "id": "2"}}},
+            }})
+        );
+    }
+
+    #[test]
+    fn test_document_included() {
+        let objects = json!([
+            {"id": 1, "user_id": {"id": 2, "username": "joe"}},
+            {"id": 3, "user_id": {"id": 2, "username": "joe"}},
+        ]);
+        let document = json_api().document(objects);
+
+        assert_eq!(document["data"][1]["relationships"]["user"]["data"]["id"], "2");
+        assert_eq!(
+            document["included"],
+            json!([{"type": "user", "id": "2", "attributes": {"username": "joe"}}])
+        );
+    }
+
+    #[test]
+    fn test_parse() {
+        let body = json!({"data": {
+            "type": "order",
+            "id": "7",
+            "attributes": {"note": "x"},
+            "relationships": {"user": {"data": {"type": "user", "id": "2"}}},
+        }});
+        let parsed = json_api().parse(body.to_string().as_bytes()).unwrap();
+
+        assert_eq!(
+            serde_json::from_slice::<serde_json::Value>(&parsed).unwrap(),
+            json!({"id": 7, "note": "x", "user_id": 2})
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        let json_api = json_api();
+        for body in [
+            json!({"attributes": {}}),
+            json!({"data": {"type": "user", "attributes": {}}}),
+            json!({"data": {"type": "order", "relationships": {"other": {"data": null}}}}),
+            json!({"data": {"type": "order", "id": "abc"}}),
+        ] {
+            assert!(json_api.parse(body.to_string().as_bytes()).is_err());
+        }
+    }
+}
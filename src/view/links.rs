@@ -0,0 +1,306 @@
+/*
+ *  The MIT License (MIT)
+ *
+ *  Copyright (c) 2024-2025, Peter Vrba
+ *
+ *  Permission is hereby granted, free of charge, to any person obtaining a copy
+ *  of this software and associated documentation files (the "Software"), to deal
+ *  in the Software without restriction, including without limitation the rights
+ *  to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ *  copies of the Software, and to permit persons to whom the Software is
+ *  furnished to do so, subject to the following conditions:
+ *
+ *  The above copyright notice and this permission notice shall be included in
+ *  all copies or substantial portions of the Software.
+ *
+ *  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ *  IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ *  FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ *  AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ *  LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ *  OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+ *  THE SOFTWARE.
+ *
+ */
+
+use sea_orm::sea_query::sea_value_to_json_value;
+use sea_orm::{EntityTrait, ModelTrait};
+use serde_json::{Map, Value, json};
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+/// LINKS_KEY is the key of links in serialized objects (HAL).
+pub const LINKS_KEY: &str = "_links";
+
+/// Link is a path template of a view, e.g. `/api/user/{id}`.
+/// Path parameters are reverse resolved from column values of the model (column of the same name by default).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Link {
+    template: String,
+    columns: Vec<(String, String)>,
+}
+
+impl Link {
+    /// new creates a link from path template.
+    pub fn new(template: impl Into<String>) -> Self {
+        Self {
+            template: template.into(),
+            columns: Vec::new(),
+        }
+    }
+
+    /// with_column resolves path parameter from given column,
+    /// e.g. `id` of user detail link from `user_id` column of order.
+    pub fn with_column(mut self, param: impl Into<String>, column: impl Into<String>) -> Self {
+        self.columns.push((param.into(), column.into()));
+        self
+    }
+
+    /// template returns path template of the link.
+    pub fn template(&self) -> &str {
+        &self.template
+    }
+
+    /// column returns name of column the path parameter is resolved from.
+    pub fn column<'a>(&'a self, param: &'a str) -> &'a str {
+        self.columns
+            .iter()
+            .rev()
+            .find(|(name, _)| name == param)
+            .map_or(param, |(_, column)| column)
+    }
+
+    /// params returns names of path parameters (e.g. `id` for `/api/user/{id}`).
+    pub fn params(&self) -> Vec<&str> {
+        let mut params = Vec::new();
+        let mut rest = self.template.as_str();
+        while let Some(start) = rest.find('{') {
+            let Some(end) = rest[start..].find('}') else {
+                break;
+            };
+            params.push(rest[start + 1..start + end].trim_start_matches('*'));
+            rest = &rest[start + end + 1..];
+        }
+        params
+    }
+
+    /// reverse returns url with path parameters replaced by values of columns (given function),
+    /// None when any of the values is missing (e.g. null foreign key).
+    pub fn reverse(&self, mut value: impl FnMut(&str) -> Option<String>) -> Option<String> {
+        let mut result = String::with_capacity(self.template.len());
+        let mut rest = self.template.as_str();
+        while let Some(start) = rest.find('{') {
+            let end = start + rest[start..].find('}')?;
+            result.push_str(&rest[..start]);
+            let param = rest[start + 1..end].trim_start_matches('*');
+            result.push_str(&encode(&value(self.column(param))?));
+            rest = &rest[end + 1..];
+        }
+        result.push_str(rest);
+        Some(result)
+    }
+}
+
+/// Convert from &str to Link
+impl From<&str> for Link {
+    fn from(template: &str) -> Self {
+        Link::new(template)
+    }
+}
+
+/// Convert from String to Link
+impl From<String> for Link {
+    fn from(template: String) -> Self {
+        Link::new(template)
+    }
+}
+
+/// Links are named links (self, collection, related resources) added to serialized objects of entity E
+/// as HAL `_links`, e.g. `{"_links": {"self": {"href": "/api/user/1"}}}`.
+#[derive(Debug)]
+pub struct Links<E>
+where
+    E: EntityTrait,
+{
+    links: Vec<(String, Link)>,
+    _phantom: PhantomData<E>,
+}
+
+/// Implementing Clone for Links (entity does not need to be Clone)
+impl<E> Clone for Links<E>
+where
+    E: EntityTrait,
+{
+    fn clone(&self) -> Self {
+        Self {
+            links: self.links.clone(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+/// Implementing Default for Links
+impl<E> Default for Links<E>
+where
+    E: EntityTrait,
+{
+    fn default() -> Self {
+        Self {
+            links: Vec::new(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<E> Links<E>
+where
+    E: EntityTrait,
+{
+    /// push adds named link, all path parameters must be columns of the entity.
+    pub fn push(
+        &mut self,
+        name: impl Into<String>,
+        link: impl Into<Link>,
+    ) -> Result<(), crate::Error> {
+        let link = link.into();
+        if let Some(column) = link
+            .params()
+            .into_iter()
+            .map(|param| link.column(param))
+            .find(|column| E::Column::from_str(column).is_err())
+        {
+            return Err(crate::Error::ImproperlyConfigured(format!(
+                "link `{}` column `{}` does not exist",
+                link.template(),
+                column
+            )));
+        }
+
+        let name = name.into();
+        self.links.retain(|(existing, _)| existing != &name);
+        self.links.push((name, link));
+        Ok(())
+    }
+
+    /// is_empty returns whether there are no links.
+    pub fn is_empty(&self) -> bool {
+        self.links.is_empty()
+    }
+
+    /// render returns links of given model (None when there are no links),
+    /// links with missing values are omitted.
+    pub fn render(&self, model: &E::Model) -> Option<Map<String, Value>> {
+        if self.is_empty() {
+            return None;
+        }
+
+        Some(
+            self.links
+                .iter()
+                .filter_map(|(name, link)| {
+                    let href = link.reverse(|column| {
+                        let column = E::Column::from_str(column).ok()?;
+                        match sea_value_to_json_value(&model.get(column)) {
+                            Value::Null => None,
+                            Value::String(value) => Some(value),
+                            value => Some(value.to_string()),
+                        }
+                    })?;
+                    Some((name.clone(), json!({"href": href})))
+                })
+                .collect(),
+        )
+    }
+
+    /// attach adds rendered links to serialized object.
+    pub fn attach(serialized: &mut Value, links: Option<Map<String, Value>>) {
+        if let (Value::Object(object), Some(links)) = (serialized, links) {
+            object.insert(LINKS_KEY.to_owned(), Value::Object(links));
+        }
+    }
+}
+
+/// encode percent-encodes path parameter value (all but unreserved characters).
+fn encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (byte as char).to_string()
+            }
+            byte => format!("%{byte:02X}"),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sea_orm::entity::prelude::*;
+
+    #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+    #[sea_orm(table_name = "order")]
+    pub struct Model {
+        #[sea_orm(primary_key)]
+        pub id: i32,
+        pub user_id: Option<i32>,
+        pub code: String,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+
+    #[test]
+    fn test_link_reverse() {
+        let link = Link::new("/api/user/{user_id}/order/{id}").with_column("id", "order_id");
+        assert_eq!(link.params(), vec!["user_id", "id"]);
+        assert_eq!(
+            link.reverse(|column| Some(format!("{column} 1"))),
+            Some("/api/user/user_id%201/order/order_id%201".to_owned())
+        );
+        assert_eq!(link.reverse(|_| None), None);
+    }
+
+    #[test]
+    fn test_links() {
+        let mut links = Links::<Entity>::default();
+        links.push("self", "/api/order/{id}").unwrap();
+        links.push("collection", "/api/order").unwrap();
+        links
+            .push(
+                "user",
+                Link::new("/api/user/{id}").with_column("id", "user_id"),
+            )
+            .unwrap();
+        links.push("code", "/api/code/{code}").unwrap();
+        assert!(links.push("other", "/api/other/{other_id}").is_err());
+
+        let mut serialized = json!({"id": 1});
+        let model = Model {
+            id: 1,
+            user_id: Some(2),
+            code: "a/b".to_owned(),
+        };
+        Links::<Entity>::attach(&mut serialized, links.render(&model));
+        assert_eq!(
+            serialized,
+            json!({"id": 1, "_links": {
+                "self": {"href": "/api/order/1"},
+                "collection": {"href": "/api/order"},
+                "user": {"href": "/api/user/2"},
+                "code": {"href": "/api/code/a%2Fb"},
+            }})
+        );
+
+        // null foreign key has no link
+        let links = links
+            .render(&Model {
+                user_id: None,
+                ..model
+            })
+            .unwrap();
+        assert!(!links.contains_key("user"));
+    }
+}
@@ -25,14 +25,19 @@
 
 use crate::Error;
 use crate::view::handler::Handler;
+use crate::view::jsonapi::{JSON_API_CONTENT_TYPE, JsonApi};
+use crate::view::links::{Link, Links};
+use crate::view::registry::{Registration, Routes};
 use axum::Router;
 use axum::http::Method;
 use axum::http::request::Parts;
 use axum::routing::on;
-use quickapi_filter::{SelectFilter, SelectFilterErased};
+use quickapi_filter::{Annotation, Annotations, Scope, SelectFilter, SelectFilterErased};
 use quickapi_http::ModelSerializerJson;
 use quickapi_http::response::Response;
 use quickapi_http::response::key::Key;
+use quickapi_model::Fields;
+use quickapi_permission::Permission;
 use quickapi_view::RouterExt;
 use quickapi_view::ViewTrait;
 use sea_orm::{DatabaseConnection, EntityTrait};
@@ -51,12 +56,20 @@ where
 {
     db: DatabaseConnection,
     pub filters: quickapi_filter::SelectFilters<E, S>,
+    scopes: quickapi_filter::Scopes<E, S>,
+    soft_delete: Option<quickapi_filter::SoftDelete>,
+    permissions: quickapi_permission::Permissions<E, S>,
     when: quickapi_when::WhenViews<S>,
     path: String,
     method: Method,
+    registration: Registration,
     fallback: bool,
     _phantom_data: PhantomData<E>,
     ser: ModelSerializerJson<O>,
+    fields: Fields<E>,
+    annotations: Annotations,
+    json_api: Option<JsonApi>,
+    links: Links<E>,
     wrap_json_key: Option<Key>,
 }
 
@@ -74,11 +87,19 @@ where
             db: self.db.clone(),
             path: self.path.clone(),
             filters: self.filters.clone(),
+            scopes: self.scopes.clone(),
+            soft_delete: self.soft_delete,
+            permissions: self.permissions.clone(),
             when: self.when.clone(),
             _phantom_data: PhantomData,
             method: self.method.clone(),
+            registration: self.registration.clone(),
             fallback: false,
             ser: self.ser.clone(),
+            fields: self.fields.clone(),
+            annotations: self.annotations.clone(),
+            json_api: self.json_api.clone(),
+            links: self.links.clone(),
             wrap_json_key: self.wrap_json_key.clone(),
         }
     }
@@ -101,15 +122,35 @@ where
             db,
             path: path.into(),
             method,
+            registration: Registration::new::<E>("list"),
             filters: quickapi_filter::SelectFilters::new(),
+            scopes: quickapi_filter::Scopes::new(),
+            soft_delete: None,
+            permissions: Default::default(),
             when: quickapi_when::WhenViews::new(),
             _phantom_data: PhantomData,
             fallback: false,
             ser: ModelSerializerJson::<O>::new(),
+            fields: Default::default(),
+            annotations: Default::default(),
+            json_api: None,
+            links: Default::default(),
             wrap_json_key: Some(DEFAULT_JSON_KEY.into()),
         }
     }
 
+    /// with_name sets name of the route in QuickApi routes (`{table}-{kind}` by default, e.g. `user-list`).
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.registration = self.registration.with_name(name);
+        self
+    }
+
+    /// with_routes sets registry of routes the view is recorded into when registered.
+    pub(crate) fn with_routes(mut self, routes: Routes) -> Self {
+        self.registration = self.registration.with_routes(routes);
+        self
+    }
+
     /// with_fallback method to handle fallback logic
     pub fn with_fallback<F>(mut self, _fallback: bool) -> Self {
         self.fallback = true;
@@ -120,7 +161,7 @@ where
     #[allow(unused_mut)]
     pub fn when<F, T, Ser>(
         mut self,
-        _when: impl quickapi_when::When<S, T> + Clone + Send + Sync + 'static,
+        _when: impl quickapi_when::When<S, T> + Clone + Sync + 'static,
         _f: F,
     ) -> Result<Self, Error>
     where
@@ -146,6 +187,66 @@ where
         self
     }
 
+    /// with_scope adds a row-level scope that is applied before filters
+    pub fn with_scope(mut self, scope: impl Scope<E, S> + 'static) -> Self {
+        self.scopes.push(scope);
+        self
+    }
+
+    /// with_soft_delete marks entity as soft deletable by column, soft deleted rows are hidden.
+    pub fn with_soft_delete(mut self, column: &'static str) -> Self {
+        self.soft_delete = Some(quickapi_filter::SoftDelete::new(column));
+        self
+    }
+
+    /// with_deleted includes soft deleted rows in the ListView (usually in when condition for admins).
+    pub fn with_deleted(mut self) -> Self {
+        self.soft_delete = None;
+        self
+    }
+
+    /// with_permission adds a permission that is checked before the query runs
+    pub fn with_permission(mut self, permission: impl Permission<E, S> + 'static) -> Self {
+        self.permissions.push(permission);
+        self
+    }
+
+    /// with_write_only marks columns as write only, they are never returned in response.
+    pub fn with_write_only(mut self, columns: impl IntoIterator<Item = E::Column>) -> Self {
+        self.fields = self.fields.with_write_only(columns);
+        self
+    }
+
+    /// with_annotation adds named SQL expression (or subquery) to the query.
+    /// Its value is returned alongside serialized objects, filters can use it by name.
+    pub fn with_annotation(mut self, annotation: Annotation) -> Self {
+        self.annotations.push(annotation);
+        self
+    }
+
+    /// with_link adds named link (e.g. self, related resource) to each serialized object as HAL `_links`,
+    /// path parameters of the link are resolved from model columns (e.g. `/api/user/{id}`).
+    pub fn with_link(
+        mut self,
+        name: impl Into<String>,
+        link: impl Into<Link>,
+    ) -> Result<Self, Error> {
+        self.links.push(name, link)?;
+        Ok(self)
+    }
+
+    /// link returns link to this view (path template).
+    pub fn link(&self) -> Link {
+        Link::new(self.path.clone())
+    }
+
+    /// with_json_api renders objects as JSON:API document (wrap result key is not used),
+    /// pagination links and meta are added when Paginator filter is used.
+    pub fn with_json_api(mut self, json_api: JsonApi) -> Self {
+        self.json_api = Some(json_api);
+        self
+    }
+
     /// with_serializer method to set a custom serializer
     pub fn with_serializer<Ser>(self) -> ListView<E, S, Ser>
     where
@@ -156,11 +257,19 @@ where
             db: self.db,
             path: self.path,
             method: self.method,
+            registration: self.registration,
             filters: self.filters,
+            scopes: self.scopes,
+            soft_delete: self.soft_delete,
+            permissions: self.permissions,
             when: self.when,
             _phantom_data: PhantomData,
             fallback: self.fallback,
             ser: ModelSerializerJson::<Ser>::new(),
+            fields: self.fields,
+            annotations: self.annotations,
+            json_api: self.json_api,
+            links: self.links,
             wrap_json_key: self.wrap_json_key,
         }
     }
@@ -186,7 +295,15 @@ where
         debug!(method = self.method.to_string(), path = &path, "API list",);
 
         // Register the ListView with the axum router
-        Ok(router.route(&path, on(mf, Handler::new(self.clone()))))
+        self.registration.register(&self.method, &path)?;
+        let handler = Handler::new(self.clone()).with_db(&self.db);
+        Ok(router.route(&path, on(mf, handler)))
+    }
+
+    /// apply_scopes adds scopes registered for the entity (also to when views)
+    fn apply_scopes(&mut self, scopes: &quickapi_filter::ScopeRegistry<S>) {
+        self.scopes.extend(&scopes.get::<E>());
+        self.when.apply_scopes(scopes);
     }
 }
 
@@ -205,39 +322,84 @@ where
         _state: &S,
         _body: &bytes::Bytes,
     ) -> Result<Response, quickapi_view::Error> {
+        // check permissions before the query runs
+        self.permissions.has_permission(_parts, _state).await?;
+
+        // annotations are available to filters by name
+        _parts.extensions.insert(self.annotations.clone());
+
+        // restrict rows by scopes before any user filters
+        let mut query = self.scopes.scope_select(_parts, _state, E::find()).await?;
+
+        // hide soft deleted rows (unless deleted are included)
+        if let Some(soft_delete) = &self.soft_delete {
+            query = soft_delete.exclude_deleted(query)?;
+        }
+
         //
         // create query first and call filters
         //
         let query = self
             .filters
-            .filter_select_boxed(_parts, &_state, E::find())
+            .filter_select_boxed(_parts, _state, query)
             .await
             .map_err(|e| quickapi_view::Error::InternalError(Box::new(e)))?;
 
+        // fetch objects (with annotation values)
+        let rows = if self.annotations.is_empty() {
+            query
+                .all(&self.db)
+                .await?
+                .into_iter()
+                .map(|o| (o, Default::default()))
+                .collect()
+        } else {
+            self.annotations.all(&self.db, query).await?
+        };
+
         // convert objects to the desired type using the serializer
         // If the serializer is not set, we use the default one.
-        let objects = query
-            .all(&self.db)
-            .await?
+        let objects = rows
             .into_iter()
-            .map(|o| {
-                self.ser
+            .map(|(o, values)| {
+                let links = self.links.render(&o);
+                let mut object = self
+                    .ser
                     .serialize_json(o)
-                    .map_err(|e| quickapi_view::Error::InternalError(Box::new(e)))
+                    .map_err(|e| quickapi_view::Error::InternalError(Box::new(e)))?;
+                Annotations::merge(&mut object, values);
+                Links::<E>::attach(&mut object, links);
+                Ok(object)
             })
-            .collect::<Result<Vec<_>, _>>()?;
+            .collect::<Result<Vec<_>, quickapi_view::Error>>()?;
 
-        // prepare array of objects
+        // prepare array of objects (without write only fields)
         let mut objects = serde_json::Value::Array(objects);
+        self.fields.strip_output(&mut objects);
+
+        // render JSON:API document (with pagination) or wrap the JSON response in a key
+        let (objects, content_type) = match (&self.json_api, self.wrap_json_key.clone()) {
+            (Some(json_api), _) => (
+                json_api.list_document(objects, _parts),
+                JSON_API_CONTENT_TYPE,
+            ),
+            (None, Some(key)) => (
+                serde_json::Value::Object(serde_json::Map::from_iter(vec![(key.into(), objects)])),
+                "application/json",
+            ),
+            (None, None) => (objects, "application/json"),
+        };
 
-        // should we wrap the JSON response in a key?
-        if let Some(key) = self.wrap_json_key.clone() {
-            objects =
-                serde_json::Value::Object(serde_json::Map::from_iter(vec![(key.into(), objects)]));
+        // entity tag of the whole list, client may already have it
+        let etag = quickapi_http::etag::hash(objects.to_string().as_bytes());
+        if quickapi_http::etag::if_none_match(&_parts.headers, &etag) {
+            return Ok(quickapi_http::etag::not_modified(&etag));
         }
 
         // return the response with the serialized objects
-        Ok(Response::new(objects))
+        Ok(Response::new(objects)
+            .with_header(axum::http::header::CONTENT_TYPE, content_type)
+            .with_header(axum::http::header::ETAG, &etag))
     }
 
     /// get_when_views method to retrieve views based on conditions
@@ -249,7 +411,7 @@ where
         self.when
             .get_views(_parts, _state)
             .await
-            .map_err(|e| quickapi_view::Error::InternalError(Box::new(e)))
+            .map_err(Into::into)
     }
 
     /// has_fallback method to check if the view has a fallback (used when when conditions are not met)
@@ -282,3 +444,155 @@ where
         self.wrap_json_key.clone()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quickapi_filter::common::ordering::Ordering;
+    use sea_orm::entity::prelude::*;
+    use sea_orm::sea_query::{Alias, Expr, Func, Query};
+    use sea_orm::{ActiveValue, ConnectionTrait, Database, Schema};
+    use serde_json::json;
+
+    #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, serde::Serialize)]
+    #[sea_orm(table_name = "user")]
+    pub struct Model {
+        #[sea_orm(primary_key)]
+        pub id: i32,
+        pub username: String,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+
+    // db creates in memory database with users of given usernames
+    async fn db(usernames: &[&str]) -> DatabaseConnection {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        let backend = db.get_database_backend();
+        db.execute(backend.build(&Schema::new(backend).create_table_from_entity(Entity)))
+            .await
+            .unwrap();
+        Entity::insert_many(usernames.iter().map(|username| ActiveModel {
+            username: ActiveValue::Set((*username).to_owned()),
+            ..Default::default()
+        }))
+        .exec(&db)
+        .await
+        .unwrap();
+        db
+    }
+
+    #[tokio::test]
+    async fn test_annotations_sqlite() {
+        let db = db(&["joe", "anna"]).await;
+        db.execute_unprepared(
+            r#"CREATE TABLE "order" ("id" integer PRIMARY KEY, "user_id" integer NOT NULL);
+               INSERT INTO "order" ("user_id") VALUES (1), (2), (2);"#,
+        )
+        .await
+        .unwrap();
+
+        let view = ListView::<Entity, (), Model>::new(db, "/user", Method::GET)
+            .with_annotation(Annotation::subquery::<i64>(
+                "order_count",
+                Query::select()
+                    .expr(Expr::col(Alias::new("id")).count())
+                    .from(Alias::new("order"))
+                    .and_where(
+                        Expr::col((Alias::new("order"), Alias::new("user_id")))
+                            .equals((Entity, Column::Id)),
+                    )
+                    .to_owned(),
+            ))
+            .with_annotation(Annotation::new::<String>(
+                "upper_name",
+                Func::upper(Expr::col(Column::Username)),
+            ))
+            .with_filter(Ordering::new([Column::Id]).with_annotations(["order_count"]));
+
+        let (mut parts, _) = axum::http::Request::builder()
+            .uri("/user?ordering=-order_count")
+            .body(())
+            .unwrap()
+            .into_parts();
+        let response = view
+            .handle_view(&mut parts, &(), &bytes::Bytes::new())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            response.data(),
+            &json!({"objects": [
+                {"id": 2, "username": "anna", "order_count": 2, "upper_name": "ANNA"},
+                {"id": 1, "username": "joe", "order_count": 1, "upper_name": "JOE"},
+            ]})
+        );
+    }
+
+    #[tokio::test]
+    async fn test_json_api_sqlite() {
+        let db = db(&["joe", "anna", "bob"]).await;
+
+        let view = ListView::<Entity, (), Model>::new(db, "/user", Method::GET)
+            .with_filter(
+                quickapi_filter::common::paginator::Paginator::default().with_default_limit(2),
+            )
+            .with_json_api(JsonApi::new::<Entity>().unwrap());
+
+        let (mut parts, _) = axum::http::Request::builder()
+            .uri("/user?page=2")
+            .body(())
+            .unwrap()
+            .into_parts();
+        let response = view
+            .handle_view(&mut parts, &(), &bytes::Bytes::new())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            response.data(),
+            &json!({
+                "data": [{"type": "user", "id": "3", "attributes": {"username": "bob"}}],
+                "links": {
+                    "self": "/user?page=2&limit=2",
+                    "first": "/user?page=1&limit=2",
+                    "prev": "/user?page=1&limit=2",
+                },
+                "meta": {"page": 2, "limit": 2},
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_links_sqlite() {
+        let view = ListView::<Entity, (), Model>::new(db(&["joe"]).await, "/user", Method::GET);
+        let view = view
+            .clone()
+            .with_link("self", "/user/{id}")
+            .unwrap()
+            .with_link("collection", view.link())
+            .unwrap();
+        assert!(view.clone().with_link("other", "/other/{other_id}").is_err());
+
+        let (mut parts, _) = axum::http::Request::builder()
+            .uri("/user")
+            .body(())
+            .unwrap()
+            .into_parts();
+        let response = view
+            .handle_view(&mut parts, &(), &bytes::Bytes::new())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            response.data(),
+            &json!({"objects": [{
+                "id": 1,
+                "username": "joe",
+                "_links": {"self": {"href": "/user/1"}, "collection": {"href": "/user"}},
+            }]})
+        );
+    }
+}
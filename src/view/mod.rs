@@ -23,12 +23,26 @@
  *
  */
 
+pub mod action;
+pub mod aggregate;
+pub mod bulk;
+pub mod count;
 pub mod create;
 pub mod delete;
 pub mod detail;
 pub mod error;
+pub mod export;
 pub mod handler;
+pub mod import;
+pub mod jsonapi;
+pub mod links;
 pub mod list;
+pub mod nested;
 pub mod prefix;
+pub mod registry;
+pub mod restore;
+pub mod transaction;
+pub mod update;
+pub mod upsert;
 
 pub use error::Error;
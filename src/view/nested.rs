@@ -0,0 +1,470 @@
+/*
+ *  The MIT License (MIT)
+ *
+ *  Copyright (c) 2024-2025, Peter Vrba
+ *
+ *  Permission is hereby granted, free of charge, to any person obtaining a copy
+ *  of this software and associated documentation files (the "Software"), to deal
+ *  in the Software without restriction, including without limitation the rights
+ *  to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ *  copies of the Software, and to permit persons to whom the Software is
+ *  furnished to do so, subject to the following conditions:
+ *
+ *  The above copyright notice and this permission notice shall be included in
+ *  all copies or substantial portions of the Software.
+ *
+ *  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ *  IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ *  FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ *  AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ *  LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ *  OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+ *  THE SOFTWARE.
+ *
+ */
+
+use crate::Error;
+
+use crate::quickapi::{DeletePathMethod, DetailPathMethod, QuickApi, UpdatePathMethod};
+use crate::view::create::CreateView;
+use crate::view::delete::DeleteView;
+use crate::view::detail::DetailView;
+use crate::view::list::ListView;
+use crate::view::transaction::Transaction;
+use crate::view::update::UpdateView;
+use axum::http::Method;
+use axum::http::request::Parts;
+use quickapi_filter::{Scope, SoftDelete};
+use quickapi_lookup::{Lookup, PrimaryKey};
+use sea_orm::sea_query::{Expr, Iden};
+use sea_orm::{
+    DatabaseConnection, EntityTrait, Identity, ModelTrait, QueryFilter, Related, Select,
+};
+use std::marker::PhantomData;
+use std::str::FromStr;
+use std::sync::Arc;
+
+/// Nested creates views for child entity C that live under parent entity P
+/// (e.g. `/users/{user_id}/orders`). Parent is resolved by its lookup (404 when not found),
+/// child queries are scoped by relation foreign key and created children get it set.
+pub struct Nested<'a, P, C, S>
+where
+    P: EntityTrait,
+    C: EntityTrait,
+    S: Clone + Send + Sync + 'static,
+{
+    api: &'a QuickApi<S>,
+    path: String,
+    scope: ParentScope<P, C, S>,
+}
+
+impl<'a, P, C, S> Nested<'a, P, C, S>
+where
+    P: EntityTrait,
+    C: EntityTrait + Related<P>,
+    S: Clone + Send + Sync + 'static,
+{
+    /// new creates Nested for parent path (with parent lookup parameter) and child path.
+    /// Parent is looked up by primary key from the last path parameter of parent path.
+    pub(crate) fn new(
+        api: &'a QuickApi<S>,
+        parent_path: impl Into<String>,
+        path: impl Into<String>,
+    ) -> Result<Self, Error> {
+        let parent_path = parent_path.into();
+        let param = last_path_param(&parent_path).ok_or(Error::ImproperlyConfigured(format!(
+            "parent path `{}` has no path parameter",
+            parent_path
+        )))?;
+        let (column, parent_column) = foreign_key::<P, C>()?;
+
+        Ok(Self {
+            api,
+            path: format!("{}{}", parent_path, path.into()),
+            scope: ParentScope {
+                db: api.db.clone(),
+                lookup: Arc::new(PrimaryKey::Path(param)),
+                scopes: api.scopes.get::<P>(),
+                soft_delete: api.soft_delete::<P>().map(SoftDelete::new),
+                column,
+                parent_column,
+            },
+        })
+    }
+
+    /// with_parent_lookup sets the lookup of parent (e.g. by slug instead of primary key).
+    pub fn with_parent_lookup(mut self, lookup: impl Lookup<P, S> + 'static) -> Self {
+        self.scope.lookup = Arc::new(lookup);
+        self
+    }
+
+    /// with_parent_scope adds a row-level scope to parent lookup, children of parents
+    /// outside of the scope are not found.
+    pub fn with_parent_scope(mut self, scope: impl Scope<P, S> + 'static) -> Self {
+        self.scope.scopes.push(scope);
+        self
+    }
+
+    /// list creates a ListView of children of the parent.
+    pub fn list(&self) -> Result<ListView<C, S, C::Model>, Error>
+    where
+        <C as EntityTrait>::Model: serde::Serialize + Clone + Send + Sync + 'static,
+    {
+        Ok(self
+            .api
+            .list::<C>((self.path.clone(), Method::GET))?
+            .with_scope(self.scope.clone()))
+    }
+
+    /// create creates a CreateView of children, foreign key to parent is set on created child.
+    pub fn create(&self) -> Result<CreateView<C, S, C::Model>, Error>
+    where
+        <C as EntityTrait>::Model: serde::Serialize
+            + for<'de> serde::Deserialize<'de>
+            + Into<C::Model>
+            + Sync
+            + Send
+            + 'static,
+    {
+        Ok(self
+            .api
+            .create::<C>((self.path.clone(), Method::POST))?
+            .with_scope(self.scope.clone()))
+    }
+
+    /// detail creates a DetailView of child, path is relative to the nested path (e.g. `/{id}`).
+    pub fn detail(
+        &self,
+        path_method: impl Into<DetailPathMethod>,
+        lookup: impl Lookup<C, S> + 'static,
+    ) -> Result<DetailView<C, S, C::Model>, Error>
+    where
+        <C as EntityTrait>::Model: serde::Serialize + Clone + Send + Sync + 'static,
+    {
+        let pm = path_method.into();
+        Ok(self
+            .api
+            .detail::<C>((self.child_path(&pm.path), pm.method), lookup)?
+            .with_scope(self.scope.clone()))
+    }
+
+    /// update creates an UpdateView of child, path is relative to the nested path (e.g. `/{id}`).
+    pub fn update(
+        &self,
+        path_method: impl Into<UpdatePathMethod>,
+        lookup: impl Lookup<C, S> + 'static,
+    ) -> Result<UpdateView<C, S>, Error>
+    where
+        <C as EntityTrait>::Model:
+            serde::Serialize + for<'de> serde::Deserialize<'de> + Clone + Send + Sync + 'static,
+    {
+        let pm = path_method.into();
+        Ok(self
+            .api
+            .update::<C>((self.child_path(&pm.path), pm.method), lookup)?
+            .with_scope(self.scope.clone()))
+    }
+
+    /// delete creates a DeleteView of child, path is relative to the nested path (e.g. `/{id}`).
+    pub fn delete(
+        &self,
+        path_method: impl Into<DeletePathMethod>,
+    ) -> Result<DeleteView<C, S>, Error>
+    where
+        <C as EntityTrait>::Model: serde::Serialize,
+    {
+        let pm = path_method.into();
+        Ok(self
+            .api
+            .delete::<C>((self.child_path(&pm.path), pm.method))?
+            .with_scope(self.scope.clone()))
+    }
+
+    /// child_path returns full path of child view.
+    fn child_path(&self, path: &str) -> String {
+        format!("{}{}", self.path, path)
+    }
+}
+
+/// ParentScope scopes child rows by foreign key to parent resolved by lookup.
+/// Parent is resolved once per request, when it's not found request responds with 404.
+pub struct ParentScope<P, C, S>
+where
+    P: EntityTrait,
+    C: EntityTrait,
+    S: Clone + Send + Sync + 'static,
+{
+    db: DatabaseConnection,
+    lookup: Arc<dyn Lookup<P, S>>,
+    scopes: quickapi_filter::Scopes<P, S>,
+    soft_delete: Option<SoftDelete>,
+    column: C::Column,
+    parent_column: P::Column,
+}
+
+impl<P, C, S> Clone for ParentScope<P, C, S>
+where
+    P: EntityTrait,
+    C: EntityTrait,
+    S: Clone + Send + Sync + 'static,
+{
+    fn clone(&self) -> Self {
+        Self {
+            db: self.db.clone(),
+            lookup: self.lookup.clone(),
+            scopes: self.scopes.clone(),
+            soft_delete: self.soft_delete,
+            column: self.column,
+            parent_column: self.parent_column,
+        }
+    }
+}
+
+/// ParentValue caches value of parent column referenced by foreign key in request extensions.
+struct ParentValue<P>(sea_orm::Value, PhantomData<P>);
+
+impl<P> Clone for ParentValue<P> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone(), PhantomData)
+    }
+}
+
+impl<P, C, S> ParentScope<P, C, S>
+where
+    P: EntityTrait,
+    C: EntityTrait,
+    S: Clone + Send + Sync + 'static,
+{
+    /// parent_value resolves parent and returns value referenced by child foreign key.
+    async fn parent_value(
+        &self,
+        parts: &mut Parts,
+        state: &S,
+    ) -> Result<sea_orm::Value, quickapi_filter::Error> {
+        if let Some(value) = parts.extensions.get::<ParentValue<P>>() {
+            return Ok(value.0.clone());
+        }
+
+        let mut query = self.scopes.scope_select(parts, state, P::find()).await?;
+        if let Some(soft_delete) = &self.soft_delete {
+            query = soft_delete.exclude_deleted(query)?;
+        }
+
+        let query = self
+            .lookup
+            .lookup(parts, state, query)
+            .await
+            .map_err(|err| match err {
+                quickapi_lookup::Error::ImproperlyConfigured(message) => {
+                    quickapi_filter::Error::ImproperlyConfigured(message)
+                }
+                err => quickapi_filter::Error::NotFound(err.to_string()),
+            })?;
+
+        // read parent in the view transaction (when view runs in one)
+        let parent = match parts.extensions.get::<Transaction>().cloned() {
+            Some(transaction) => query.one(&*transaction).await?,
+            None => query.one(&self.db).await?,
+        };
+
+        let value = parent
+            .ok_or_else(|| quickapi_filter::Error::NotFound("parent not found".to_owned()))?
+            .get(self.parent_column);
+
+        parts
+            .extensions
+            .insert(ParentValue::<P>(value.clone(), PhantomData));
+
+        Ok(value)
+    }
+}
+
+#[async_trait::async_trait]
+impl<P, C, S> Scope<C, S> for ParentScope<P, C, S>
+where
+    P: EntityTrait,
+    C: EntityTrait,
+    S: Clone + Send + Sync + 'static,
+{
+    async fn scope_select(
+        &self,
+        parts: &mut Parts,
+        state: &S,
+        query: Select<C>,
+    ) -> Result<Select<C>, quickapi_filter::Error> {
+        let value = self.parent_value(parts, state).await?;
+        Ok(query.filter(Expr::col((C::default(), self.column)).eq(value)))
+    }
+
+    async fn scope_model(
+        &self,
+        parts: &mut Parts,
+        state: &S,
+        model: C::Model,
+    ) -> Result<C::Model, quickapi_filter::Error> {
+        let value = self.parent_value(parts, state).await?;
+        let mut model = model;
+        model.set(self.column, value);
+        Ok(model)
+    }
+}
+
+/// foreign_key returns child foreign key column and parent column it references
+/// (from relation of child to parent).
+fn foreign_key<P, C>() -> Result<(C::Column, P::Column), Error>
+where
+    P: EntityTrait,
+    C: EntityTrait + Related<P>,
+{
+    let mut relation = <C as Related<P>>::to();
+    // relation defined from parent side (has one / has many), child holds the foreign key
+    if relation.is_owner {
+        relation = relation.rev();
+    }
+
+    let (Identity::Unary(from), Identity::Unary(to)) = (&relation.from_col, &relation.to_col)
+    else {
+        return Err(Error::ImproperlyConfigured(
+            "nested views support only single column foreign keys".to_owned(),
+        ));
+    };
+
+    let (from, to) = (Iden::to_string(&**from), Iden::to_string(&**to));
+    let column = C::Column::from_str(&from).map_err(|_| {
+        Error::ImproperlyConfigured(format!("foreign key column `{}` not found", from))
+    })?;
+    let parent_column = P::Column::from_str(&to)
+        .map_err(|_| Error::ImproperlyConfigured(format!("parent column `{}` not found", to)))?;
+
+    Ok((column, parent_column))
+}
+
+/// last_path_param returns name of the last path parameter (e.g. `user_id` for `/users/{user_id}`).
+fn last_path_param(path: &str) -> Option<String> {
+    let start = path.rfind('{')?;
+    let end = start + path[start..].find('}')?;
+    let name = path[start + 1..end].trim_start_matches('*');
+    (!name.is_empty()).then(|| name.to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sea_orm::{DbBackend, QueryTrait};
+
+    mod user {
+        use sea_orm::entity::prelude::*;
+
+        #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+        #[sea_orm(table_name = "user")]
+        pub struct Model {
+            #[sea_orm(primary_key)]
+            pub id: i32,
+        }
+
+        #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+        pub enum Relation {
+            #[sea_orm(has_many = "super::order::Entity")]
+            Orders,
+        }
+
+        impl Related<super::order::Entity> for Entity {
+            fn to() -> RelationDef {
+                Relation::Orders.def()
+            }
+        }
+
+        impl ActiveModelBehavior for ActiveModel {}
+    }
+
+    mod order {
+        use sea_orm::entity::prelude::*;
+
+        #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+        #[sea_orm(table_name = "order")]
+        pub struct Model {
+            #[sea_orm(primary_key)]
+            pub id: i32,
+            pub user_id: i32,
+        }
+
+        #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+        pub enum Relation {
+            #[sea_orm(
+                belongs_to = "super::user::Entity",
+                from = "Column::UserId",
+                to = "super::user::Column::Id"
+            )]
+            User,
+        }
+
+        impl Related<super::user::Entity> for Entity {
+            fn to() -> RelationDef {
+                Relation::User.def()
+            }
+        }
+
+        impl ActiveModelBehavior for ActiveModel {}
+    }
+
+    #[test]
+    fn test_last_path_param() {
+        assert_eq!(
+            last_path_param("/users/{user_id}"),
+            Some("user_id".to_owned())
+        );
+        assert_eq!(
+            last_path_param("/{org}/users/{user_id}/"),
+            Some("user_id".to_owned())
+        );
+        assert_eq!(last_path_param("/users"), None);
+        assert_eq!(last_path_param("/users/{}"), None);
+    }
+
+    #[test]
+    fn test_foreign_key() {
+        let (column, parent_column) = foreign_key::<user::Entity, order::Entity>().unwrap();
+        assert!(matches!(column, order::Column::UserId));
+        assert!(matches!(parent_column, user::Column::Id));
+
+        // user does not hold foreign key to order
+        assert!(foreign_key::<order::Entity, user::Entity>().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_parent_scope() {
+        let scope = ParentScope::<user::Entity, order::Entity, ()> {
+            db: DatabaseConnection::Disconnected,
+            lookup: Arc::new(PrimaryKey::Path("user_id".to_owned())),
+            scopes: Default::default(),
+            soft_delete: None,
+            column: order::Column::UserId,
+            parent_column: user::Column::Id,
+        };
+
+        // parent is already resolved for the request
+        let (mut parts, _) = axum::http::Request::builder()
+            .uri("/users/42/orders")
+            .body(())
+            .unwrap()
+            .into_parts();
+        parts
+            .extensions
+            .insert(ParentValue::<user::Entity>(42.into(), PhantomData));
+
+        let query = scope
+            .scope_select(&mut parts, &(), order::Entity::find())
+            .await
+            .unwrap();
+        assert_eq!(
+            query.build(DbBackend::Postgres).to_string(),
+            r#"SELECT "order"."id", "order"."user_id" FROM "order" WHERE "order"."user_id" = 42"#
+        );
+
+        let model = scope
+            .scope_model(&mut parts, &(), order::Model { id: 1, user_id: 0 })
+            .await
+            .unwrap();
+        assert_eq!(model.user_id, 42);
+    }
+}
@@ -25,6 +25,7 @@
 
 use axum::Router;
 use axum::http::request::Parts;
+use quickapi_filter::{Scope, ScopeRegistry};
 use quickapi_http::Response;
 use quickapi_view::{Error, RouterExt, ViewTrait};
 
@@ -36,6 +37,7 @@ where
 {
     pub(crate) path: String,
     pub(crate) views: Vec<Box<dyn RouterExt<S> + Send + Sync>>,
+    pub(crate) scopes: ScopeRegistry<S>,
 }
 
 /// Prefix implements Clone to allow cloning of the struct.
@@ -51,6 +53,7 @@ where
                 .iter()
                 .map(|v| dyn_clone::clone_box(&**v))
                 .collect(),
+            scopes: self.scopes.clone(),
         }
     }
 }
@@ -65,6 +68,7 @@ where
         Prefix {
             path: path.as_ref().to_string(),
             views: Vec::new(),
+            scopes: Default::default(),
         }
     }
 
@@ -77,6 +81,16 @@ where
         self.views.push(Box::new(view));
         self
     }
+
+    /// with_scope registers a row-level scope for entity E.
+    /// Scope is applied to all views for the entity under this prefix (also nested prefixes).
+    pub fn with_scope<E>(mut self, scope: impl Scope<E, S> + 'static) -> Self
+    where
+        E: sea_orm::EntityTrait,
+    {
+        self.scopes.push::<E>(scope);
+        self
+    }
 }
 
 /// Prefix implements the ViewTrait for handling requests under the specified path prefix.
@@ -125,6 +139,10 @@ where
         let _x = _span.enter();
 
         for view in &self.views {
+            // apply prefix scopes to the view (on a clone, so prefix can be registered multiple times)
+            let mut view = dyn_clone::clone_box(&**view);
+            view.apply_scopes(&self.scopes);
+
             // Register each view with the router using the prefix
             let updated_router =
                 view.register_router_with_prefix(router.clone(), &prefixed_path)?;
@@ -133,4 +151,9 @@ where
 
         Ok(router)
     }
+
+    /// apply_scopes adds scopes from parent prefix, they are applied to views on registration.
+    fn apply_scopes(&mut self, scopes: &ScopeRegistry<S>) {
+        self.scopes.extend(scopes);
+    }
 }
@@ -0,0 +1,230 @@
+/*
+ *  The MIT License (MIT)
+ *
+ *  Copyright (c) 2024-2025, Peter Vrba
+ *
+ *  Permission is hereby granted, free of charge, to any person obtaining a copy
+ *  of this software and associated documentation files (the "Software"), to deal
+ *  in the Software without restriction, including without limitation the rights
+ *  to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ *  copies of the Software, and to permit persons to whom the Software is
+ *  furnished to do so, subject to the following conditions:
+ *
+ *  The above copyright notice and this permission notice shall be included in
+ *  all copies or substantial portions of the Software.
+ *
+ *  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ *  IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ *  FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ *  AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ *  LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ *  OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+ *  THE SOFTWARE.
+ *
+ */
+
+use crate::view::links::Link;
+use axum::http::Method;
+use sea_orm::EntityTrait;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+/// Route is a registered view: name, method, full path (with prefixes), entity table and view kind.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize)]
+pub struct Route {
+    pub name: String,
+    pub method: String,
+    pub path: String,
+    pub table: String,
+    pub kind: &'static str,
+}
+
+impl Route {
+    /// link returns link to the route (path template).
+    pub fn link(&self) -> Link {
+        Link::new(self.path.clone())
+    }
+}
+
+/// Routes is the registry of views registered to router, shared by QuickApi and its views.
+/// Views record themselves when they are registered (so the path includes prefixes).
+#[derive(Clone, Debug, Default)]
+pub struct Routes(Arc<RwLock<Vec<Route>>>);
+
+impl Routes {
+    /// all returns all registered routes in order of registration.
+    pub fn all(&self) -> Vec<Route> {
+        self.0.read().unwrap_or_else(|err| err.into_inner()).clone()
+    }
+
+    /// get returns first route registered under given name.
+    pub fn get(&self, name: &str) -> Option<Route> {
+        self.0
+            .read()
+            .unwrap_or_else(|err| err.into_inner())
+            .iter()
+            .find(|route| route.name == name)
+            .cloned()
+    }
+
+    /// reverse returns url of named route with path parameters replaced by given values.
+    pub fn reverse<K, V>(
+        &self,
+        name: &str,
+        params: impl IntoIterator<Item = (K, V)>,
+    ) -> Result<String, crate::Error>
+    where
+        K: Into<String>,
+        V: ToString,
+    {
+        let route = self.get(name).ok_or_else(|| {
+            crate::Error::ImproperlyConfigured(format!("route `{}` is not registered", name))
+        })?;
+        let params: HashMap<String, String> = params
+            .into_iter()
+            .map(|(key, value)| (key.into(), value.to_string()))
+            .collect();
+
+        route
+            .link()
+            .reverse(|param| params.get(param).cloned())
+            .ok_or_else(|| {
+                crate::Error::ImproperlyConfigured(format!(
+                    "missing parameters of route `{}` ({})",
+                    name, route.path
+                ))
+            })
+    }
+
+    /// push records a route, explicitly named routes must be unique (same route may be registered again).
+    pub(crate) fn push(&self, route: Route, named: bool) -> Result<(), quickapi_view::Error> {
+        let mut routes = self.0.write().unwrap_or_else(|err| err.into_inner());
+        if routes.contains(&route) {
+            return Ok(());
+        }
+        if named && routes.iter().any(|existing| existing.name == route.name) {
+            return Err(quickapi_view::Error::ImproperlyConfigured(format!(
+                "route `{}` is already registered",
+                route.name
+            )));
+        }
+        routes.push(route);
+        Ok(())
+    }
+}
+
+/// Registration holds what view records into Routes when it's registered to router.
+/// Default name of the route is `{table}-{kind}`, e.g. `user-list`.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Registration {
+    routes: Option<Routes>,
+    name: Option<String>,
+    table: String,
+    kind: &'static str,
+}
+
+impl Registration {
+    /// new creates registration of view of given kind for entity E.
+    pub(crate) fn new<E>(kind: &'static str) -> Self
+    where
+        E: EntityTrait,
+    {
+        Self {
+            routes: None,
+            name: None,
+            table: E::default().table_name().to_owned(),
+            kind,
+        }
+    }
+
+    /// with_routes sets registry the view is recorded into.
+    pub(crate) fn with_routes(mut self, routes: Routes) -> Self {
+        self.routes = Some(routes);
+        self
+    }
+
+    /// with_name sets name of the route.
+    pub(crate) fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// register records route of the view with full path (no-op without registry).
+    pub(crate) fn register(&self, method: &Method, path: &str) -> Result<(), quickapi_view::Error> {
+        let Some(routes) = &self.routes else {
+            return Ok(());
+        };
+
+        let route = Route {
+            name: self
+                .name
+                .clone()
+                .unwrap_or_else(|| format!("{}-{}", self.table, self.kind)),
+            method: method.to_string(),
+            path: path.to_owned(),
+            table: self.table.clone(),
+            kind: self.kind,
+        };
+        routes.push(route, self.name.is_some())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sea_orm::entity::prelude::*;
+
+    #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+    #[sea_orm(table_name = "user")]
+    pub struct Model {
+        #[sea_orm(primary_key)]
+        pub id: i32,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+
+    #[test]
+    fn test_routes() {
+        let routes = Routes::default();
+        let detail = Registration::new::<Entity>("detail").with_routes(routes.clone());
+        detail.register(&Method::GET, "/api/user/{id}").unwrap();
+        // registering same route again is fine
+        detail.register(&Method::GET, "/api/user/{id}").unwrap();
+
+        let named = Registration::new::<Entity>("list")
+            .with_routes(routes.clone())
+            .with_name("users");
+        named.register(&Method::GET, "/api/user").unwrap();
+        assert!(named.register(&Method::GET, "/api/v2/user").is_err());
+
+        assert_eq!(
+            routes.all(),
+            vec![
+                Route {
+                    name: "user-detail".into(),
+                    method: "GET".into(),
+                    path: "/api/user/{id}".into(),
+                    table: "user".into(),
+                    kind: "detail",
+                },
+                Route {
+                    name: "users".into(),
+                    method: "GET".into(),
+                    path: "/api/user".into(),
+                    table: "user".into(),
+                    kind: "list",
+                },
+            ]
+        );
+
+        assert_eq!(
+            routes.reverse("user-detail", [("id", 42)]).unwrap(),
+            "/api/user/42"
+        );
+        assert!(routes.reverse("user-detail", [("other", 42)]).is_err());
+        assert!(routes.reverse("unknown", [("id", 42)]).is_err());
+    }
+}
@@ -0,0 +1,466 @@
+/*
+ *  The MIT License (MIT)
+ *
+ *  Copyright (c) 2024-2025, Peter Vrba
+ *
+ *  Permission is hereby granted, free of charge, to any person obtaining a copy
+ *  of this software and associated documentation files (the "Software"), to deal
+ *  in the Software without restriction, including without limitation the rights
+ *  to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ *  copies of the Software, and to permit persons to whom the Software is
+ *  furnished to do so, subject to the following conditions:
+ *
+ *  The above copyright notice and this permission notice shall be included in
+ *  all copies or substantial portions of the Software.
+ *
+ *  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ *  IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ *  FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ *  AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ *  LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ *  OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+ *  THE SOFTWARE.
+ *
+ */
+
+use crate::view::handler::Handler;
+use crate::view::registry::{Registration, Routes};
+use axum::Router;
+use axum::http::Method;
+use axum::http::request::Parts;
+use axum::routing::on;
+use quickapi_filter::{Scope, SoftDelete};
+use quickapi_http::response::{Key, Response};
+use quickapi_lookup::Lookup;
+use quickapi_permission::Permission;
+use quickapi_view::{Error, ViewTrait, as_method_filter};
+use quickapi_when::WhenViews;
+use sea_orm::{ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter, TransactionTrait};
+use serde_json::json;
+use std::marker::PhantomData;
+use std::sync::Arc;
+use tracing::debug;
+
+const DEFAULT_JSON_KEY: &str = "object";
+
+/// RestoreView is a view that restores soft deleted entity (see DeleteMode::Soft).
+#[derive(Clone)]
+pub struct RestoreView<E, S>
+where
+    E: EntityTrait,
+    S: Clone + Send + Sync + 'static,
+{
+    db: DatabaseConnection,
+    path: String,
+    method: Method,
+    registration: Registration,
+    soft_delete: SoftDelete,
+    when: WhenViews<S>,
+    lookup: Arc<dyn Lookup<E, S>>,
+    scopes: quickapi_filter::Scopes<E, S>,
+    permissions: quickapi_permission::Permissions<E, S>,
+    fallback: bool,
+    transaction: bool,
+    wrap_json_key: Option<Key>,
+    _phantom_data: PhantomData<(E, S)>,
+}
+
+impl<E, S> RestoreView<E, S>
+where
+    E: EntityTrait,
+    S: Clone + Send + Sync + 'static,
+{
+    /// new creates a new RestoreView instance for given soft delete column.
+    pub(crate) fn new(
+        db: DatabaseConnection,
+        path: impl Into<String>,
+        method: Method,
+        column: &'static str,
+        lookup: impl Lookup<E, S> + 'static,
+    ) -> Self {
+        Self {
+            db,
+            path: path.into(),
+            method,
+            registration: Registration::new::<E>("restore"),
+            soft_delete: SoftDelete::new(column),
+            when: Default::default(),
+            lookup: Arc::new(lookup),
+            scopes: quickapi_filter::Scopes::new(),
+            permissions: Default::default(),
+            fallback: false,
+            transaction: false,
+            wrap_json_key: Some(DEFAULT_JSON_KEY.into()),
+            _phantom_data: PhantomData,
+        }
+    }
+
+    /// with_name sets name of the route in QuickApi routes (`{table}-{kind}` by default, e.g. `user-restore`).
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.registration = self.registration.with_name(name);
+        self
+    }
+
+    /// with_routes sets registry of routes the view is recorded into when registered.
+    pub(crate) fn with_routes(mut self, routes: Routes) -> Self {
+        self.registration = self.registration.with_routes(routes);
+        self
+    }
+
+    /// with_transaction runs the whole RestoreView (callbacks and queries) in one database transaction.
+    /// Transaction is committed on success response, rolled back otherwise.
+    pub fn with_transaction(mut self, transaction: bool) -> Self {
+        self.transaction = transaction;
+        self
+    }
+
+    /// with_fallback sets the fallback for the RestoreView.
+    pub fn with_fallback(mut self, fallback: bool) -> Self {
+        self.fallback = fallback;
+        self
+    }
+
+    /// with_lookup sets the lookup for the RestoreView.
+    pub fn with_lookup(mut self, lookup: impl Lookup<E, S> + 'static) -> Self {
+        self.lookup = Arc::new(lookup);
+        self
+    }
+
+    /// with_soft_delete sets the soft delete column for the RestoreView.
+    pub fn with_soft_delete(mut self, column: &'static str) -> Self {
+        self.soft_delete = SoftDelete::new(column);
+        self
+    }
+
+    /// with_scope adds a row-level scope to the RestoreView.
+    pub fn with_scope(mut self, scope: impl Scope<E, S> + 'static) -> Self {
+        self.scopes.push(scope);
+        self
+    }
+
+    /// with_permission adds a permission to the RestoreView.
+    /// Permission is checked before the query runs, object permission before the object is restored.
+    pub fn with_permission(mut self, permission: impl Permission<E, S> + 'static) -> Self {
+        self.permissions.push(permission);
+        self
+    }
+
+    /// when adds a condition to the RestoreView.
+    pub fn when<F, T>(
+        mut self,
+        _when: impl quickapi_when::When<S, T> + Clone + Sync + 'static,
+        _f: F,
+    ) -> Result<Self, crate::Error>
+    where
+        F: Fn(RestoreView<E, S>) -> Result<RestoreView<E, S>, crate::Error>,
+        T: Sync + Send + 'static,
+        <E as EntityTrait>::Model: serde::Serialize + Sync,
+    {
+        let mut clone = self.clone();
+        clone.when = Default::default();
+        let _result = _f(clone)?;
+        self.when.add_when(_when, _result);
+        Ok(self)
+    }
+}
+
+/// RestoreView implementation of the request pipeline.
+impl<E, S> RestoreView<E, S>
+where
+    E: EntityTrait,
+    S: Clone + Send + Sync + 'static,
+    <E as EntityTrait>::Model: serde::Serialize + Sync,
+{
+    /// handle runs the view pipeline on given connection (database or transaction).
+    async fn handle<C>(
+        &self,
+        conn: &C,
+        _parts: &mut Parts,
+        _state: &S,
+        _body: &bytes::Bytes,
+    ) -> Result<Response, Error>
+    where
+        C: ConnectionTrait + TransactionTrait,
+    {
+        let mut parts = _parts.clone();
+
+        // check permissions before the query runs
+        self.permissions.has_permission(&mut parts, _state).await?;
+
+        // restrict rows by scopes, only soft deleted objects can be restored
+        let query = self
+            .scopes
+            .scope_select(&mut parts, _state, E::find())
+            .await?;
+        let query = self.soft_delete.only_deleted(query)?;
+
+        // prepare lookup
+        let query = self
+            .lookup
+            .lookup(&mut parts, _state, query)
+            .await
+            .map_err(|e| Error::InternalError(Box::new(e)))?;
+
+        let Some(object) = query.one(conn).await? else {
+            return Ok(Response::new(json!({
+                "error": "Not Found",
+                "message": "The requested resource was not found."
+            }))
+            .with_status(axum::http::StatusCode::NOT_FOUND));
+        };
+
+        // check object permissions before it's restored
+        self.permissions
+            .has_object_permission(&mut parts, _state, &object)
+            .await?;
+
+        let condition = quickapi_model::primary_key_condition::<E>(&object);
+
+        E::update_many()
+            .col_expr(
+                self.soft_delete.resolve_column::<E>()?,
+                self.soft_delete.restored_value::<E>()?,
+            )
+            .filter(condition.clone())
+            .exec(conn)
+            .await?;
+
+        // read restored object back
+        let restored = E::find()
+            .filter(condition)
+            .one(conn)
+            .await?
+            .ok_or(Error::InternalError(
+                "restored object not found".to_string().into(),
+            ))?;
+
+        let serialized =
+            serde_json::to_value(restored).map_err(|e| Error::InternalError(Box::new(e)))?;
+
+        let object = match &self.wrap_json_key {
+            Some(key) => serde_json::Value::Object(serde_json::Map::from_iter(vec![(
+                Into::<String>::into(key.clone()),
+                serialized,
+            )])),
+            None => serialized,
+        };
+
+        Ok(Response::new(object))
+    }
+}
+
+/// Implement the ViewTrait for RestoreView
+#[async_trait::async_trait]
+impl<E, S> ViewTrait<S> for RestoreView<E, S>
+where
+    E: EntityTrait,
+    S: Clone + Send + Sync + 'static,
+    <E as EntityTrait>::Model: serde::Serialize + Sync,
+{
+    async fn handle_view(
+        &self,
+        _parts: &mut Parts,
+        _state: &S,
+        _body: &bytes::Bytes,
+    ) -> Result<Response, Error> {
+        if !self.transaction {
+            return self.handle(&self.db, _parts, _state, _body).await;
+        }
+
+        // run the whole pipeline in one transaction, rolled back on any error
+        let transaction = crate::view::transaction::begin(&self.db, _parts).await?;
+        let response = match self.handle(&*transaction, _parts, _state, _body).await {
+            Ok(response) => response,
+            Err(err) => {
+                crate::view::transaction::discard(transaction, _parts);
+                return Err(err);
+            }
+        };
+        crate::view::transaction::finish(transaction, _parts, response).await
+    }
+
+    /// get_when_views returns a list of when views for the RestoreView.
+    async fn get_when_views<'a>(
+        &'a self,
+        _parts: &'a mut Parts,
+        _state: &'a S,
+    ) -> Result<Vec<&'a (dyn ViewTrait<S> + Send + Sync)>, Error> {
+        self.when
+            .get_views(_parts, _state)
+            .await
+            .map_err(Into::into)
+    }
+
+    fn has_fallback(&self) -> bool {
+        self.fallback
+    }
+}
+
+/// Implement the RouterExt trait for RestoreView
+impl<E, S> quickapi_view::RouterExt<S> for RestoreView<E, S>
+where
+    E: EntityTrait,
+    S: Clone + Send + Sync + 'static,
+    <E as EntityTrait>::Model: serde::Serialize + Sync,
+{
+    fn register_router_with_prefix(
+        &self,
+        router: Router<S>,
+        prefix: &str,
+    ) -> Result<Router<S>, Error> {
+        let mf = as_method_filter(&self.method)?;
+        let path = format!("{}{}", prefix, self.path);
+
+        debug!(
+            method = self.method.to_string(),
+            path = &path,
+            "API restore",
+        );
+
+        self.registration.register(&self.method, &path)?;
+        let handler = Handler::new(self.clone()).with_db(&self.db);
+        Ok(router.route(&path, on(mf, handler)))
+    }
+
+    /// apply_scopes adds scopes registered for the entity (also to when views)
+    fn apply_scopes(&mut self, scopes: &quickapi_filter::ScopeRegistry<S>) {
+        self.scopes.extend(&scopes.get::<E>());
+        self.when.apply_scopes(scopes);
+    }
+}
+
+/// Implementing ViewWrapResultTrait for RestoreView to handle JSON response wrapping
+impl<E, S> quickapi_view::ViewWrapResultTrait<S> for RestoreView<E, S>
+where
+    E: EntityTrait,
+    S: Clone + Send + Sync + 'static,
+    <E as EntityTrait>::Model: serde::Serialize + Sync,
+{
+    /// wrap_result_key method to set a custom key for the JSON response
+    fn wrap_result_key(mut self, key: impl Into<Key>) -> Self {
+        self.wrap_json_key = Some(key.into());
+        self
+    }
+
+    /// no_wrap_result_key method to disable wrapping the JSON response in a key
+    fn no_wrap_result_key(mut self) -> Self {
+        self.wrap_json_key = None;
+        self
+    }
+
+    /// get_wrap_result_key method to retrieve the key used for wrapping the JSON response
+    fn get_wrap_result_key(&self) -> Option<Key> {
+        self.wrap_json_key.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::StatusCode;
+    use axum::http::request::Parts;
+    use axum_test::TestServer;
+    use quickapi_view::RouterExt;
+    use sea_orm::entity::prelude::*;
+    use sea_orm::{Database, Schema};
+    use serde_json::json;
+
+    #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, serde::Serialize)]
+    #[sea_orm(table_name = "user")]
+    pub struct Model {
+        #[sea_orm(primary_key)]
+        pub id: i32,
+        pub username: String,
+        pub deleted: bool,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+
+    // NoRoot denies restoring user named root
+    #[derive(Clone)]
+    struct NoRoot;
+
+    #[async_trait::async_trait]
+    impl quickapi_permission::Permission<Entity, ()> for NoRoot {
+        async fn has_permission(
+            &self,
+            _parts: &mut Parts,
+            _state: &(),
+        ) -> Result<(), quickapi_permission::Error> {
+            Ok(())
+        }
+
+        async fn has_object_permission(
+            &self,
+            _parts: &mut Parts,
+            _state: &(),
+            model: &Model,
+        ) -> Result<(), quickapi_permission::Error> {
+            match model.username.as_str() {
+                "root" => Err(quickapi_permission::Error::Denied(
+                    "root is reserved".to_owned(),
+                )),
+                _ => Ok(()),
+            }
+        }
+    }
+
+    async fn deleted(db: &DatabaseConnection, id: i32) -> bool {
+        Entity::find_by_id(id)
+            .one(db)
+            .await
+            .unwrap()
+            .map(|user| user.deleted)
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_restore() {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        let backend = db.get_database_backend();
+        db.execute(backend.build(&Schema::new(backend).create_table_from_entity(Entity)))
+            .await
+            .unwrap();
+        db.execute_unprepared(
+            r#"INSERT INTO "user" ("username", "deleted") VALUES
+                   ('root', true), ('joe', true), ('anna', false)"#,
+        )
+        .await
+        .unwrap();
+        let api = crate::new::<()>(db.clone()).with_soft_delete::<Entity>("deleted");
+        let router = api
+            .restore::<Entity>("/user/{id}/restore", "id")
+            .unwrap()
+            .with_permission(NoRoot)
+            .register_router(axum::Router::new())
+            .unwrap();
+        let server = TestServer::new(router).unwrap();
+
+        // object permission is checked before the object is restored
+        server
+            .post("/user/1/restore")
+            .await
+            .assert_status(StatusCode::FORBIDDEN);
+        assert!(deleted(&db, 1).await);
+
+        let response = server.post("/user/2/restore").await;
+        response.assert_status_ok();
+        response.assert_json(&json!({"object": {"id": 2, "username": "joe", "deleted": false}}));
+        assert!(!deleted(&db, 2).await);
+
+        // only soft deleted objects can be restored
+        server
+            .post("/user/3/restore")
+            .await
+            .assert_status(StatusCode::NOT_FOUND);
+
+        // entity must be marked as soft deletable
+        assert!(
+            crate::new::<()>(db)
+                .restore::<Entity>("/user/{id}/restore", "id")
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,118 @@
+/*
+ *  The MIT License (MIT)
+ *
+ *  Copyright (c) 2024-2025, Peter Vrba
+ *
+ *  Permission is hereby granted, free of charge, to any person obtaining a copy
+ *  of this software and associated documentation files (the "Software"), to deal
+ *  in the Software without restriction, including without limitation the rights
+ *  to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ *  copies of the Software, and to permit persons to whom the Software is
+ *  furnished to do so, subject to the following conditions:
+ *
+ *  The above copyright notice and this permission notice shall be included in
+ *  all copies or substantial portions of the Software.
+ *
+ *  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ *  IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ *  FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ *  AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ *  LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ *  OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+ *  THE SOFTWARE.
+ *
+ */
+
+use axum::extract::FromRequestParts;
+use axum::http::StatusCode;
+use axum::http::request::Parts;
+use quickapi_http::Response;
+use quickapi_view::Error;
+use sea_orm::{DatabaseConnection, DatabaseTransaction, TransactionTrait};
+use std::ops::Deref;
+use std::sync::Arc;
+
+/// Transaction is the database transaction of the request when view runs in transaction (see with_transaction).
+/// It can be used as an extractor in callbacks, so they write in the same transaction as the view.
+#[derive(Clone)]
+pub struct Transaction(Arc<DatabaseTransaction>);
+
+impl Deref for Transaction {
+    type Target = DatabaseTransaction;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// Transaction extractor, rejected when view does not run in transaction.
+impl<S> FromRequestParts<S> for Transaction
+where
+    S: Send + Sync,
+{
+    type Rejection = (StatusCode, &'static str);
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        parts.extensions.get::<Transaction>().cloned().ok_or((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "view does not run in transaction",
+        ))
+    }
+}
+
+/// begin starts transaction and makes it available to callbacks in request extensions.
+pub(crate) async fn begin(
+    db: &DatabaseConnection,
+    parts: &mut Parts,
+) -> Result<Transaction, Error> {
+    let transaction = Transaction(Arc::new(db.begin().await?));
+    parts.extensions.insert(transaction.clone());
+    Ok(transaction)
+}
+
+/// finish commits transaction when view responded with success, otherwise it's rolled back.
+pub(crate) async fn finish(
+    transaction: Transaction,
+    parts: &mut Parts,
+    response: Response,
+) -> Result<Response, Error> {
+    let transaction = take(transaction, parts)?;
+
+    if response.status().is_success() {
+        transaction.commit().await?;
+    } else {
+        transaction.rollback().await?;
+    }
+
+    Ok(response)
+}
+
+/// discard rolls back transaction when view failed with error (rollback happens on drop).
+pub(crate) fn discard(transaction: Transaction, parts: &mut Parts) {
+    parts.extensions.remove::<Transaction>();
+    drop(transaction);
+}
+
+/// take removes transaction from request extensions and takes its ownership.
+fn take(transaction: Transaction, parts: &mut Parts) -> Result<DatabaseTransaction, Error> {
+    parts.extensions.remove::<Transaction>();
+
+    Arc::try_unwrap(transaction.0).map_err(|_| {
+        Error::ImproperlyConfigured("transaction is still used after the view finished".to_string())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_extractor_without_transaction() {
+        let (mut parts, _) = axum::http::Request::new(()).into_parts();
+        let rejection = Transaction::from_request_parts(&mut parts, &())
+            .await
+            .err()
+            .unwrap();
+        assert_eq!(rejection.0, StatusCode::INTERNAL_SERVER_ERROR);
+    }
+}
@@ -0,0 +1,862 @@
+/*
+ *  The MIT License (MIT)
+ *
+ *  Copyright (c) 2024-2025, Peter Vrba
+ *
+ *  Permission is hereby granted, free of charge, to any person obtaining a copy
+ *  of this software and associated documentation files (the "Software"), to deal
+ *  in the Software without restriction, including without limitation the rights
+ *  to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ *  copies of the Software, and to permit persons to whom the Software is
+ *  furnished to do so, subject to the following conditions:
+ *
+ *  The above copyright notice and this permission notice shall be included in
+ *  all copies or substantial portions of the Software.
+ *
+ *  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ *  IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ *  FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ *  AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ *  LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ *  OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+ *  THE SOFTWARE.
+ *
+ */
+
+use crate::view::handler::{Handler, check_when_max_body_size};
+use crate::view::jsonapi::{JSON_API_CONTENT_TYPE, JsonApi};
+use crate::view::links::{Link, Links};
+use crate::view::registry::{Registration, Routes};
+use axum::Router;
+use axum::http::Method;
+use axum::http::request::Parts;
+use axum::routing::on;
+use quickapi_filter::Scope;
+use quickapi_http::Response;
+use quickapi_http::etag::ETag;
+use quickapi_http::response::Key;
+use quickapi_http::serializer::OutputSerializerJson;
+use quickapi_lookup::Lookup;
+use quickapi_model::{Fields, ModelCallbackErased, ReadOnlyMode, ReplaceModel, UpdateModelTrait};
+use quickapi_permission::Permission;
+use quickapi_view::{Error, ViewTrait, as_method_filter};
+use sea_orm::{
+    ActiveModelBehavior, ConnectionTrait, DatabaseConnection, EntityTrait, IntoActiveModel,
+    TransactionTrait,
+};
+use serde_json::json;
+use std::marker::PhantomData;
+use std::sync::Arc;
+use tracing::debug;
+
+const DEFAULT_JSON_KEY: &str = "object";
+
+/// UpdateView is a view for updating single entity found by lookup.
+/// Request body is deserialized into serializer that updates the model (see UpdateModelTrait).
+#[derive(Clone)]
+pub struct UpdateView<E, S, Ser = ReplaceModel<E>>
+where
+    E: EntityTrait,
+    S: Clone + Send + Sync + 'static,
+    Ser: UpdateModelTrait<E> + Clone + 'static,
+    <E as EntityTrait>::Model: Send + Sync + 'static,
+{
+    db: DatabaseConnection,
+    path: String,
+    method: Method,
+    registration: Registration,
+    when: quickapi_when::WhenViews<S>,
+    lookup: Arc<dyn Lookup<E, S>>,
+    before_save: quickapi_model::ModelCallbacks<E, S>,
+    after_save: quickapi_model::ModelCallbacks<E, S>,
+    scopes: quickapi_filter::Scopes<E, S>,
+    soft_delete: Option<quickapi_filter::SoftDelete>,
+    etag: ETag,
+    permissions: quickapi_permission::Permissions<E, S>,
+    fallback: bool,
+    transaction: bool,
+    output: OutputSerializerJson<E::Model>,
+    fields: Fields<E>,
+    max_body_size: usize,
+    json_api: Option<JsonApi>,
+    links: Links<E>,
+    wrap_json_key: Option<Key>,
+    _phantom_data: PhantomData<(E, S, Ser)>,
+}
+
+impl<E, S, Ser> UpdateView<E, S, Ser>
+where
+    E: EntityTrait,
+    S: Clone + Send + Sync + 'static,
+    Ser: UpdateModelTrait<E> + Clone + 'static,
+    <E as EntityTrait>::Model: Send + Sync + 'static,
+{
+    /// new creates a new UpdateView instance.
+    pub(crate) fn new(
+        db: DatabaseConnection,
+        path: impl Into<String>,
+        method: Method,
+        lookup: impl Lookup<E, S> + 'static,
+    ) -> Self
+    where
+        <E as EntityTrait>::Model: serde::Serialize,
+    {
+        Self {
+            db,
+            path: path.into(),
+            method,
+            registration: Registration::new::<E>("update"),
+            when: Default::default(),
+            lookup: Arc::new(lookup),
+            before_save: Default::default(),
+            after_save: Default::default(),
+            scopes: quickapi_filter::Scopes::new(),
+            soft_delete: None,
+            etag: Default::default(),
+            permissions: Default::default(),
+            fallback: false,
+            transaction: false,
+            output: Default::default(),
+            fields: Default::default(),
+            max_body_size: crate::quickapi::DEFAULT_MAX_BODY_SIZE,
+            json_api: None,
+            links: Default::default(),
+            wrap_json_key: Some(DEFAULT_JSON_KEY.into()),
+            _phantom_data: PhantomData,
+        }
+    }
+
+    /// with_name sets name of the route in QuickApi routes (`{table}-{kind}` by default, e.g. `user-update`).
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.registration = self.registration.with_name(name);
+        self
+    }
+
+    /// with_routes sets registry of routes the view is recorded into when registered.
+    pub(crate) fn with_routes(mut self, routes: Routes) -> Self {
+        self.registration = self.registration.with_routes(routes);
+        self
+    }
+
+    /// with_serializer sets custom update serializer for the UpdateView.
+    pub fn with_serializer<Serializer>(self) -> UpdateView<E, S, Serializer>
+    where
+        Serializer: UpdateModelTrait<E> + Clone + 'static,
+    {
+        UpdateView {
+            db: self.db,
+            path: self.path,
+            method: self.method,
+            registration: self.registration,
+            when: self.when,
+            lookup: self.lookup,
+            before_save: self.before_save,
+            after_save: self.after_save,
+            scopes: self.scopes,
+            soft_delete: self.soft_delete,
+            etag: self.etag,
+            permissions: self.permissions,
+            fallback: false,
+            transaction: self.transaction,
+            output: self.output,
+            fields: self.fields,
+            max_body_size: self.max_body_size,
+            json_api: self.json_api,
+            links: self.links,
+            wrap_json_key: self.wrap_json_key,
+            _phantom_data: PhantomData,
+        }
+    }
+
+    /// with_output_serializer sets serializer of updated object in response (update serializer is kept).
+    pub fn with_output_serializer<O>(mut self) -> Self
+    where
+        O: From<E::Model> + serde::Serialize,
+    {
+        self.output = OutputSerializerJson::new::<O>();
+        self
+    }
+
+    /// with_read_only marks columns as read only, client cannot change them (original values are kept).
+    pub fn with_read_only(mut self, columns: impl IntoIterator<Item = E::Column>) -> Self {
+        self.fields = self.fields.with_read_only(columns);
+        self
+    }
+
+    /// with_write_only marks columns as write only, they are never returned in response.
+    pub fn with_write_only(mut self, columns: impl IntoIterator<Item = E::Column>) -> Self {
+        self.fields = self.fields.with_write_only(columns);
+        self
+    }
+
+    /// with_read_only_mode sets whether read only fields sent by client are ignored (default)
+    /// or rejected with 400.
+    pub fn with_read_only_mode(mut self, mode: ReadOnlyMode) -> Self {
+        self.fields = self.fields.with_read_only_mode(mode);
+        self
+    }
+
+    /// with_lookup sets the lookup for the UpdateView.
+    pub fn with_lookup(mut self, lookup: impl Lookup<E, S> + 'static) -> Self {
+        self.lookup = Arc::new(lookup);
+        self
+    }
+
+    /// with_before_save sets a before save handler for the UpdateView.
+    pub fn with_before_save<T>(
+        mut self,
+        before_save: impl quickapi_model::ModelCallback<E, S, T> + Clone + Send + Sync + 'static,
+    ) -> Self
+    where
+        T: Send + Sync + 'static,
+    {
+        self.before_save.push(before_save);
+        self
+    }
+
+    /// remove all before save handlers from the UpdateView.
+    pub fn clear_before_save(mut self) -> Self {
+        self.before_save.clear();
+        self
+    }
+
+    /// with_after_save sets an after save handler for the UpdateView.
+    /// It's called with the updated object, returned object is used in response.
+    pub fn with_after_save<T>(
+        mut self,
+        after_save: impl quickapi_model::ModelCallback<E, S, T> + Clone + Send + Sync + 'static,
+    ) -> Self
+    where
+        T: Send + Sync + 'static,
+    {
+        self.after_save.push(after_save);
+        self
+    }
+
+    /// remove all after save handlers from the UpdateView.
+    pub fn clear_after_save(mut self) -> Self {
+        self.after_save.clear();
+        self
+    }
+
+    /// with_scope adds a row-level scope to the UpdateView.
+    /// Objects out of scope are not found, scoped columns are force-set on the updated instance.
+    pub fn with_scope(mut self, scope: impl Scope<E, S> + 'static) -> Self {
+        self.scopes.push(scope);
+        self
+    }
+
+    /// with_soft_delete marks entity as soft deletable by column, soft deleted rows cannot be updated.
+    pub fn with_soft_delete(mut self, column: &'static str) -> Self {
+        self.soft_delete = Some(quickapi_filter::SoftDelete::new(column));
+        self
+    }
+
+    /// with_etag_column sets integer version column used for ETag (by default ETag is hash of serialized object).
+    /// Version column is incremented on every update, update of row modified meanwhile fails with 412.
+    pub fn with_etag_column(mut self, column: &'static str) -> Self {
+        self.etag = ETag::Column(column);
+        self
+    }
+
+    /// with_permission adds a permission to the UpdateView.
+    /// Object permission is checked on the looked up object and on the updated instance before it's saved.
+    pub fn with_permission(mut self, permission: impl Permission<E, S> + 'static) -> Self {
+        self.permissions.push(permission);
+        self
+    }
+
+    /// with_max_body_size sets the maximum size of request body (0 means no limit).
+    pub fn with_max_body_size(mut self, max_body_size: usize) -> Self {
+        self.max_body_size = max_body_size;
+        self
+    }
+
+    /// with_link adds named link (e.g. self, related resource) to serialized object as HAL `_links`,
+    /// path parameters of the link are resolved from model columns (e.g. `/api/user/{id}`).
+    pub fn with_link(
+        mut self,
+        name: impl Into<String>,
+        link: impl Into<Link>,
+    ) -> Result<Self, crate::Error> {
+        self.links.push(name, link)?;
+        Ok(self)
+    }
+
+    /// with_json_api parses JSON:API request document and renders updated object as JSON:API document.
+    pub fn with_json_api(mut self, json_api: JsonApi) -> Self {
+        self.json_api = Some(json_api);
+        self
+    }
+
+    /// with_transaction runs the whole UpdateView (callbacks and queries) in one database transaction.
+    /// Transaction is committed on success response, rolled back otherwise.
+    pub fn with_transaction(mut self, transaction: bool) -> Self {
+        self.transaction = transaction;
+        self
+    }
+
+    /// with_fallback sets a fallback UpdateView that will be used if when conditions are not met.
+    pub fn with_fallback(mut self, fallback: bool) -> Self {
+        self.fallback = fallback;
+        self
+    }
+
+    /// when adds a condition to the UpdateView.
+    /// Closure receives clone of the view (without when conditions) and can change
+    /// serializer, before save callbacks and other settings.
+    pub fn when<F, T, Serializer>(
+        mut self,
+        _when: impl quickapi_when::When<S, T> + Clone + Sync + 'static,
+        _f: F,
+    ) -> Result<Self, crate::Error>
+    where
+        Serializer: UpdateModelTrait<E> + Clone + 'static,
+        <E as EntityTrait>::Model: IntoActiveModel<E::ActiveModel> + serde::Serialize,
+        <E as EntityTrait>::ActiveModel: ActiveModelBehavior + Send,
+        F: Fn(UpdateView<E, S, Ser>) -> Result<UpdateView<E, S, Serializer>, crate::Error>,
+        T: Sync + Send + 'static,
+    {
+        let mut clone = self.clone();
+        clone.when = Default::default();
+        let _result = _f(clone)?;
+        check_when_max_body_size(self.max_body_size, _result.max_body_size)?;
+        self.when.add_when(_when, _result);
+        Ok(self)
+    }
+}
+
+/// UpdateView implementation of the request pipeline.
+impl<E, S, Ser> UpdateView<E, S, Ser>
+where
+    E: EntityTrait,
+    S: Clone + Send + Sync + 'static,
+    Ser: UpdateModelTrait<E> + Clone + 'static,
+    <E as EntityTrait>::Model:
+        IntoActiveModel<E::ActiveModel> + serde::Serialize + Send + Sync + 'static,
+    <E as EntityTrait>::ActiveModel: ActiveModelBehavior + Send,
+{
+    /// handle runs the view pipeline on given connection (database or transaction).
+    async fn handle<C>(
+        &self,
+        conn: &C,
+        _parts: &mut Parts,
+        _state: &S,
+        _body: &bytes::Bytes,
+    ) -> Result<Response, Error>
+    where
+        C: ConnectionTrait + TransactionTrait,
+    {
+        let mut parts = _parts.clone();
+
+        // check permissions before anything else
+        self.permissions.has_permission(&mut parts, _state).await?;
+
+        // JSON:API request document is parsed into plain object
+        let _body = &match &self.json_api {
+            Some(json_api) => match json_api.parse(_body) {
+                Ok(body) => body,
+                Err(e) => {
+                    return Ok(Response::new(json!({
+                        "error": "Bad Request",
+                        "message": e.to_string(),
+                    }))
+                    .with_status(axum::http::StatusCode::BAD_REQUEST));
+                }
+            },
+            None => _body.clone(),
+        };
+
+        // reject read only fields sent by client (when configured)
+        if let Err(e) = self.fields.check_input(_body) {
+            return Ok(Response::new(json!({
+                "error": "Bad Request",
+                "message": e.to_string(),
+            }))
+            .with_status(axum::http::StatusCode::BAD_REQUEST));
+        }
+
+        // deserialize the body into the update serializer
+        let serializer: Ser = match serde_json::from_slice(_body) {
+            Ok(serializer) => serializer,
+            Err(e) => {
+                return Ok(Response::new(json!({
+                    "error": "Bad Request",
+                    "message": e.to_string(),
+                }))
+                .with_status(axum::http::StatusCode::BAD_REQUEST));
+            }
+        };
+
+        // restrict rows by scopes, soft deleted rows cannot be updated
+        let mut query = self
+            .scopes
+            .scope_select(&mut parts, _state, E::find())
+            .await?;
+        if let Some(soft_delete) = &self.soft_delete {
+            query = soft_delete.exclude_deleted(query)?;
+        }
+
+        // prepare lookup
+        let query = self
+            .lookup
+            .lookup(&mut parts, _state, query)
+            .await
+            .map_err(|e| Error::InternalError(Box::new(e)))?;
+
+        let Some(original) = query.one(conn).await? else {
+            return Ok(Response::new(json!({
+                "error": "Not Found",
+                "message": "The requested resource was not found."
+            }))
+            .with_status(axum::http::StatusCode::NOT_FOUND));
+        };
+
+        // check object permissions on the looked up object
+        self.permissions
+            .has_object_permission(&mut parts, _state, &original)
+            .await?;
+
+        // object must not be modified since client has read it (same representation as in response)
+        if parts.headers.contains_key(axum::http::header::IF_MATCH) {
+            let serialized = self.represent(original.clone())?;
+            let etag = self
+                .etag
+                .compute::<E>(&original, &serialized)
+                .map_err(|e| Error::InternalError(Box::new(e)))?;
+            if !quickapi_http::etag::if_match(&parts.headers, &etag) {
+                return Ok(quickapi_http::etag::precondition_failed());
+            }
+        }
+
+        // update the model by serializer
+        let instance = match serializer.update(original.clone()) {
+            Ok(instance) => instance,
+            Err(e) => {
+                return Ok(Response::new(json!({
+                    "error": "Bad Request",
+                    "message": e.to_string(),
+                }))
+                .with_status(axum::http::StatusCode::BAD_REQUEST));
+            }
+        };
+
+        // apply before_save handlers
+        let received = instance.clone();
+        let instance = self
+            .before_save
+            .call(&mut parts, _state, instance)
+            .await
+            .map_err(|e| Error::InternalError(Box::new(e)))?;
+
+        // force scoped columns, so client cannot move object outside of its scope
+        let instance = self
+            .scopes
+            .scope_model(&mut parts, _state, instance)
+            .await?;
+
+        // client cannot change read only columns
+        let instance = self.fields.keep_read_only(&original, &received, instance);
+
+        // check object permissions on the instance before it's saved
+        self.permissions
+            .has_object_permission(&mut parts, _state, &instance)
+            .await?;
+
+        // update the instance in the database, versioned row only when it was not modified meanwhile
+        let active = quickapi_model::update_active_model::<E>(&original, instance);
+        let version = self
+            .etag
+            .version_column::<E>()
+            .map_err(|e| Error::InternalError(Box::new(e)))?;
+        let updated = match version {
+            Some(version) => {
+                match quickapi_model::update_versioned::<E, _>(conn, &original, active, version)
+                    .await?
+                {
+                    Some(updated) => updated,
+                    None => return Ok(quickapi_http::etag::precondition_failed()),
+                }
+            }
+            None => E::update(active).exec(conn).await?,
+        };
+
+        // apply after_save handlers
+        let updated = self
+            .after_save
+            .call(&mut parts, _state, updated)
+            .await
+            .map_err(|e| Error::InternalError(Box::new(e)))?;
+
+        let serialized = self.represent(updated.clone())?;
+        let etag = self
+            .etag
+            .compute::<E>(&updated, &serialized)
+            .map_err(|e| Error::InternalError(Box::new(e)))?;
+
+        if let Some(json_api) = &self.json_api {
+            return Ok(Response::new(json_api.document(serialized))
+                .with_header(axum::http::header::CONTENT_TYPE, JSON_API_CONTENT_TYPE)
+                .with_header(axum::http::header::ETAG, &etag));
+        }
+
+        let object = match &self.wrap_json_key {
+            Some(key) => serde_json::Value::Object(serde_json::Map::from_iter(vec![(
+                Into::<String>::into(key.clone()),
+                serialized,
+            )])),
+            None => serialized,
+        };
+
+        Ok(Response::new(object).with_header(axum::http::header::ETAG, &etag))
+    }
+
+    /// represent serializes object as it's returned in response (output serializer, links, write only
+    /// fields stripped), ETag is computed from it, so it matches ETag of DetailView with same output.
+    fn represent(&self, object: E::Model) -> Result<serde_json::Value, Error> {
+        let links = self.links.render(&object);
+        let mut serialized = self
+            .output
+            .serialize_json(object)
+            .map_err(|e| Error::InternalError(Box::new(e)))?;
+        Links::<E>::attach(&mut serialized, links);
+        self.fields.strip_output(&mut serialized);
+        Ok(serialized)
+    }
+}
+
+/// Implement the ViewTrait for UpdateView, which defines how the view handles requests.
+#[async_trait::async_trait]
+impl<E, S, Ser> ViewTrait<S> for UpdateView<E, S, Ser>
+where
+    E: EntityTrait,
+    S: Clone + Send + Sync + 'static,
+    Ser: UpdateModelTrait<E> + Clone + 'static,
+    <E as EntityTrait>::Model:
+        IntoActiveModel<E::ActiveModel> + serde::Serialize + Send + Sync + 'static,
+    <E as EntityTrait>::ActiveModel: ActiveModelBehavior + Send,
+{
+    async fn handle_view(
+        &self,
+        _parts: &mut Parts,
+        _state: &S,
+        _body: &bytes::Bytes,
+    ) -> Result<Response, Error> {
+        if !self.transaction {
+            return self.handle(&self.db, _parts, _state, _body).await;
+        }
+
+        // run the whole pipeline in one transaction, rolled back on any error
+        let transaction = crate::view::transaction::begin(&self.db, _parts).await?;
+        let response = match self.handle(&*transaction, _parts, _state, _body).await {
+            Ok(response) => response,
+            Err(err) => {
+                crate::view::transaction::discard(transaction, _parts);
+                return Err(err);
+            }
+        };
+        crate::view::transaction::finish(transaction, _parts, response).await
+    }
+
+    /// get_when_views returns a vector of views that should be executed when the UpdateView is called.
+    async fn get_when_views<'a>(
+        &'a self,
+        _parts: &'a mut Parts,
+        _state: &'a S,
+    ) -> Result<Vec<&'a (dyn ViewTrait<S> + Send + Sync)>, Error> {
+        self.when
+            .get_views(_parts, _state)
+            .await
+            .map_err(Into::into)
+    }
+
+    /// has_fallback returns true if the UpdateView has a fallback defined (if when does not matches).
+    fn has_fallback(&self) -> bool {
+        self.fallback
+    }
+
+    /// max_body_size returns the maximum size of request body for the UpdateView.
+    fn max_body_size(&self) -> Option<usize> {
+        Some(self.max_body_size)
+    }
+}
+
+/// UpdateView implementation for registering the view with an axum router.
+impl<E, S, Ser> quickapi_view::RouterExt<S> for UpdateView<E, S, Ser>
+where
+    E: EntityTrait,
+    S: Clone + Send + Sync + 'static,
+    Ser: UpdateModelTrait<E> + Clone + 'static,
+    <E as EntityTrait>::Model:
+        IntoActiveModel<E::ActiveModel> + serde::Serialize + Send + Sync + 'static,
+    <E as EntityTrait>::ActiveModel: ActiveModelBehavior + Send,
+{
+    fn register_router_with_prefix(
+        &self,
+        router: Router<S>,
+        prefix: &str,
+    ) -> Result<Router<S>, Error> {
+        let mf = as_method_filter(&self.method)?;
+        let path = format!("{}{}", prefix, self.path);
+
+        debug!(method = self.method.to_string(), path = &path, "API update",);
+
+        self.registration.register(&self.method, &path)?;
+        let handler = Handler::new(self.clone()).with_db(&self.db);
+        Ok(router.route(&path, on(mf, handler)))
+    }
+
+    /// apply_scopes adds scopes registered for the entity (also to when views)
+    fn apply_scopes(&mut self, scopes: &quickapi_filter::ScopeRegistry<S>) {
+        self.scopes.extend(&scopes.get::<E>());
+        self.when.apply_scopes(scopes);
+    }
+}
+
+/// Implementing ViewWrapResultTrait for UpdateView to handle JSON response wrapping
+impl<E, S, Ser> quickapi_view::ViewWrapResultTrait<S> for UpdateView<E, S, Ser>
+where
+    E: EntityTrait,
+    S: Clone + Send + Sync + 'static,
+    Ser: UpdateModelTrait<E> + Clone + 'static,
+    <E as EntityTrait>::Model:
+        IntoActiveModel<E::ActiveModel> + serde::Serialize + Send + Sync + 'static,
+    <E as EntityTrait>::ActiveModel: ActiveModelBehavior + Send,
+{
+    /// wrap_result_key method to set a custom key for the JSON response
+    fn wrap_result_key(mut self, key: impl Into<Key>) -> Self {
+        self.wrap_json_key = Some(key.into());
+        self
+    }
+
+    /// no_wrap_result_key method to disable wrapping the JSON response in a key
+    fn no_wrap_result_key(mut self) -> Self {
+        self.wrap_json_key = None;
+        self
+    }
+
+    /// get_wrap_result_key method to retrieve the key used for wrapping the JSON response
+    fn get_wrap_result_key(&self) -> Option<Key> {
+        self.wrap_json_key.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::{StatusCode, header};
+    use axum_test::TestServer;
+    use quickapi_view::RouterExt;
+    use sea_orm::entity::prelude::*;
+    use sea_orm::{Database, Schema};
+    use serde_json::json;
+
+    #[derive(
+        Clone, Debug, PartialEq, Eq, DeriveEntityModel, serde::Deserialize, serde::Serialize,
+    )]
+    #[sea_orm(table_name = "user")]
+    pub struct Model {
+        #[sea_orm(primary_key)]
+        pub id: i32,
+        pub username: String,
+        pub password: String,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+
+    // Public is output serializer without password
+    #[derive(Clone, serde::Serialize)]
+    struct Public {
+        id: i32,
+        username: String,
+    }
+
+    impl From<Model> for Public {
+        fn from(model: Model) -> Self {
+            Public {
+                id: model.id,
+                username: model.username,
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_if_match_round_trip() {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        let backend = db.get_database_backend();
+        db.execute(backend.build(&Schema::new(backend).create_table_from_entity(Entity)))
+            .await
+            .unwrap();
+        db.execute_unprepared(
+            r#"INSERT INTO "user" ("username", "password") VALUES ('joe', 'secret')"#,
+        )
+        .await
+        .unwrap();
+
+        let api = crate::new::<()>(db);
+        let router = api
+            .detail::<Entity>("/user/{id}", "id")
+            .unwrap()
+            .with_serializer::<Public>()
+            .with_link("self", "/user/{id}")
+            .unwrap()
+            .register_router(axum::Router::new())
+            .unwrap();
+        let router = api
+            .update::<Entity>("/user/{id}", "id")
+            .unwrap()
+            .with_output_serializer::<Public>()
+            .with_link("self", "/user/{id}")
+            .unwrap()
+            .register_router(router)
+            .unwrap();
+        let router = api
+            .delete::<Entity>("/user/{id}")
+            .unwrap()
+            .with_output_serializer::<Public>()
+            .with_link("self", "/user/{id}")
+            .unwrap()
+            .register_router(router)
+            .unwrap();
+        let server = TestServer::new(router).unwrap();
+
+        // ETag returned by detail view is accepted by update view
+        let etag = server.get("/user/1").await.header(header::ETAG);
+        let body = json!({"id": 1, "username": "anna", "password": "secret"});
+        let response = server
+            .put("/user/1")
+            .add_header(header::IF_MATCH, etag.clone())
+            .json(&body)
+            .await;
+        response.assert_status_ok();
+
+        // update responds with the same ETag as detail view for the updated object
+        let updated = response.header(header::ETAG);
+        assert_eq!(server.get("/user/1").await.header(header::ETAG), updated);
+
+        // stale ETag is rejected
+        server
+            .put("/user/1")
+            .add_header(header::IF_MATCH, etag.clone())
+            .json(&body)
+            .await
+            .assert_status(StatusCode::PRECONDITION_FAILED);
+        server
+            .delete("/user/1")
+            .add_header(header::IF_MATCH, etag)
+            .await
+            .assert_status(StatusCode::PRECONDITION_FAILED);
+
+        // current ETag is accepted by delete view
+        server
+            .delete("/user/1")
+            .add_header(header::IF_MATCH, updated)
+            .await
+            .assert_status(StatusCode::NO_CONTENT);
+    }
+
+    mod order {
+        use sea_orm::entity::prelude::*;
+
+        #[derive(
+            Clone, Debug, PartialEq, Eq, DeriveEntityModel, serde::Deserialize, serde::Serialize,
+        )]
+        #[sea_orm(table_name = "order")]
+        pub struct Model {
+            #[sea_orm(primary_key)]
+            pub id: i32,
+            pub note: String,
+            pub version: i32,
+        }
+
+        #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+        pub enum Relation {}
+
+        impl ActiveModelBehavior for ActiveModel {}
+    }
+
+    #[tokio::test]
+    async fn test_version_column() {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        let backend = db.get_database_backend();
+        db.execute(backend.build(&Schema::new(backend).create_table_from_entity(order::Entity)))
+            .await
+            .unwrap();
+        db.execute_unprepared(r#"INSERT INTO "order" ("note", "version") VALUES ('first', 1)"#)
+            .await
+            .unwrap();
+
+        let api = crate::new::<()>(db.clone());
+        let router = api
+            .detail::<order::Entity>("/order/{id}", "id")
+            .unwrap()
+            .with_etag_column("version")
+            .register_router(axum::Router::new())
+            .unwrap();
+        let router = api
+            .update::<order::Entity>("/order/{id}", "id")
+            .unwrap()
+            .with_etag_column("version")
+            .register_router(router)
+            .unwrap();
+        // row is modified by someone else after it's read by the update view
+        let concurrent = db.clone();
+        let router = api
+            .update::<order::Entity>(("/race/{id}", axum::http::Method::PATCH), "id")
+            .unwrap()
+            .with_etag_column("version")
+            .with_before_save(move |m: order::Model| {
+                let concurrent = concurrent.clone();
+                async move {
+                    concurrent
+                        .execute_unprepared(r#"UPDATE "order" SET "version" = "version" + 1"#)
+                        .await
+                        .unwrap();
+                    Ok(m)
+                }
+            })
+            .register_router(router)
+            .unwrap();
+        let server = TestServer::new(router).unwrap();
+
+        // two writes with the same ETag, the second one is rejected and version is incremented once
+        let etag = server.get("/order/1").await.header(header::ETAG);
+        let response = server
+            .put("/order/1")
+            .add_header(header::IF_MATCH, etag.clone())
+            .json(&json!({"id": 1, "note": "second", "version": 1}))
+            .await;
+        response.assert_status_ok();
+        response.assert_json(&json!({"object": {"id": 1, "note": "second", "version": 2}}));
+        assert_eq!(
+            server.get("/order/1").await.header(header::ETAG),
+            response.header(header::ETAG)
+        );
+        server
+            .put("/order/1")
+            .add_header(header::IF_MATCH, etag)
+            .json(&json!({"id": 1, "note": "third", "version": 1}))
+            .await
+            .assert_status(StatusCode::PRECONDITION_FAILED);
+
+        // version is set by the view, not by client
+        server
+            .put("/order/1")
+            .json(&json!({"id": 1, "note": "third", "version": 10}))
+            .await
+            .assert_json(&json!({"object": {"id": 1, "note": "third", "version": 3}}));
+
+        // update of row modified between read and write is not applied
+        server
+            .patch("/race/1")
+            .json(&json!({"id": 1, "note": "lost", "version": 3}))
+            .await
+            .assert_status(StatusCode::PRECONDITION_FAILED);
+        server
+            .get("/order/1")
+            .await
+            .assert_json(&json!({"object": {"id": 1, "note": "third", "version": 4}}));
+    }
+}
@@ -0,0 +1,823 @@
+/*
+ *  The MIT License (MIT)
+ *
+ *  Copyright (c) 2024-2025, Peter Vrba
+ *
+ *  Permission is hereby granted, free of charge, to any person obtaining a copy
+ *  of this software and associated documentation files (the "Software"), to deal
+ *  in the Software without restriction, including without limitation the rights
+ *  to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ *  copies of the Software, and to permit persons to whom the Software is
+ *  furnished to do so, subject to the following conditions:
+ *
+ *  The above copyright notice and this permission notice shall be included in
+ *  all copies or substantial portions of the Software.
+ *
+ *  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ *  IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ *  FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ *  AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ *  LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ *  OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+ *  THE SOFTWARE.
+ *
+ */
+
+use crate::view::handler::{Handler, check_when_max_body_size};
+use crate::view::links::{Link, Links};
+use crate::view::registry::{Registration, Routes};
+use axum::Router;
+use axum::http::Method;
+use axum::http::request::Parts;
+use axum::routing::on;
+use quickapi_filter::Scope;
+use quickapi_http::Response;
+use quickapi_http::etag::ETag;
+use quickapi_http::response::Key;
+use quickapi_http::serializer::OutputSerializerJson;
+use quickapi_lookup::Lookup;
+use quickapi_model::{Fields, ModelCallbackErased, ReplaceModel, UpdateModelTrait};
+use quickapi_permission::Permission;
+use quickapi_view::{Error, ViewTrait, as_method_filter};
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{
+    ActiveModelBehavior, ConnectionTrait, DatabaseConnection, DbErr, EntityTrait, IntoActiveModel,
+    TransactionTrait,
+};
+use serde::de::DeserializeOwned;
+use serde_json::json;
+use std::marker::PhantomData;
+use std::sync::Arc;
+use tracing::debug;
+
+const DEFAULT_JSON_KEY: &str = "object";
+
+/// UpsertView is a view that updates entity found by lookup, or creates it when it does not exist.
+/// Request body is deserialized by create serializer (Cr) or update serializer (Up, see UpdateModelTrait).
+/// Responds with `201 Created` when object was created, `200 OK` when it was updated.
+#[derive(Clone)]
+pub struct UpsertView<E, S, Cr = <E as EntityTrait>::Model, Up = ReplaceModel<E>>
+where
+    E: EntityTrait,
+    S: Clone + Send + Sync + 'static,
+    Cr: Clone + DeserializeOwned + Send + Sync + 'static,
+    Up: UpdateModelTrait<E> + Clone + 'static,
+    <E as EntityTrait>::Model: From<Cr> + Send + Sync + 'static,
+{
+    db: DatabaseConnection,
+    path: String,
+    method: Method,
+    registration: Registration,
+    when: quickapi_when::WhenViews<S>,
+    lookup: Arc<dyn Lookup<E, S>>,
+    on_conflict: Vec<E::Column>,
+    before_save: quickapi_model::ModelCallbacks<E, S>,
+    after_save: quickapi_model::ModelCallbacks<E, S>,
+    scopes: quickapi_filter::Scopes<E, S>,
+    soft_delete: Option<quickapi_filter::SoftDelete>,
+    etag: ETag,
+    permissions: quickapi_permission::Permissions<E, S>,
+    fallback: bool,
+    transaction: bool,
+    max_body_size: usize,
+    output: OutputSerializerJson<E::Model>,
+    fields: Fields<E>,
+    links: Links<E>,
+    wrap_json_key: Option<Key>,
+    _phantom_data: PhantomData<(E, S, Cr, Up)>,
+}
+
+impl<E, S, Cr, Up> UpsertView<E, S, Cr, Up>
+where
+    E: EntityTrait,
+    S: Clone + Send + Sync + 'static,
+    Cr: Clone + DeserializeOwned + Send + Sync + 'static,
+    Up: UpdateModelTrait<E> + Clone + 'static,
+    <E as EntityTrait>::Model: From<Cr> + Send + Sync + 'static,
+{
+    /// new creates a new UpsertView instance.
+    pub(crate) fn new(
+        db: DatabaseConnection,
+        path: impl Into<String>,
+        method: Method,
+        lookup: impl Lookup<E, S> + 'static,
+    ) -> Self
+    where
+        <E as EntityTrait>::Model: serde::Serialize,
+    {
+        Self {
+            db,
+            path: path.into(),
+            method,
+            registration: Registration::new::<E>("upsert"),
+            when: Default::default(),
+            lookup: Arc::new(lookup),
+            on_conflict: Vec::new(),
+            before_save: Default::default(),
+            after_save: Default::default(),
+            scopes: quickapi_filter::Scopes::new(),
+            soft_delete: None,
+            etag: Default::default(),
+            permissions: Default::default(),
+            fallback: false,
+            transaction: false,
+            max_body_size: crate::quickapi::DEFAULT_MAX_BODY_SIZE,
+            output: Default::default(),
+            fields: Default::default(),
+            links: Default::default(),
+            wrap_json_key: Some(DEFAULT_JSON_KEY.into()),
+            _phantom_data: PhantomData,
+        }
+    }
+
+    /// with_name sets name of the route in QuickApi routes (`{table}-{kind}` by default, e.g. `user-upsert`).
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.registration = self.registration.with_name(name);
+        self
+    }
+
+    /// with_routes sets registry of routes the view is recorded into when registered.
+    pub(crate) fn with_routes(mut self, routes: Routes) -> Self {
+        self.registration = self.registration.with_routes(routes);
+        self
+    }
+
+    /// with_create_serializer sets custom serializer used when object does not exist (same as in CreateView).
+    pub fn with_create_serializer<Serializer>(self) -> UpsertView<E, S, Serializer, Up>
+    where
+        Serializer: Clone + DeserializeOwned + Send + Sync + 'static,
+        <E as EntityTrait>::Model: From<Serializer>,
+    {
+        UpsertView {
+            db: self.db,
+            path: self.path,
+            method: self.method,
+            registration: self.registration,
+            when: self.when,
+            lookup: self.lookup,
+            on_conflict: self.on_conflict,
+            before_save: self.before_save,
+            after_save: self.after_save,
+            scopes: self.scopes,
+            soft_delete: self.soft_delete,
+            etag: self.etag,
+            permissions: self.permissions,
+            fallback: false,
+            transaction: self.transaction,
+            max_body_size: self.max_body_size,
+            output: self.output,
+            fields: self.fields,
+            links: self.links,
+            wrap_json_key: self.wrap_json_key,
+            _phantom_data: PhantomData,
+        }
+    }
+
+    /// with_update_serializer sets custom serializer used when object exists (same as in UpdateView).
+    pub fn with_update_serializer<Serializer>(self) -> UpsertView<E, S, Cr, Serializer>
+    where
+        Serializer: UpdateModelTrait<E> + Clone + 'static,
+    {
+        UpsertView {
+            db: self.db,
+            path: self.path,
+            method: self.method,
+            registration: self.registration,
+            when: self.when,
+            lookup: self.lookup,
+            on_conflict: self.on_conflict,
+            before_save: self.before_save,
+            after_save: self.after_save,
+            scopes: self.scopes,
+            soft_delete: self.soft_delete,
+            etag: self.etag,
+            permissions: self.permissions,
+            fallback: false,
+            transaction: self.transaction,
+            max_body_size: self.max_body_size,
+            output: self.output,
+            fields: self.fields,
+            links: self.links,
+            wrap_json_key: self.wrap_json_key,
+            _phantom_data: PhantomData,
+        }
+    }
+
+    /// with_output_serializer sets serializer of saved object in response (create and update serializers are kept).
+    pub fn with_output_serializer<O>(mut self) -> Self
+    where
+        O: From<E::Model> + serde::Serialize,
+    {
+        self.output = OutputSerializerJson::new::<O>();
+        self
+    }
+
+    /// with_write_only marks columns as write only, they are never returned in response.
+    pub fn with_write_only(mut self, columns: impl IntoIterator<Item = E::Column>) -> Self {
+        self.fields = self.fields.with_write_only(columns);
+        self
+    }
+
+    /// with_link adds named link (e.g. self, related resource) to serialized object as HAL `_links`,
+    /// path parameters of the link are resolved from model columns (e.g. `/api/user/{id}`).
+    pub fn with_link(
+        mut self,
+        name: impl Into<String>,
+        link: impl Into<Link>,
+    ) -> Result<Self, crate::Error> {
+        self.links.push(name, link)?;
+        Ok(self)
+    }
+
+    /// with_lookup sets the lookup for the UpsertView.
+    pub fn with_lookup(mut self, lookup: impl Lookup<E, S> + 'static) -> Self {
+        self.lookup = Arc::new(lookup);
+        self
+    }
+
+    /// with_on_conflict sets unique key columns used by the lookup.
+    /// Insert is then done with `ON CONFLICT (columns) DO NOTHING`, conflicting row is looked up again
+    /// within scopes and updated, row out of scope (or soft deleted) responds with 409 Conflict.
+    pub fn with_on_conflict(mut self, columns: impl IntoIterator<Item = E::Column>) -> Self {
+        self.on_conflict = columns.into_iter().collect();
+        self
+    }
+
+    /// with_before_save sets a before save handler for the UpsertView (called on create and update).
+    pub fn with_before_save<T>(
+        mut self,
+        before_save: impl quickapi_model::ModelCallback<E, S, T> + Clone + Send + Sync + 'static,
+    ) -> Self
+    where
+        T: Send + Sync + 'static,
+    {
+        self.before_save.push(before_save);
+        self
+    }
+
+    /// remove all before save handlers from the UpsertView.
+    pub fn clear_before_save(mut self) -> Self {
+        self.before_save.clear();
+        self
+    }
+
+    /// with_after_save sets an after save handler for the UpsertView (called on create and update).
+    /// It's called with the saved object, returned object is used in response.
+    pub fn with_after_save<T>(
+        mut self,
+        after_save: impl quickapi_model::ModelCallback<E, S, T> + Clone + Send + Sync + 'static,
+    ) -> Self
+    where
+        T: Send + Sync + 'static,
+    {
+        self.after_save.push(after_save);
+        self
+    }
+
+    /// remove all after save handlers from the UpsertView.
+    pub fn clear_after_save(mut self) -> Self {
+        self.after_save.clear();
+        self
+    }
+
+    /// with_scope adds a row-level scope to the UpsertView.
+    /// Objects out of scope are not found, scoped columns are force-set on the saved instance.
+    pub fn with_scope(mut self, scope: impl Scope<E, S> + 'static) -> Self {
+        self.scopes.push(scope);
+        self
+    }
+
+    /// with_soft_delete marks entity as soft deletable by column, soft deleted rows are not updated.
+    pub fn with_soft_delete(mut self, column: &'static str) -> Self {
+        self.soft_delete = Some(quickapi_filter::SoftDelete::new(column));
+        self
+    }
+
+    /// with_etag_column sets integer version column used for ETag (by default ETag is hash of serialized object).
+    /// Version column is incremented on every update, update of row modified meanwhile fails with 412.
+    pub fn with_etag_column(mut self, column: &'static str) -> Self {
+        self.etag = ETag::Column(column);
+        self
+    }
+
+    /// with_permission adds a permission to the UpsertView.
+    /// Object permission is checked on the looked up object and on the instance before it's saved.
+    pub fn with_permission(mut self, permission: impl Permission<E, S> + 'static) -> Self {
+        self.permissions.push(permission);
+        self
+    }
+
+    /// with_max_body_size sets the maximum size of request body (0 means no limit).
+    pub fn with_max_body_size(mut self, max_body_size: usize) -> Self {
+        self.max_body_size = max_body_size;
+        self
+    }
+
+    /// with_transaction runs the whole UpsertView (callbacks and queries) in one database transaction.
+    /// Transaction is committed on success response, rolled back otherwise.
+    pub fn with_transaction(mut self, transaction: bool) -> Self {
+        self.transaction = transaction;
+        self
+    }
+
+    /// with_fallback sets a fallback UpsertView that will be used if when conditions are not met.
+    pub fn with_fallback(mut self, fallback: bool) -> Self {
+        self.fallback = fallback;
+        self
+    }
+
+    /// when adds a condition to the UpsertView.
+    /// Closure receives clone of the view (without when conditions) and can change
+    /// serializers, callbacks and other settings.
+    pub fn when<F, T, CrSerializer, UpSerializer>(
+        mut self,
+        _when: impl quickapi_when::When<S, T> + Clone + Sync + 'static,
+        _f: F,
+    ) -> Result<Self, crate::Error>
+    where
+        CrSerializer: Clone + DeserializeOwned + Send + Sync + 'static,
+        UpSerializer: UpdateModelTrait<E> + Clone + 'static,
+        <E as EntityTrait>::Model:
+            From<CrSerializer> + IntoActiveModel<E::ActiveModel> + serde::Serialize,
+        <E as EntityTrait>::ActiveModel: ActiveModelBehavior + Send,
+        F: Fn(
+            UpsertView<E, S, Cr, Up>,
+        ) -> Result<UpsertView<E, S, CrSerializer, UpSerializer>, crate::Error>,
+        T: Sync + Send + 'static,
+    {
+        let mut clone = self.clone();
+        clone.when = Default::default();
+        let _result = _f(clone)?;
+        check_when_max_body_size(self.max_body_size, _result.max_body_size)?;
+        self.when.add_when(_when, _result);
+        Ok(self)
+    }
+
+    /// on_conflict_clause returns `ON CONFLICT (columns) DO NOTHING` clause for insert.
+    /// Conflicting row is never updated by insert, it's looked up again within scopes instead.
+    fn on_conflict_clause(&self) -> Option<OnConflict> {
+        if self.on_conflict.is_empty() {
+            return None;
+        }
+
+        Some(
+            OnConflict::columns(self.on_conflict.clone())
+                .do_nothing()
+                .to_owned(),
+        )
+    }
+}
+
+/// UpsertView implementation of the request pipeline.
+impl<E, S, Cr, Up> UpsertView<E, S, Cr, Up>
+where
+    E: EntityTrait,
+    S: Clone + Send + Sync + 'static,
+    Cr: Clone + DeserializeOwned + Send + Sync + 'static,
+    Up: UpdateModelTrait<E> + Clone + 'static,
+    <E as EntityTrait>::Model:
+        From<Cr> + IntoActiveModel<E::ActiveModel> + serde::Serialize + Send + Sync + 'static,
+    <E as EntityTrait>::ActiveModel: ActiveModelBehavior + Send,
+{
+    /// handle runs the view pipeline on given connection (database or transaction).
+    async fn handle<C>(
+        &self,
+        conn: &C,
+        _parts: &mut Parts,
+        _state: &S,
+        _body: &bytes::Bytes,
+    ) -> Result<Response, Error>
+    where
+        C: ConnectionTrait + TransactionTrait,
+    {
+        let mut parts = _parts.clone();
+
+        // check permissions before anything else
+        self.permissions.has_permission(&mut parts, _state).await?;
+
+        // restrict rows by scopes, soft deleted rows are not updated
+        let mut query = self
+            .scopes
+            .scope_select(&mut parts, _state, E::find())
+            .await?;
+        if let Some(soft_delete) = &self.soft_delete {
+            query = soft_delete.exclude_deleted(query)?;
+        }
+
+        // prepare lookup
+        let query = self
+            .lookup
+            .lookup(&mut parts, _state, query)
+            .await
+            .map_err(|e| Error::InternalError(Box::new(e)))?;
+
+        let original = match query.clone().one(conn).await? {
+            Some(original) => original,
+            None => {
+                // deserialize the body into the model (via the create serializer)
+                let instance: E::Model = match serde_json::from_slice::<Cr>(_body) {
+                    Ok(instance) => instance.into(),
+                    Err(e) => return Ok(bad_request(e.to_string())),
+                };
+
+                let instance = self.prepare(&mut parts, _state, instance).await?;
+
+                let mut insert = E::insert(quickapi_model::insert_active_model::<E>(instance));
+                if let Some(on_conflict) = self.on_conflict_clause() {
+                    insert = insert.on_conflict(on_conflict);
+                }
+
+                match insert.exec_with_returning(conn).await {
+                    Ok(created) => {
+                        return self
+                            .respond(&mut parts, _state, created, axum::http::StatusCode::CREATED)
+                            .await;
+                    }
+                    // conflicting row was created meanwhile, update it when it's within scopes
+                    Err(DbErr::RecordNotFound(_) | DbErr::RecordNotInserted)
+                        if !self.on_conflict.is_empty() =>
+                    {
+                        match query.one(conn).await? {
+                            Some(original) => original,
+                            None => return Ok(conflict()),
+                        }
+                    }
+                    Err(err) => return Err(err.into()),
+                }
+            }
+        };
+
+        // check object permissions on the looked up object
+        self.permissions
+            .has_object_permission(&mut parts, _state, &original)
+            .await?;
+
+        // object must not be modified since client has read it (same representation as in response)
+        if parts.headers.contains_key(axum::http::header::IF_MATCH) {
+            let serialized = self.represent(original.clone())?;
+            let etag = self
+                .etag
+                .compute::<E>(&original, &serialized)
+                .map_err(|e| Error::InternalError(Box::new(e)))?;
+            if !quickapi_http::etag::if_match(&parts.headers, &etag) {
+                return Ok(quickapi_http::etag::precondition_failed());
+            }
+        }
+
+        // update the model by update serializer
+        let instance = match serde_json::from_slice::<Up>(_body)
+            .map_err(|e| e.to_string())
+            .and_then(|s| s.update(original.clone()).map_err(|e| e.to_string()))
+        {
+            Ok(instance) => instance,
+            Err(message) => return Ok(bad_request(message)),
+        };
+
+        let instance = self.prepare(&mut parts, _state, instance).await?;
+
+        // update the instance in the database, versioned row only when it was not modified meanwhile
+        let active = quickapi_model::update_active_model::<E>(&original, instance);
+        let version = self
+            .etag
+            .version_column::<E>()
+            .map_err(|e| Error::InternalError(Box::new(e)))?;
+        let updated = match version {
+            Some(version) => {
+                match quickapi_model::update_versioned::<E, _>(conn, &original, active, version)
+                    .await?
+                {
+                    Some(updated) => updated,
+                    None => return Ok(quickapi_http::etag::precondition_failed()),
+                }
+            }
+            None => E::update(active).exec(conn).await?,
+        };
+
+        self.respond(&mut parts, _state, updated, axum::http::StatusCode::OK)
+            .await
+    }
+
+    /// respond runs after save handlers and responds with saved object.
+    async fn respond(
+        &self,
+        parts: &mut Parts,
+        state: &S,
+        saved: E::Model,
+        status: axum::http::StatusCode,
+    ) -> Result<Response, Error> {
+        // apply after_save handlers
+        let saved = self
+            .after_save
+            .call(parts, state, saved)
+            .await
+            .map_err(|e| Error::InternalError(Box::new(e)))?;
+
+        let serialized = self.represent(saved.clone())?;
+        let etag = self
+            .etag
+            .compute::<E>(&saved, &serialized)
+            .map_err(|e| Error::InternalError(Box::new(e)))?;
+
+        let object = match &self.wrap_json_key {
+            Some(key) => serde_json::Value::Object(serde_json::Map::from_iter(vec![(
+                Into::<String>::into(key.clone()),
+                serialized,
+            )])),
+            None => serialized,
+        };
+
+        Ok(Response::new(object)
+            .with_status(status)
+            .with_header(axum::http::header::ETAG, &etag))
+    }
+
+    /// represent serializes object as it's returned in response (output serializer, links, write only
+    /// fields stripped), ETag is computed from it, so it matches ETag of DetailView with same output.
+    fn represent(&self, object: E::Model) -> Result<serde_json::Value, Error> {
+        let links = self.links.render(&object);
+        let mut serialized = self
+            .output
+            .serialize_json(object)
+            .map_err(|e| Error::InternalError(Box::new(e)))?;
+        Links::<E>::attach(&mut serialized, links);
+        self.fields.strip_output(&mut serialized);
+        Ok(serialized)
+    }
+
+    /// prepare runs before save handlers, forces scoped columns and checks object permissions
+    /// on the instance before it's saved.
+    async fn prepare(
+        &self,
+        parts: &mut Parts,
+        state: &S,
+        instance: E::Model,
+    ) -> Result<E::Model, Error> {
+        let instance = self
+            .before_save
+            .call(parts, state, instance)
+            .await
+            .map_err(|e| Error::InternalError(Box::new(e)))?;
+
+        let instance = self.scopes.scope_model(parts, state, instance).await?;
+
+        self.permissions
+            .has_object_permission(parts, state, &instance)
+            .await?;
+
+        Ok(instance)
+    }
+}
+
+/// bad_request returns 400 response with given message.
+fn bad_request(message: String) -> Response {
+    Response::new(json!({
+        "error": "Bad Request",
+        "message": message,
+    }))
+    .with_status(axum::http::StatusCode::BAD_REQUEST)
+}
+
+/// conflict returns 409 response when object exists, but it's out of scope (or soft deleted).
+fn conflict() -> Response {
+    Response::new(json!({
+        "error": "Conflict",
+        "message": "The resource already exists and cannot be modified.",
+    }))
+    .with_status(axum::http::StatusCode::CONFLICT)
+}
+
+/// Implement the ViewTrait for UpsertView, which defines how the view handles requests.
+#[async_trait::async_trait]
+impl<E, S, Cr, Up> ViewTrait<S> for UpsertView<E, S, Cr, Up>
+where
+    E: EntityTrait,
+    S: Clone + Send + Sync + 'static,
+    Cr: Clone + DeserializeOwned + Send + Sync + 'static,
+    Up: UpdateModelTrait<E> + Clone + 'static,
+    <E as EntityTrait>::Model:
+        From<Cr> + IntoActiveModel<E::ActiveModel> + serde::Serialize + Send + Sync + 'static,
+    <E as EntityTrait>::ActiveModel: ActiveModelBehavior + Send,
+{
+    async fn handle_view(
+        &self,
+        _parts: &mut Parts,
+        _state: &S,
+        _body: &bytes::Bytes,
+    ) -> Result<Response, Error> {
+        if !self.transaction {
+            return self.handle(&self.db, _parts, _state, _body).await;
+        }
+
+        // run the whole pipeline in one transaction, rolled back on any error
+        let transaction = crate::view::transaction::begin(&self.db, _parts).await?;
+        let response = match self.handle(&*transaction, _parts, _state, _body).await {
+            Ok(response) => response,
+            Err(err) => {
+                crate::view::transaction::discard(transaction, _parts);
+                return Err(err);
+            }
+        };
+        crate::view::transaction::finish(transaction, _parts, response).await
+    }
+
+    /// get_when_views returns a vector of views that should be executed when the UpsertView is called.
+    async fn get_when_views<'a>(
+        &'a self,
+        _parts: &'a mut Parts,
+        _state: &'a S,
+    ) -> Result<Vec<&'a (dyn ViewTrait<S> + Send + Sync)>, Error> {
+        self.when
+            .get_views(_parts, _state)
+            .await
+            .map_err(Into::into)
+    }
+
+    /// has_fallback returns true if the UpsertView has a fallback defined (if when does not matches).
+    fn has_fallback(&self) -> bool {
+        self.fallback
+    }
+
+    /// max_body_size returns the maximum size of request body for the UpsertView.
+    fn max_body_size(&self) -> Option<usize> {
+        Some(self.max_body_size)
+    }
+}
+
+/// UpsertView implementation for registering the view with an axum router.
+impl<E, S, Cr, Up> quickapi_view::RouterExt<S> for UpsertView<E, S, Cr, Up>
+where
+    E: EntityTrait,
+    S: Clone + Send + Sync + 'static,
+    Cr: Clone + DeserializeOwned + Send + Sync + 'static,
+    Up: UpdateModelTrait<E> + Clone + 'static,
+    <E as EntityTrait>::Model:
+        From<Cr> + IntoActiveModel<E::ActiveModel> + serde::Serialize + Send + Sync + 'static,
+    <E as EntityTrait>::ActiveModel: ActiveModelBehavior + Send,
+{
+    fn register_router_with_prefix(
+        &self,
+        router: Router<S>,
+        prefix: &str,
+    ) -> Result<Router<S>, Error> {
+        let mf = as_method_filter(&self.method)?;
+        let path = format!("{}{}", prefix, self.path);
+
+        debug!(method = self.method.to_string(), path = &path, "API upsert",);
+
+        self.registration.register(&self.method, &path)?;
+        let handler = Handler::new(self.clone()).with_db(&self.db);
+        Ok(router.route(&path, on(mf, handler)))
+    }
+
+    /// apply_scopes adds scopes registered for the entity (also to when views)
+    fn apply_scopes(&mut self, scopes: &quickapi_filter::ScopeRegistry<S>) {
+        self.scopes.extend(&scopes.get::<E>());
+        self.when.apply_scopes(scopes);
+    }
+}
+
+/// Implementing ViewWrapResultTrait for UpsertView to handle JSON response wrapping
+impl<E, S, Cr, Up> quickapi_view::ViewWrapResultTrait<S> for UpsertView<E, S, Cr, Up>
+where
+    E: EntityTrait,
+    S: Clone + Send + Sync + 'static,
+    Cr: Clone + DeserializeOwned + Send + Sync + 'static,
+    Up: UpdateModelTrait<E> + Clone + 'static,
+    <E as EntityTrait>::Model:
+        From<Cr> + IntoActiveModel<E::ActiveModel> + serde::Serialize + Send + Sync + 'static,
+    <E as EntityTrait>::ActiveModel: ActiveModelBehavior + Send,
+{
+    /// wrap_result_key method to set a custom key for the JSON response
+    fn wrap_result_key(mut self, key: impl Into<Key>) -> Self {
+        self.wrap_json_key = Some(key.into());
+        self
+    }
+
+    /// no_wrap_result_key method to disable wrapping the JSON response in a key
+    fn no_wrap_result_key(mut self) -> Self {
+        self.wrap_json_key = None;
+        self
+    }
+
+    /// get_wrap_result_key method to retrieve the key used for wrapping the JSON response
+    fn get_wrap_result_key(&self) -> Option<Key> {
+        self.wrap_json_key.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sea_orm::entity::prelude::*;
+    use sea_orm::sea_query::{PostgresQueryBuilder, Query};
+
+    #[derive(
+        Clone, Debug, PartialEq, Eq, DeriveEntityModel, serde::Deserialize, serde::Serialize,
+    )]
+    #[sea_orm(table_name = "partner")]
+    pub struct Model {
+        #[sea_orm(primary_key)]
+        pub id: i32,
+        pub external_id: String,
+        pub name: String,
+        pub tenant_id: i32,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+
+    // view prepares upsert view with given conflict columns
+    fn view(columns: Vec<Column>) -> UpsertView<Entity, ()> {
+        UpsertView::new(
+            DatabaseConnection::Disconnected,
+            "/partner/{external_id}",
+            Method::PUT,
+            "external_id",
+        )
+        .with_on_conflict(columns)
+    }
+
+    #[test]
+    fn test_on_conflict_clause() {
+        assert!(view(vec![]).on_conflict_clause().is_none());
+
+        let on_conflict = view(vec![Column::ExternalId]).on_conflict_clause().unwrap();
+        let sql = Query::insert()
+            .into_table(Entity)
+            .columns([Column::ExternalId, Column::Name])
+            .values_panic(["x".into(), "y".into()])
+            .on_conflict(on_conflict)
+            .to_string(PostgresQueryBuilder);
+        assert!(
+            sql.ends_with(r#"ON CONFLICT ("external_id") DO NOTHING"#),
+            "{sql}"
+        );
+    }
+
+    #[derive(Clone)]
+    struct Tenant(i32);
+
+    #[tokio::test]
+    async fn test_scoped_conflict() {
+        use axum_test::TestServer;
+        use quickapi_view::RouterExt;
+
+        let db = sea_orm::Database::connect("sqlite::memory:").await.unwrap();
+        db.execute_unprepared(
+            r#"CREATE TABLE "partner" (
+                   "id" integer PRIMARY KEY,
+                   "external_id" text NOT NULL UNIQUE,
+                   "name" text NOT NULL,
+                   "tenant_id" integer NOT NULL
+               );
+               INSERT INTO "partner" ("external_id", "name", "tenant_id") VALUES ('x', 'other', 2);"#,
+        )
+        .await
+        .unwrap();
+
+        let view = UpsertView::<Entity, ()>::new(
+            db.clone(),
+            "/partner/{external_id}",
+            Method::PUT,
+            quickapi_lookup::Map::default().with(
+                "external_id",
+                quickapi_lookup::Value::Path("external_id".into()),
+            ),
+        )
+        .with_on_conflict([Column::ExternalId])
+        .with_scope(quickapi_filter::ExtensionScope::<Entity, Tenant>::new(
+            Column::TenantId,
+            |tenant: &Tenant| tenant.0,
+        ));
+        let router = view
+            .register_router(axum::Router::new())
+            .unwrap()
+            .layer(axum::Extension(Tenant(1)));
+        let server = TestServer::new(router).unwrap();
+
+        let body = |external_id: &str| json!({"id": 0, "external_id": external_id, "name": "mine", "tenant_id": 0});
+
+        // row of other tenant with the same unique key is neither updated nor taken over
+        server
+            .put("/partner/x")
+            .json(&body("x"))
+            .await
+            .assert_status(axum::http::StatusCode::CONFLICT);
+        let other = Entity::find_by_id(1).one(&db).await.unwrap().unwrap();
+        assert_eq!((other.name.as_str(), other.tenant_id), ("other", 2));
+
+        // row within scope is created, then updated
+        let response = server.put("/partner/y").json(&body("y")).await;
+        response.assert_status(axum::http::StatusCode::CREATED);
+        response.assert_json(
+            &json!({"object": {"id": 2, "external_id": "y", "name": "mine", "tenant_id": 1}}),
+        );
+        server
+            .put("/partner/y")
+            .json(&body("y"))
+            .await
+            .assert_status_ok();
+    }
+}